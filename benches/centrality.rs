@@ -0,0 +1,47 @@
+//! Benchmarks of loading a network and of the centrality measures
+//!
+//! The networks are generated from a fixed seed, so timings are comparable between
+//! runs. Throughput is reported in elements per second, where an element is an edge.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use citation_network_analysis::centrality::degree_centrality::calculate_degree_centrality;
+use citation_network_analysis::centrality::pagerank_centrality::PageRankConfig;
+use citation_network_analysis::generators::{edge_list, random_edges};
+use citation_network_analysis::network::CitationNetwork;
+
+const NUM_NODES: usize = 20_000;
+const NUM_EDGES: usize = 100_000;
+const SEED: u64 = 42;
+/// The number of PageRank iterations, fixed so that every run does the same work
+const PAGERANK_ITERATIONS: usize = 20;
+
+fn bench_load(c: &mut Criterion) {
+    let text = edge_list(&random_edges(NUM_NODES, NUM_EDGES, SEED));
+    let mut group = c.benchmark_group("load");
+    group.throughput(Throughput::Elements(NUM_EDGES as u64));
+    group.bench_function("load_from_reader", |b| {
+        b.iter(|| CitationNetwork::load_from_reader(black_box(text.as_bytes())))
+    });
+    group.finish();
+}
+
+fn bench_centrality(c: &mut Criterion) {
+    let text = edge_list(&random_edges(NUM_NODES, NUM_EDGES, SEED));
+    let network = CitationNetwork::load_from_reader(text.as_bytes());
+    let pagerank = PageRankConfig::new()
+        .max_iterations(PAGERANK_ITERATIONS)
+        .tolerance(0.0);
+    let mut group = c.benchmark_group("centrality");
+    group.throughput(Throughput::Elements(network.num_edges() as u64));
+    group.bench_function("degree", |b| {
+        b.iter(|| calculate_degree_centrality(black_box(&network)))
+    });
+    group.bench_function("pagerank", |b| {
+        b.iter(|| pagerank.calculate(black_box(&network)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_load, bench_centrality);
+criterion_main!(benches);
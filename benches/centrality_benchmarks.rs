@@ -0,0 +1,115 @@
+//! Benchmarks comparing the centrality measures on networks of different sizes
+//!
+//! Every measure is timed on Zachary's karate club, on two generated networks, of
+//! 100 and 10k nodes, and on the full HepTh network when `data/cit-HepTh.txt` is
+//! present. Each measure is reported twice, once with throughput in nodes per
+//! second and once in edges per second. Betweenness and harmonic centrality take
+//! `O(V E)` time, so they are not run on HepTh. Harmonic centrality stands in for
+//! closeness, which is not implemented. The number of PageRank iterations is
+//! benchmarked separately.
+
+use std::fs::File;
+use std::path::Path;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use citation_network_analysis::centrality::betweenness_centrality::calculate_betweenness_centrality;
+use citation_network_analysis::centrality::degree_centrality::calculate_degree_centrality;
+use citation_network_analysis::centrality::harmonic_centrality::calculate_harmonic_centrality;
+use citation_network_analysis::centrality::pagerank_centrality::{
+    calculate_pagerank_centrality, PageRankConfig,
+};
+use citation_network_analysis::example_graphs::zachary_like;
+use citation_network_analysis::generators::random_edges;
+use citation_network_analysis::network::{CitationNetwork, Direction};
+
+const SEED: u64 = 42;
+/// The average number of references per paper in the generated networks
+const EDGES_PER_NODE: usize = 5;
+/// The numbers of PageRank iterations to compare
+const PAGERANK_ITERATIONS: [usize; 4] = [1, 10, 20, 50];
+
+/// Returns the networks to benchmark on, with their names
+fn networks() -> Vec<(&'static str, CitationNetwork)> {
+    let generated = |n: usize| {
+        let mut network = CitationNetwork::new();
+        for (from, to) in random_edges(n, n * EDGES_PER_NODE, SEED) {
+            network.add_edge(from, to);
+        }
+        network
+    };
+    let mut networks = vec![
+        ("karate", zachary_like()),
+        ("100", generated(100)),
+        ("10k", generated(10_000)),
+    ];
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("data/cit-HepTh.txt");
+    match File::open(&path) {
+        Ok(file) => networks.push(("hepth", CitationNetwork::load_from_file(file))),
+        Err(error) => eprintln!("skipping HepTh: {}: {}", path.display(), error),
+    }
+    networks
+}
+
+/// Benchmarks a measure on every network, in nodes and in edges per second
+fn bench_measure<F: Fn(&CitationNetwork)>(
+    c: &mut Criterion,
+    name: &str,
+    networks: &[(&str, CitationNetwork)],
+    measure: F,
+) {
+    let mut group = c.benchmark_group(name);
+    for (size, network) in networks {
+        group.throughput(Throughput::Elements(network.size() as u64));
+        group.bench_with_input(BenchmarkId::new("nodes", size), network, |b, network| {
+            b.iter(|| measure(black_box(network)))
+        });
+        group.throughput(Throughput::Elements(network.num_edges() as u64));
+        group.bench_with_input(BenchmarkId::new("edges", size), network, |b, network| {
+            b.iter(|| measure(black_box(network)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_centrality(c: &mut Criterion) {
+    let networks = networks();
+    let small = &networks[..3];
+    bench_measure(c, "degree", &networks, |network| {
+        calculate_degree_centrality(network);
+    });
+    bench_measure(c, "pagerank", &networks, |network| {
+        calculate_pagerank_centrality(network);
+    });
+    bench_measure(c, "betweenness", small, |network| {
+        calculate_betweenness_centrality(network);
+    });
+    bench_measure(c, "harmonic", small, |network| {
+        calculate_harmonic_centrality(network, Direction::Incoming);
+    });
+}
+
+fn bench_pagerank_iterations(c: &mut Criterion) {
+    let networks = networks();
+    let (_, network) = &networks[2];
+    let mut group = c.benchmark_group("pagerank_iterations");
+    group.throughput(Throughput::Elements(network.num_edges() as u64));
+    for iterations in PAGERANK_ITERATIONS {
+        let config = PageRankConfig::new()
+            .max_iterations(iterations)
+            .tolerance(0.0);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(iterations),
+            &config,
+            |b, config| b.iter(|| config.calculate(black_box(network)).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench_centrality, bench_pagerank_iterations
+}
+criterion_main!(benches);
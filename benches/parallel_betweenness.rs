@@ -0,0 +1,52 @@
+//! Benchmarks of betweenness centrality on several threads
+//!
+//! The parallel betweenness is timed on a generated network of 5k nodes with thread
+//! pools of different sizes, next to the sequential version. With enough cores the
+//! time falls nearly in proportion to the number of threads, since the sources are
+//! independent and the partial scores are only added up at the end.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use citation_network_analysis::centrality::betweenness_centrality::{
+    calculate_betweenness_centrality, calculate_betweenness_parallel,
+};
+use citation_network_analysis::generators::random_edges;
+use citation_network_analysis::network::CitationNetwork;
+
+const SEED: u64 = 42;
+const NUM_NODES: usize = 5_000;
+/// The average number of references per paper in the generated network
+const EDGES_PER_NODE: usize = 5;
+/// The numbers of threads to compare
+const NUM_THREADS: [usize; 4] = [1, 2, 4, 8];
+
+fn bench_parallel_betweenness(c: &mut Criterion) {
+    let network: CitationNetwork = random_edges(NUM_NODES, NUM_NODES * EDGES_PER_NODE, SEED)
+        .into_iter()
+        .collect();
+    let mut group = c.benchmark_group("parallel_betweenness");
+    group.bench_function("sequential", |b| {
+        b.iter(|| calculate_betweenness_centrality(black_box(&network)))
+    });
+    for num_threads in NUM_THREADS {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("threads", num_threads),
+            &network,
+            |b, network| {
+                b.iter(|| pool.install(|| calculate_betweenness_parallel(black_box(network))))
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench_parallel_betweenness
+}
+criterion_main!(benches);
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use crate::network::weighted::WeightedCitationNetwork;
+use crate::network::{CitationNetwork, Direction};
+
+/// Counts, for every pair of distinct papers, the nodes adjacent to both of them
+///
+/// Each node contributes one to every pair of distinct papers it is joined to in the
+/// given direction, so repeated citations and self-citations are not counted. The
+/// pairs are keyed with the smaller id first.
+fn shared_neighbor_counts(
+    network: &CitationNetwork,
+    direction: Direction,
+) -> HashMap<(usize, usize), usize> {
+    let mut counts = HashMap::new();
+    for vertex in network.nodes_sorted() {
+        let mut adjacent = network.neighbors_in(vertex, direction);
+        adjacent.retain(|&other| other != vertex);
+        adjacent.sort_unstable();
+        adjacent.dedup();
+        for (i, &a) in adjacent.iter().enumerate() {
+            for &b in &adjacent[i + 1..] {
+                *counts.entry((a, b)).or_insert(0) += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// Builds the network joining the pairs counted at least `min_count` times
+///
+/// Every node of the original network is kept, and each pair is joined in both
+/// directions with its count as the weight.
+fn pair_network(
+    network: &CitationNetwork,
+    direction: Direction,
+    min_count: usize,
+) -> WeightedCitationNetwork {
+    assert!(min_count >= 1, "min_count must be at least 1");
+    let mut derived = WeightedCitationNetwork::new();
+    for vertex in network.nodes_sorted() {
+        derived.add_node(vertex);
+    }
+    let mut pairs: Vec<((usize, usize), usize)> = shared_neighbor_counts(network, direction)
+        .into_iter()
+        .filter(|&(_, count)| count >= min_count)
+        .collect();
+    pairs.sort_unstable();
+    for ((a, b), count) in pairs {
+        derived.add_edge(a, b, count as f64);
+        derived.add_edge(b, a, count as f64);
+    }
+    derived
+}
+
+/// Returns the co-citation network of a network
+///
+/// Two papers are co-cited when some paper cites both of them. The derived network
+/// joins every pair of papers co-cited by at least `min_count` papers, with the
+/// number of co-citing papers as the weight. Since citation networks are directed,
+/// each pair is joined in both directions, so the in-degree and out-degree of a
+/// paper are both the number of papers it is co-cited with.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `min_count` - The smallest number of co-citing papers for a pair to be joined
+///
+/// # Panics
+///
+/// Panics if `min_count` is 0.
+pub fn co_citation_network(network: &CitationNetwork, min_count: usize) -> WeightedCitationNetwork {
+    pair_network(network, Direction::Outgoing, min_count)
+}
+
+/// Returns the bibliographic coupling network of a network
+///
+/// Two papers are coupled when they cite a common paper. The derived network joins
+/// every pair of papers sharing at least `min_count` references, in both directions,
+/// with the number of shared references as the weight.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `min_count` - The smallest number of shared references for a pair to be joined
+///
+/// # Panics
+///
+/// Panics if `min_count` is 0.
+pub fn bibliographic_coupling_network(
+    network: &CitationNetwork,
+    min_count: usize,
+) -> WeightedCitationNetwork {
+    pair_network(network, Direction::Incoming, min_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_network() -> CitationNetwork {
+        let mut network = CitationNetwork::new();
+        // Papers 0, 1 and 2 all cite 10 and 11, and paper 0 cites 12 twice
+        for citer in 0..3 {
+            network.add_edge(citer, 10);
+            network.add_edge(citer, 11);
+        }
+        network.add_edge(0, 12);
+        network.add_edge(0, 12);
+        network.add_edge(1, 12);
+        network.add_edge(3, 3);
+        network.add_edge(3, 13);
+        network
+    }
+
+    /// Returns the edges of a derived network with their weights
+    fn weighted_edges(network: &WeightedCitationNetwork) -> Vec<(usize, usize, f64)> {
+        network
+            .network()
+            .edges_iter()
+            .map(|(from, to)| (from, to, network.weight(from, to).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_co_citation_network() {
+        let network = build_network();
+        let derived = co_citation_network(&network, 1);
+        assert_eq!(derived.network().size(), network.size());
+        assert_eq!(
+            weighted_edges(&derived),
+            vec![
+                (10, 11, 3.0),
+                (10, 12, 2.0),
+                (11, 10, 3.0),
+                (11, 12, 2.0),
+                (12, 10, 2.0),
+                (12, 11, 2.0),
+            ]
+        );
+        let strong = co_citation_network(&network, 3);
+        assert_eq!(weighted_edges(&strong), vec![(10, 11, 3.0), (11, 10, 3.0)]);
+        assert_eq!(co_citation_network(&network, 4).network().num_edges(), 0);
+    }
+
+    #[test]
+    fn test_bibliographic_coupling_network() {
+        let network = build_network();
+        let derived = bibliographic_coupling_network(&network, 1);
+        assert_eq!(
+            weighted_edges(&derived),
+            vec![
+                (0, 1, 3.0),
+                (0, 2, 2.0),
+                (1, 0, 3.0),
+                (1, 2, 2.0),
+                (2, 0, 2.0),
+                (2, 1, 2.0),
+            ]
+        );
+        let strong = bibliographic_coupling_network(&network, 3);
+        assert_eq!(weighted_edges(&strong), vec![(0, 1, 3.0), (1, 0, 3.0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "min_count must be at least 1")]
+    fn test_zero_min_count() {
+        co_citation_network(&build_network(), 0);
+    }
+}
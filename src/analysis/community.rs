@@ -0,0 +1,329 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::io::{self, Write};
+
+use crate::centrality::centrality::Centrality;
+use crate::centrality::pagerank_centrality::calculate_pagerank_centrality;
+use crate::network::girvan_newman::{GirvanNewmanConfig, GnResult};
+use crate::network::CitationNetwork;
+
+/// The number of papers listed as the top papers of a community
+const TOP_PAPERS: usize = 5;
+
+/// The default smallest community whose internal PageRank is computed
+const MIN_PAGERANK_SIZE: usize = 3;
+
+/// The statistics of a single community of a partition
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommunityStats {
+    /// The id of the community
+    pub community: usize,
+    /// The number of papers in the community
+    pub size: usize,
+    /// The number of citations between papers of the community
+    pub internal_edges: usize,
+    /// The number of citations between the community and the rest of the network, in
+    /// either direction
+    pub external_edges: usize,
+    /// The papers with the highest PageRank within the community, with their scores,
+    /// or `None` if the community was too small for PageRank to be computed
+    pub top_papers: Option<Vec<(usize, f64)>>,
+    /// The external citations divided by the smaller of the total degrees of the
+    /// community and of the rest of the network, or 0 if either has no citations
+    pub conductance: f64,
+}
+
+impl Display for CommunityStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "community {}: {} papers, {} internal and {} external citations, conductance {:.4}",
+            self.community, self.size, self.internal_edges, self.external_edges, self.conductance
+        )?;
+        if let Some(top_papers) = &self.top_papers {
+            let papers: Vec<String> = top_papers
+                .iter()
+                .map(|(vertex, score)| format!("{} ({:.4})", vertex, score))
+                .collect();
+            write!(f, ", top papers {}", papers.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// The parameters of a community report
+pub struct CommunityReportConfig {
+    min_pagerank_size: usize,
+}
+
+impl Default for CommunityReportConfig {
+    fn default() -> Self {
+        CommunityReportConfig {
+            min_pagerank_size: MIN_PAGERANK_SIZE,
+        }
+    }
+}
+
+impl CommunityReportConfig {
+    /// Creates a configuration computing the internal PageRank of communities of at
+    /// least three papers
+    pub fn new() -> CommunityReportConfig {
+        CommunityReportConfig::default()
+    }
+    /// Sets the smallest community whose internal PageRank is computed
+    ///
+    /// Smaller communities get no top papers, which saves running PageRank on many
+    /// tiny subnetworks.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_pagerank_size` - The smallest number of papers
+    pub fn min_pagerank_size(mut self, min_pagerank_size: usize) -> CommunityReportConfig {
+        self.min_pagerank_size = min_pagerank_size;
+        self
+    }
+    /// Reports the statistics of every community of a partition of a network
+    ///
+    /// The internal PageRank of a community is computed on the subnetwork induced by
+    /// its papers. Every citation counts, including repeated citations and
+    /// self-citations, which are internal.
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The partitioned network
+    /// * `partition` - The community of every node
+    ///
+    /// # Returns
+    ///
+    /// The statistics of the communities, from the largest to the smallest, with ties
+    /// broken by community id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a node of the network has no community.
+    pub fn report(
+        &self,
+        network: &CitationNetwork,
+        partition: &HashMap<usize, usize>,
+    ) -> Vec<CommunityStats> {
+        let community = |vertex: usize| {
+            *partition
+                .get(&vertex)
+                .unwrap_or_else(|| panic!("vertex {} has no community", vertex))
+        };
+        let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for vertex in network.nodes_sorted() {
+            members.entry(community(vertex)).or_default().push(vertex);
+        }
+        let mut internal: HashMap<usize, usize> = HashMap::new();
+        let mut external: HashMap<usize, usize> = HashMap::new();
+        for (from, to) in network.edges_iter() {
+            let (a, b) = (community(from), community(to));
+            if a == b {
+                *internal.entry(a).or_insert(0) += 1;
+            } else {
+                *external.entry(a).or_insert(0) += 1;
+                *external.entry(b).or_insert(0) += 1;
+            }
+        }
+        let total_volume = 2 * network.num_edges();
+        let mut report: Vec<CommunityStats> = members
+            .into_iter()
+            .map(|(id, papers)| {
+                let internal_edges = internal.get(&id).copied().unwrap_or(0);
+                let external_edges = external.get(&id).copied().unwrap_or(0);
+                let volume = 2 * internal_edges + external_edges;
+                let smaller = volume.min(total_volume - volume);
+                let conductance = if smaller == 0 {
+                    0.0
+                } else {
+                    external_edges as f64 / smaller as f64
+                };
+                let top_papers = (papers.len() >= self.min_pagerank_size)
+                    .then(|| top_internal_papers(network, &papers, |v| community(v) == id));
+                CommunityStats {
+                    community: id,
+                    size: papers.len(),
+                    internal_edges,
+                    external_edges,
+                    top_papers,
+                    conductance,
+                }
+            })
+            .collect();
+        report.sort_by(|a, b| b.size.cmp(&a.size).then(a.community.cmp(&b.community)));
+        report
+    }
+}
+
+/// Returns the papers of a community with the highest PageRank in its induced
+/// subnetwork
+fn top_internal_papers<F>(
+    network: &CitationNetwork,
+    papers: &[usize],
+    is_member: F,
+) -> Vec<(usize, f64)>
+where
+    F: Fn(usize) -> bool,
+{
+    let mut subnetwork = CitationNetwork::new();
+    for &paper in papers {
+        subnetwork.add_node(paper);
+        for &reference in network.out_edges_from(paper) {
+            if is_member(reference) {
+                subnetwork.add_edge(paper, reference);
+            }
+        }
+    }
+    calculate_pagerank_centrality(&subnetwork)
+        .iter()
+        .take(TOP_PAPERS)
+        .map(|centrality| (centrality.vertex(), centrality.score()))
+        .collect()
+}
+
+/// Reports the statistics of every community of a partition of a network
+///
+/// This is [`CommunityReportConfig::report`] with the internal PageRank computed for
+/// communities of at least three papers.
+///
+/// # Arguments
+///
+/// * `network` - The partitioned network
+/// * `partition` - The community of every node
+pub fn community_report(
+    network: &CitationNetwork,
+    partition: &HashMap<usize, usize>,
+) -> Vec<CommunityStats> {
+    CommunityReportConfig::new().report(network, partition)
+}
+
+/// Writes a community report as CSV with one row per community
+///
+/// The columns are `community`, `size`, `internal_edges`, `external_edges`,
+/// `conductance` and `top_papers`, which lists the top papers as `id:score`
+/// separated by semicolons and is empty for communities without PageRank.
+///
+/// # Arguments
+///
+/// * `writer` - Where to write the CSV
+/// * `report` - The report from [`community_report`]
+pub fn write_community_report_csv<W: Write>(
+    mut writer: W,
+    report: &[CommunityStats],
+) -> io::Result<()> {
+    writeln!(
+        writer,
+        "community,size,internal_edges,external_edges,conductance,top_papers"
+    )?;
+    for stats in report {
+        let top_papers: Vec<String> = stats
+            .top_papers
+            .iter()
+            .flatten()
+            .map(|(vertex, score)| format!("{}:{}", vertex, score))
+            .collect();
+        writeln!(
+            writer,
+            "{},{},{},{},{},{}",
+            stats.community,
+            stats.size,
+            stats.internal_edges,
+            stats.external_edges,
+            stats.conductance,
+            top_papers.join(";")
+        )?;
+    }
+    Ok(())
+}
+
+/// Splits a network into communities with the Girvan-Newman algorithm
+///
+/// This is [`GirvanNewmanConfig::run`] with the betweenness recomputed after every
+/// removal, which is intended for networks of up to a few thousand papers.
+///
+/// # Arguments
+///
+/// * `network` - The network to split
+/// * `target_communities` - The number of communities to stop at
+pub fn girvan_newman(network: &CitationNetwork, target_communities: usize) -> GnResult {
+    GirvanNewmanConfig::new().run(network, target_communities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_graphs::two_cliques_bridge;
+
+    #[test]
+    fn test_community_report_of_two_cliques() {
+        let mut network = two_cliques_bridge(5);
+        network.add_node(10);
+        let mut partition: HashMap<usize, usize> = (0..10).map(|v| (v, v / 5)).collect();
+        partition.insert(10, 2);
+        let report = community_report(&network, &partition);
+        assert_eq!(report.len(), 3);
+        for (stats, id) in report.iter().zip([0, 1]) {
+            assert_eq!(stats.community, id);
+            assert_eq!(stats.size, 5);
+            assert_eq!(stats.internal_edges, 10);
+            // The bridge is external to both cliques
+            assert_eq!(stats.external_edges, 1);
+            assert!((stats.conductance - 1.0 / 21.0).abs() < 1e-12);
+            assert_eq!(stats.top_papers.as_ref().unwrap().len(), 5);
+        }
+        // In each clique every paper cites the papers with smaller ids
+        assert_eq!(report[0].top_papers.as_ref().unwrap()[0].0, 0);
+        assert_eq!(report[1].top_papers.as_ref().unwrap()[0].0, 5);
+        assert_eq!(report[2].size, 1);
+        assert_eq!(report[2].top_papers, None);
+        assert_eq!(report[2].conductance, 0.0);
+    }
+
+    #[test]
+    fn test_community_report_output() {
+        let network = two_cliques_bridge(5);
+        let partition: HashMap<usize, usize> = (0..10).map(|v| (v, v / 5)).collect();
+        let report = CommunityReportConfig::new()
+            .min_pagerank_size(6)
+            .report(&network, &partition);
+        assert!(report.iter().all(|stats| stats.top_papers.is_none()));
+        assert_eq!(
+            report[1].to_string(),
+            "community 1: 5 papers, 10 internal and 1 external citations, conductance 0.0476"
+        );
+        let mut output = Vec::new();
+        write_community_report_csv(&mut output, &report).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            output.lines().next(),
+            Some("community,size,internal_edges,external_edges,conductance,top_papers")
+        );
+        assert!(output.lines().nth(1).unwrap().starts_with("0,5,10,1,0.047"));
+        assert!(output.lines().nth(1).unwrap().ends_with(','));
+        let report = community_report(&network, &partition);
+        assert!(report[0].to_string().contains(", top papers 0 ("));
+    }
+
+    #[test]
+    fn test_girvan_newman_splits_at_bridge() {
+        let network = two_cliques_bridge(5);
+        let result = girvan_newman(&network, 2);
+        let levels = result.levels();
+        assert_eq!(levels.len(), 2);
+        assert_eq!(levels[0].communities, vec![(0..10).collect::<Vec<_>>()]);
+        assert_eq!(levels[0].modularity, 0.0);
+        assert!(levels[0].removed_edges.is_empty());
+        assert_eq!(levels[1].removed_edges, vec![(4, 5)]);
+        assert_eq!(
+            result.communities(),
+            [vec![0, 1, 2, 3, 4], vec![5, 6, 7, 8, 9]]
+        );
+        // Each clique holds 10 of the 21 edges and half of the edge ends
+        assert!((levels[1].modularity - (20.0 / 21.0 - 0.5)).abs() < 1e-12);
+        assert_eq!(result.best_level(), &levels[1]);
+        let partition = result.best_partition();
+        assert_eq!(partition[&0], partition[&4]);
+        assert_ne!(partition[&4], partition[&5]);
+    }
+}
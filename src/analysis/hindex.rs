@@ -0,0 +1,132 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+use crate::centrality::centrality::{Centrality, CentralityRank};
+use crate::network::CitationNetwork;
+
+/// The h-index of a single paper over the citation counts of its citers
+///
+/// A paper has a citer h-index of `h` if `h` of the papers citing it are each cited
+/// at least `h` times, so a paper cited by many well-cited papers scores highly.
+#[derive(Clone)]
+pub struct CiterHIndex {
+    vertex: usize,
+    h_index: i32,
+}
+
+impl CiterHIndex {
+    pub fn new(vertex: usize, h_index: i32) -> CiterHIndex {
+        CiterHIndex { vertex, h_index }
+    }
+}
+
+impl PartialOrd for CiterHIndex {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.h_index.partial_cmp(&other.h_index)
+    }
+}
+
+impl PartialEq<Self> for CiterHIndex {
+    fn eq(&self, other: &Self) -> bool {
+        self.h_index == other.h_index
+    }
+}
+
+impl Display for CiterHIndex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "vertex {}: citer h-index {}", self.vertex, self.h_index)
+    }
+}
+
+impl Centrality<i32> for CiterHIndex {
+    fn vertex(&self) -> usize {
+        self.vertex
+    }
+
+    fn score(&self) -> i32 {
+        self.h_index
+    }
+}
+
+/// Returns the h-index of a paper over the citation counts of its citers
+///
+/// Papers without citers, including papers not in the network, have an h-index of
+/// 0.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `vertex` - The id of the paper
+pub fn citer_h_index(network: &CitationNetwork, vertex: usize) -> i32 {
+    let mut counts: Vec<usize> = network
+        .in_edges_to(vertex)
+        .map(|&citer| network.in_degree(citer))
+        .collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    counts
+        .iter()
+        .enumerate()
+        .take_while(|&(position, &count)| count > position)
+        .count() as i32
+}
+
+/// Returns the citer h-index of every paper in a network
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+pub fn calculate_citer_hindex_centrality(
+    network: &CitationNetwork,
+) -> CentralityRank<i32, CiterHIndex> {
+    let mut ranks: Vec<_> = network
+        .nodes()
+        .map(|&vertex| CiterHIndex::new(vertex, citer_h_index(network, vertex)))
+        .collect();
+    ranks.sort_by(|a, b| b.partial_cmp(a).unwrap().then(a.vertex.cmp(&b.vertex)));
+    CentralityRank::new(ranks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Adds `count` citations to a paper from fresh papers numbered from `next`
+    fn cite(network: &mut CitationNetwork, vertex: usize, count: usize, next: &mut usize) {
+        for _ in 0..count {
+            network.add_edge(*next, vertex);
+            *next += 1;
+        }
+    }
+
+    #[test]
+    fn test_citer_h_index() {
+        // Paper 0 is cited by 1, 2 and 3, which are cited 5, 3 and 1 times
+        let mut network = CitationNetwork::new();
+        network.add_edge(1, 0);
+        network.add_edge(2, 0);
+        network.add_edge(3, 0);
+        let mut next = 100;
+        cite(&mut network, 1, 5, &mut next);
+        cite(&mut network, 2, 3, &mut next);
+        cite(&mut network, 3, 1, &mut next);
+        assert_eq!(citer_h_index(&network, 0), 2);
+        assert_eq!(citer_h_index(&network, 1), 0);
+        assert_eq!(citer_h_index(&network, 100), 0);
+        assert_eq!(citer_h_index(&network, 42), 0);
+    }
+
+    #[test]
+    fn test_calculate_citer_hindex_centrality() {
+        // 0 and 1 cite each other; both are cited by 2, which 3 cites
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        network.add_edge(1, 0);
+        network.add_edge(2, 0);
+        network.add_edge(2, 1);
+        network.add_edge(3, 2);
+        let ranks = calculate_citer_hindex_centrality(&network);
+        let ranking: Vec<(usize, i32)> = ranks.iter().map(|c| (c.vertex(), c.score())).collect();
+        assert_eq!(ranking, vec![(0, 1), (1, 1), (2, 0), (3, 0)]);
+        assert_eq!(ranks[0].to_string(), "vertex 0: citer h-index 1");
+    }
+}
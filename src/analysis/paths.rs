@@ -0,0 +1,304 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::network::{CitationNetwork, CycleError, Direction};
+
+/// The largest number of paths that [`all_paths`] returns
+pub const MAX_PATHS: usize = 10_000;
+
+/// The error returned when paths through a cycle are counted without a length limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnboundedPaths(pub CycleError);
+
+impl Display for UnboundedPaths {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cannot count paths without a maximum length: {}", self.0)
+    }
+}
+
+impl Error for UnboundedPaths {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+/// Returns the network of the papers on some chain of citations from `from` to `to`
+///
+/// Repeated citations are kept once, since they do not give a different chain.
+fn relevant_subgraph(network: &CitationNetwork, from: usize, to: usize) -> CitationNetwork {
+    let forward = reachable(network, from, Direction::Outgoing);
+    let backward = reachable(network, to, Direction::Incoming);
+    let mut subgraph = CitationNetwork::new();
+    let mut relevant: Vec<usize> = forward.intersection(&backward).copied().collect();
+    relevant.sort_unstable();
+    let relevant_set: HashSet<usize> = relevant.iter().copied().collect();
+    for vertex in relevant {
+        subgraph.add_node(vertex);
+        for reference in distinct_references(network, vertex) {
+            if relevant_set.contains(&reference) {
+                subgraph.add_edge(vertex, reference);
+            }
+        }
+    }
+    subgraph
+}
+
+/// Returns the papers reachable from a paper in a direction, including itself if
+/// it is in the network
+fn reachable(network: &CitationNetwork, start: usize, direction: Direction) -> HashSet<usize> {
+    let mut reached = HashSet::new();
+    if !network.contains_node(start) {
+        return reached;
+    }
+    let mut stack = vec![start];
+    while let Some(vertex) = stack.pop() {
+        if reached.insert(vertex) {
+            stack.extend(network.neighbors_in(vertex, direction));
+        }
+    }
+    reached
+}
+
+/// Returns the distinct papers cited by a paper, sorted by id
+fn distinct_references(network: &CitationNetwork, vertex: usize) -> Vec<usize> {
+    let mut references: Vec<usize> = network.out_edges_from(vertex).copied().collect();
+    references.sort_unstable();
+    references.dedup();
+    references
+}
+
+/// Counts the chains of citations from one paper to another
+///
+/// A chain is a sequence of distinct papers each citing the next, and its length is
+/// the number of citations in it, so a paper has one chain of length 0 to itself.
+/// If the papers on chains from `from` to `to` form no cycle, every chain is counted
+/// by dynamic programming over a topological order. Otherwise there are infinitely
+/// many walks, and the chains are enumerated by a depth-first search that needs
+/// `max_len` to stay bounded. Counts saturate at `u128::MAX` instead of overflowing.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `from` - The id of the citing paper at the start of the chains
+/// * `to` - The id of the cited paper at the end of the chains
+/// * `max_len` - The length of the longest chains to count, or `None` for all
+///
+/// # Returns
+///
+/// * `Ok(count)` - The number of chains, which is 0 if either paper is not in the network
+/// * `Err(UnboundedPaths)` - If `max_len` is `None` and the chains pass through a cycle
+pub fn count_paths(
+    network: &CitationNetwork,
+    from: usize,
+    to: usize,
+    max_len: Option<usize>,
+) -> Result<u128, UnboundedPaths> {
+    let subgraph = relevant_subgraph(network, from, to);
+    if subgraph.size() == 0 {
+        return Ok(0);
+    }
+    match (subgraph.topological_order(), max_len) {
+        (Ok(order), None) => {
+            let mut counts: HashMap<usize, u128> = HashMap::from([(from, 1)]);
+            for vertex in order {
+                let count = counts.get(&vertex).copied().unwrap_or(0);
+                for &reference in subgraph.out_edges_from(vertex) {
+                    let total = counts.entry(reference).or_insert(0);
+                    *total = total.saturating_add(count);
+                }
+            }
+            Ok(counts[&to])
+        }
+        (Ok(order), Some(max_len)) => {
+            // No chain is longer than the number of papers it can pass through
+            let max_len = max_len.min(subgraph.size() - 1);
+            // The number of chains of each length from `from` to every paper
+            let mut counts: HashMap<usize, Vec<u128>> = HashMap::new();
+            let mut initial = vec![0; max_len + 1];
+            initial[0] = 1;
+            counts.insert(from, initial);
+            for vertex in order {
+                let Some(lengths) = counts.get(&vertex).cloned() else {
+                    continue;
+                };
+                for &reference in subgraph.out_edges_from(vertex) {
+                    let totals = counts
+                        .entry(reference)
+                        .or_insert_with(|| vec![0; max_len + 1]);
+                    for length in 0..max_len {
+                        totals[length + 1] = totals[length + 1].saturating_add(lengths[length]);
+                    }
+                }
+            }
+            Ok(counts[&to]
+                .iter()
+                .fold(0u128, |total, &count| total.saturating_add(count)))
+        }
+        (Err(cycle), None) => Err(UnboundedPaths(cycle)),
+        (Err(_), Some(max_len)) => {
+            let mut count: u128 = 0;
+            visit_paths(&subgraph, &mut vec![from], to, max_len, &mut |_| {
+                count = count.saturating_add(1);
+                true
+            });
+            Ok(count)
+        }
+    }
+}
+
+/// Returns the chains of citations from one paper to another
+///
+/// Chains are as in [`count_paths`], and are returned in lexicographic order. Since
+/// their number can grow exponentially, the search stops after [`MAX_PATHS`] chains
+/// have been found.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `from` - The id of the citing paper at the start of the chains
+/// * `to` - The id of the cited paper at the end of the chains
+/// * `max_len` - The length of the longest chains to return, or `None` for all
+pub fn all_paths(
+    network: &CitationNetwork,
+    from: usize,
+    to: usize,
+    max_len: Option<usize>,
+) -> Vec<Vec<usize>> {
+    let subgraph = relevant_subgraph(network, from, to);
+    let mut paths = Vec::new();
+    if subgraph.size() > 0 {
+        let max_len = max_len.unwrap_or(usize::MAX);
+        visit_paths(&subgraph, &mut vec![from], to, max_len, &mut |path| {
+            paths.push(path.to_vec());
+            paths.len() < MAX_PATHS
+        });
+    }
+    paths
+}
+
+/// Calls `on_path` with every chain that extends `path` to `to` within `max_len`
+/// citations, in lexicographic order, until it returns false
+///
+/// # Returns
+///
+/// * `more` - Whether the search should go on
+fn visit_paths(
+    network: &CitationNetwork,
+    path: &mut Vec<usize>,
+    to: usize,
+    max_len: usize,
+    on_path: &mut dyn FnMut(&[usize]) -> bool,
+) -> bool {
+    let vertex = *path.last().unwrap();
+    if vertex == to {
+        return on_path(path);
+    }
+    if path.len() > max_len {
+        return true;
+    }
+    for reference in distinct_references(network, vertex) {
+        if path.contains(&reference) {
+            continue;
+        }
+        path.push(reference);
+        let more = visit_paths(network, path, to, max_len, on_path);
+        path.pop();
+        if !more {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_diamond() -> CitationNetwork {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        network.add_edge(0, 2);
+        network.add_edge(1, 3);
+        network.add_edge(1, 3);
+        network.add_edge(2, 3);
+        network.add_edge(4, 0);
+        network
+    }
+
+    #[test]
+    fn test_count_paths_in_diamond() {
+        let network = build_diamond();
+        assert_eq!(count_paths(&network, 0, 3, None), Ok(2));
+        assert_eq!(count_paths(&network, 4, 3, None), Ok(2));
+        assert_eq!(count_paths(&network, 4, 3, Some(2)), Ok(0));
+        assert_eq!(count_paths(&network, 4, 3, Some(3)), Ok(2));
+        assert_eq!(count_paths(&network, 3, 3, None), Ok(1));
+        assert_eq!(count_paths(&network, 3, 0, None), Ok(0));
+        assert_eq!(count_paths(&network, 0, 42, None), Ok(0));
+        assert_eq!(
+            all_paths(&network, 4, 3, None),
+            vec![vec![4, 0, 1, 3], vec![4, 0, 2, 3]]
+        );
+    }
+
+    #[test]
+    fn test_count_paths_in_layers() {
+        // 0 cites three papers, each citing four papers, each citing 8
+        let mut network = CitationNetwork::new();
+        for middle in 1..4 {
+            network.add_edge(0, middle);
+            for lower in 4..8 {
+                network.add_edge(middle, lower);
+            }
+        }
+        for lower in 4..8 {
+            network.add_edge(lower, 8);
+        }
+        assert_eq!(count_paths(&network, 0, 8, None), Ok(12));
+        assert_eq!(count_paths(&network, 0, 8, Some(usize::MAX)), Ok(12));
+        assert_eq!(count_paths(&network, 0, 8, Some(3)), Ok(12));
+        assert_eq!(count_paths(&network, 0, 8, Some(2)), Ok(0));
+        assert_eq!(count_paths(&network, 8, 8, Some(usize::MAX)), Ok(1));
+        assert_eq!(all_paths(&network, 0, 8, None).len(), 12);
+        assert_eq!(all_paths(&network, 0, 8, None)[0], vec![0, 1, 4, 8]);
+    }
+
+    #[test]
+    fn test_count_paths_with_cycle() {
+        let mut network = build_diamond();
+        network.add_edge(3, 1);
+        let error = count_paths(&network, 0, 3, None).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "cannot count paths without a maximum length: the network has a cycle through vertex 1"
+        );
+        assert_eq!(count_paths(&network, 0, 3, Some(2)), Ok(2));
+        assert_eq!(count_paths(&network, 0, 1, Some(10)), Ok(2));
+        assert_eq!(count_paths(&network, 0, 1, Some(usize::MAX)), Ok(2));
+        assert_eq!(
+            all_paths(&network, 0, 1, None),
+            vec![vec![0, 1], vec![0, 2, 3, 1]]
+        );
+        // A cycle off the chains does not matter
+        let mut network = build_diamond();
+        network.add_edge(5, 6);
+        network.add_edge(6, 5);
+        assert_eq!(count_paths(&network, 0, 3, None), Ok(2));
+    }
+
+    #[test]
+    fn test_count_paths_saturates() {
+        // 130 diamonds in a row have 2^130 chains
+        let mut network = CitationNetwork::new();
+        for i in 0..130 {
+            let top = 3 * i;
+            network.add_edge(top, top + 1);
+            network.add_edge(top, top + 2);
+            network.add_edge(top + 1, top + 3);
+            network.add_edge(top + 2, top + 3);
+        }
+        assert_eq!(count_paths(&network, 0, 390, None), Ok(u128::MAX));
+        assert_eq!(count_paths(&network, 0, 3 * 127, None), Ok(1 << 127));
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt::{Display, Formatter};
+
+use crate::centrality::centrality::{Centrality, CentralityRank};
+use crate::centrality::pagerank_centrality::PageRankCentrality;
+use crate::network::{CitationNetwork, UnknownVertex};
+
+/// Everything known about a single paper in a network
+#[derive(Debug, PartialEq)]
+pub struct NodeProfile {
+    pub vertex: usize,
+    pub in_degree: usize,
+    pub out_degree: usize,
+    /// The papers citing this paper, sorted by id
+    pub citers: Vec<usize>,
+    /// The papers cited by this paper, sorted by id
+    pub references: Vec<usize>,
+    pub clustering_coefficient: f64,
+    /// The PageRank score and 1-based rank position, if a ranking was supplied
+    pub pagerank: Option<(f64, usize)>,
+}
+
+impl NodeProfile {
+    /// Adds the paper's PageRank score and position from a precomputed ranking
+    ///
+    /// # Arguments
+    ///
+    /// * `ranks` - The PageRank centrality scores of the network
+    pub fn with_pagerank(mut self, ranks: &CentralityRank<f64, PageRankCentrality>) -> NodeProfile {
+        self.pagerank = ranks
+            .iter()
+            .position(|centrality| centrality.vertex() == self.vertex)
+            .map(|position| (ranks[position].score(), position + 1));
+        self
+    }
+}
+
+/// Collects the profile of a paper
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `vertex` - The id of the paper
+pub fn node_profile(
+    network: &CitationNetwork,
+    vertex: usize,
+) -> Result<NodeProfile, UnknownVertex> {
+    if !network.contains_node(vertex) {
+        return Err(UnknownVertex(vertex));
+    }
+    let mut citers: Vec<usize> = network.in_edges_to(vertex).copied().collect();
+    citers.sort_unstable();
+    let mut references: Vec<usize> = network.out_edges_from(vertex).copied().collect();
+    references.sort_unstable();
+    Ok(NodeProfile {
+        vertex,
+        in_degree: citers.len(),
+        out_degree: references.len(),
+        citers,
+        references,
+        clustering_coefficient: network.local_clustering_coefficient(vertex),
+        pagerank: None,
+    })
+}
+
+/// Joins a list of paper ids with commas, or returns "none" if it is empty
+fn join(vertices: &[usize]) -> String {
+    if vertices.is_empty() {
+        return "none".to_string();
+    }
+    let vertices: Vec<String> = vertices.iter().map(|v| v.to_string()).collect();
+    vertices.join(", ")
+}
+
+impl Display for NodeProfile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "vertex {}", self.vertex)?;
+        writeln!(f, "  in-degree: {}", self.in_degree)?;
+        writeln!(f, "  out-degree: {}", self.out_degree)?;
+        writeln!(
+            f,
+            "  clustering coefficient: {}",
+            self.clustering_coefficient
+        )?;
+        if let Some((score, position)) = self.pagerank {
+            writeln!(f, "  PageRank: {} (rank {})", score, position)?;
+        }
+        writeln!(f, "  cited by: {}", join(&self.citers))?;
+        writeln!(f, "  cites: {}", join(&self.references))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::centrality::pagerank_centrality::calculate_pagerank_centrality;
+
+    fn build_network() -> CitationNetwork {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(0, 3);
+        graph.add_edge(1, 2);
+        graph.add_edge(1, 3);
+        graph.add_edge(2, 3);
+        graph
+    }
+
+    #[test]
+    fn test_node_profile() {
+        let network = build_network();
+        let profile = node_profile(&network, 2).unwrap();
+        assert_eq!(profile.in_degree, 2);
+        assert_eq!(profile.out_degree, 1);
+        assert_eq!(profile.citers, vec![0, 1]);
+        assert_eq!(profile.references, vec![3]);
+        assert_eq!(profile.clustering_coefficient, 1.0);
+        assert_eq!(profile.pagerank, None);
+        assert_eq!(
+            profile.to_string(),
+            "vertex 2\n  in-degree: 2\n  out-degree: 1\n  clustering coefficient: 1\n  \
+             cited by: 0, 1\n  cites: 3\n"
+        );
+        let ranks = calculate_pagerank_centrality(&network);
+        let profile = node_profile(&network, 3).unwrap().with_pagerank(&ranks);
+        assert_eq!(profile.pagerank, Some((ranks[0].score(), 1)));
+        assert!(profile.to_string().contains("(rank 1)"));
+        assert!(profile.to_string().ends_with("cites: none\n"));
+    }
+
+    #[test]
+    fn test_node_profile_of_unknown_vertex() {
+        let network = build_network();
+        let error = node_profile(&network, 42).unwrap_err();
+        assert_eq!(error, UnknownVertex(42));
+        assert_eq!(error.to_string(), "vertex 42 is not in the network");
+    }
+}
@@ -0,0 +1,280 @@
+use crate::network::index::NodeIndex;
+use crate::network::CitationNetwork;
+
+/// The number of nodes tracked by each word of a bitset
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Precomputed answers to whether one paper reaches another by a chain of citations
+///
+/// Each row is a bitset over the compact indices of the network's nodes, so an index
+/// over every node takes `O(V^2 / 64)` words. Papers in the same strongly connected
+/// component reach the same papers, so [`ReachabilityIndex::build`] stores one row
+/// per component.
+///
+/// A paper reaches itself by the empty chain, so [`ReachabilityIndex::reaches`] is
+/// true for `from == to`, while [`ReachabilityIndex::descendants`] never lists the
+/// paper itself.
+pub struct ReachabilityIndex {
+    index: NodeIndex,
+    /// The row of each node, or `None` if the index was not built for it
+    row_of: Vec<Option<usize>>,
+    /// The set of nodes reachable from the nodes of each row
+    rows: Vec<Vec<u64>>,
+}
+
+impl ReachabilityIndex {
+    /// Builds the index for every node of a network
+    ///
+    /// The rows are filled on the condensation of the network in reverse topological
+    /// order, so each component's row is the union of the rows of the components it
+    /// cites.
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network to index
+    pub fn build(network: &CitationNetwork) -> ReachabilityIndex {
+        let index = network.node_index();
+        let words = index.len().div_ceil(WORD_BITS);
+        let mut row_of = vec![None; index.len()];
+        let mut rows: Vec<Vec<u64>> = Vec::new();
+        // Every component comes after the components it cites, so their rows are
+        // complete by the time it is reached
+        for component in network.strongly_connected_components() {
+            let row = rows.len();
+            let mut bits = vec![0; words];
+            for &vertex in &component {
+                let position = index.index_of(vertex).unwrap();
+                row_of[position] = Some(row);
+                set(&mut bits, position);
+            }
+            for &vertex in &component {
+                for &reference in network.out_edges_from(vertex) {
+                    let reference_row = row_of[index.index_of(reference).unwrap()].unwrap();
+                    if reference_row != row {
+                        for (word, other) in bits.iter_mut().zip(&rows[reference_row]) {
+                            *word |= other;
+                        }
+                    }
+                }
+            }
+            rows.push(bits);
+        }
+        ReachabilityIndex {
+            index,
+            row_of,
+            rows,
+        }
+    }
+    /// Builds the index only for the papers queries will start from
+    ///
+    /// Each source is searched separately, which takes `O(S (V + E))` time but only
+    /// `O(S V / 64)` memory. Sources that are not in the network are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network to index
+    /// * `sources` - The papers that reachability will be queried from
+    pub fn build_for_sources(network: &CitationNetwork, sources: &[usize]) -> ReachabilityIndex {
+        let index = network.node_index();
+        let words = index.len().div_ceil(WORD_BITS);
+        let mut row_of = vec![None; index.len()];
+        let mut rows = Vec::new();
+        for &source in sources {
+            let Some(start) = index.index_of(source) else {
+                continue;
+            };
+            if row_of[start].is_some() {
+                continue;
+            }
+            let mut bits = vec![0; words];
+            set(&mut bits, start);
+            let mut stack = vec![source];
+            while let Some(vertex) = stack.pop() {
+                for &reference in network.out_edges_from(vertex) {
+                    let position = index.index_of(reference).unwrap();
+                    if !get(&bits, position) {
+                        set(&mut bits, position);
+                        stack.push(reference);
+                    }
+                }
+            }
+            row_of[start] = Some(rows.len());
+            rows.push(bits);
+        }
+        ReachabilityIndex {
+            index,
+            row_of,
+            rows,
+        }
+    }
+    /// Returns the row of a paper, or `None` if it is not in the network
+    ///
+    /// # Panics
+    ///
+    /// Panics if the paper is in the network but the index was not built for it.
+    fn row(&self, vertex: usize) -> Option<&[u64]> {
+        let position = self.index.index_of(vertex)?;
+        let row = self.row_of[position]
+            .unwrap_or_else(|| panic!("the reachability index was not built for {}", vertex));
+        Some(&self.rows[row])
+    }
+    /// Returns whether `from` reaches `to` by a chain of zero or more citations
+    ///
+    /// Papers that are not in the network reach nothing and are reached by nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The id of the citing paper
+    /// * `to` - The id of the cited paper
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` is in the network but was not a source given to
+    /// [`ReachabilityIndex::build_for_sources`].
+    pub fn reaches(&self, from: usize, to: usize) -> bool {
+        match (self.row(from), self.index.index_of(to)) {
+            (Some(bits), Some(position)) => get(bits, position),
+            _ => false,
+        }
+    }
+    /// Returns the papers other than itself that a paper reaches, sorted by id
+    ///
+    /// A paper on a cycle of citations reaches itself, but it is still left out, so
+    /// the result is the same as [`ReachabilityIndex::reaches`] over every other
+    /// paper.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex` - The id of the paper
+    ///
+    /// # Panics
+    ///
+    /// Panics if the paper is in the network but was not a source given to
+    /// [`ReachabilityIndex::build_for_sources`].
+    pub fn descendants(&self, vertex: usize) -> Vec<usize> {
+        let Some(bits) = self.row(vertex) else {
+            return Vec::new();
+        };
+        // Positions follow the ids, so collecting them in order yields sorted ids
+        let mut descendants = Vec::new();
+        for (offset, &word) in bits.iter().enumerate() {
+            let mut word = word;
+            while word != 0 {
+                let position = offset * WORD_BITS + word.trailing_zeros() as usize;
+                let descendant = self.index.vertex_at(position);
+                if descendant != vertex {
+                    descendants.push(descendant);
+                }
+                word &= word - 1;
+            }
+        }
+        descendants
+    }
+}
+
+/// Returns whether a position is in a bitset
+fn get(bits: &[u64], position: usize) -> bool {
+    bits[position / WORD_BITS] & (1 << (position % WORD_BITS)) != 0
+}
+
+/// Adds a position to a bitset
+fn set(bits: &mut [u64], position: usize) {
+    bits[position / WORD_BITS] |= 1 << (position % WORD_BITS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::{erdos_renyi, random_edges};
+
+    /// Checks every pair of nodes of a network against breadth-first search
+    fn assert_matches_bfs(network: &CitationNetwork, index: &ReachabilityIndex) {
+        for from in network.nodes_sorted() {
+            let distances = network.bfs_distances(from);
+            let mut expected: Vec<usize> = distances
+                .keys()
+                .copied()
+                .filter(|&vertex| vertex != from)
+                .collect();
+            expected.sort_unstable();
+            assert_eq!(index.descendants(from), expected);
+            for to in network.nodes_sorted() {
+                assert_eq!(index.reaches(from, to), distances.contains_key(&to));
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_bfs_on_random_dags() {
+        for seed in 0..5 {
+            // Citing only papers with a smaller id rules out cycles
+            let network: CitationNetwork = random_edges(150, 400, seed)
+                .into_iter()
+                .filter(|&(from, to)| from > to)
+                .collect();
+            assert!(network.topological_order().is_ok());
+            assert_matches_bfs(&network, &ReachabilityIndex::build(&network));
+        }
+    }
+
+    #[test]
+    fn test_matches_bfs_with_cycles() {
+        let network = erdos_renyi(80, 0.02, 3);
+        assert!(network.topological_order().is_err());
+        assert_matches_bfs(&network, &ReachabilityIndex::build(&network));
+
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        network.add_edge(1, 2);
+        network.add_edge(2, 0);
+        network.add_edge(2, 3);
+        network.add_edge(4, 0);
+        let index = ReachabilityIndex::build(&network);
+        assert_matches_bfs(&network, &index);
+        assert_eq!(index.descendants(1), vec![0, 2, 3]);
+        assert!(index.reaches(3, 3));
+        assert!(!index.reaches(3, 0));
+        assert!(!index.reaches(9, 9));
+        assert!(index.descendants(9).is_empty());
+    }
+
+    #[test]
+    fn test_descendants_on_cycles() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        network.add_edge(1, 0);
+        network.add_edge(2, 2);
+        network.add_edge(2, 1);
+        for index in [
+            ReachabilityIndex::build(&network),
+            ReachabilityIndex::build_for_sources(&network, &[0, 1, 2]),
+        ] {
+            // Each paper reaches itself around its cycle but is not its own descendant
+            assert!((0..3).all(|vertex| index.reaches(vertex, vertex)));
+            assert_eq!(index.descendants(0), vec![1]);
+            assert_eq!(index.descendants(1), vec![0]);
+            assert_eq!(index.descendants(2), vec![0, 1]);
+        }
+    }
+
+    #[test]
+    fn test_build_for_sources() {
+        let network = erdos_renyi(80, 0.02, 3);
+        let full = ReachabilityIndex::build(&network);
+        let sources = [5, 17, 42, 1000];
+        let partial = ReachabilityIndex::build_for_sources(&network, &sources);
+        for from in sources {
+            assert_eq!(partial.descendants(from), full.descendants(from));
+            for to in network.nodes_sorted() {
+                assert_eq!(partial.reaches(from, to), full.reaches(from, to));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "not built for 6")]
+    fn test_query_outside_sources() {
+        let network = erdos_renyi(20, 0.1, 1);
+        ReachabilityIndex::build_for_sources(&network, &[5]).reaches(6, 5);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::network::CitationNetwork;
+
+/// The longest citation ring that [`citation_rings`] searches for
+pub const MAX_RING_LENGTH: usize = 5;
+/// The largest number of citation rings that [`citation_rings`] returns
+pub const MAX_RINGS: usize = 10_000;
+
+/// Returns every pair of papers that cite each other
+///
+/// Each pair is reported once as `(a, b)` with `a < b`, and the pairs are sorted.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+pub fn mutual_citation_pairs(network: &CitationNetwork) -> Vec<(usize, usize)> {
+    let mut pairs: Vec<(usize, usize)> = network
+        .edges_iter()
+        .filter(|&(from, to)| from < to && network.contains_edge(to, from))
+        .collect();
+    pairs.dedup();
+    pairs
+}
+
+/// Returns the simple directed cycles of citations up to a given length
+///
+/// A ring of length `k` is a sequence of `k` distinct papers each citing the next,
+/// with the last citing the first. Self-citations are not rings. Each ring is
+/// reported once, rotated so that it starts at its smallest paper, and the rings
+/// are sorted by length and then lexicographically. The number of rings can grow
+/// exponentially with the length, so the search stops after [`MAX_RINGS`] rings
+/// have been found.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `max_len` - The length of the longest rings to report
+///
+/// # Panics
+///
+/// Panics if `max_len` is greater than [`MAX_RING_LENGTH`].
+pub fn citation_rings(network: &CitationNetwork, max_len: usize) -> Vec<Vec<usize>> {
+    assert!(
+        max_len <= MAX_RING_LENGTH,
+        "rings longer than {} are not supported, got {}",
+        MAX_RING_LENGTH,
+        max_len
+    );
+    let starts = network.nodes_sorted();
+    let mut rings = Vec::new();
+    for start in starts {
+        if rings.len() >= MAX_RINGS {
+            break;
+        }
+        let mut path = vec![start];
+        extend_rings(network, max_len, &mut path, &mut rings);
+    }
+    rings.truncate(MAX_RINGS);
+    rings.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+    rings
+}
+
+/// Records every ring that closes a path, then extends the path by one paper
+///
+/// Only papers larger than the first paper of the path are visited, so that each
+/// ring is found exactly once, from its smallest paper.
+fn extend_rings(
+    network: &CitationNetwork,
+    max_len: usize,
+    path: &mut Vec<usize>,
+    rings: &mut Vec<Vec<usize>>,
+) {
+    let start = path[0];
+    let last = *path.last().unwrap();
+    let mut references: Vec<usize> = network.out_edges_from(last).copied().collect();
+    references.sort_unstable();
+    references.dedup();
+    for reference in references {
+        if rings.len() >= MAX_RINGS {
+            return;
+        }
+        if reference == start && path.len() >= 2 {
+            rings.push(path.clone());
+        } else if reference > start && path.len() < max_len && !path.contains(&reference) {
+            path.push(reference);
+            extend_rings(network, max_len, path, rings);
+            path.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_network() -> CitationNetwork {
+        let mut network = CitationNetwork::new();
+        // A 2-cycle
+        network.add_edge(0, 1);
+        network.add_edge(1, 0);
+        // A 3-cycle entered from the 2-cycle
+        network.add_edge(1, 4);
+        network.add_edge(4, 2);
+        network.add_edge(2, 3);
+        network.add_edge(3, 4);
+        // An acyclic region with a self-citation
+        network.add_edge(5, 6);
+        network.add_edge(5, 7);
+        network.add_edge(6, 7);
+        network.add_edge(7, 7);
+        network
+    }
+
+    #[test]
+    fn test_mutual_citation_pairs() {
+        let mut network = build_network();
+        assert_eq!(mutual_citation_pairs(&network), vec![(0, 1)]);
+        network.add_edge(1, 0);
+        network.add_edge(7, 5);
+        assert_eq!(mutual_citation_pairs(&network), vec![(0, 1), (5, 7)]);
+    }
+
+    #[test]
+    fn test_citation_rings() {
+        let network = build_network();
+        assert_eq!(citation_rings(&network, 5), vec![vec![0, 1], vec![2, 3, 4]]);
+        assert_eq!(citation_rings(&network, 2), vec![vec![0, 1]]);
+        assert!(citation_rings(&network, 1).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_citation_rings_rejects_long_rings() {
+        citation_rings(&build_network(), MAX_RING_LENGTH + 1);
+    }
+}
@@ -0,0 +1,315 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::centrality::centrality::{Centrality, CentralityRank};
+use crate::network::CitationNetwork;
+
+/// The participation coefficient of a single paper
+///
+/// The participation coefficient is close to 1 for a paper whose citations are
+/// spread evenly over many communities, and 0 for a paper whose citations all stay
+/// within one community.
+#[derive(Clone)]
+pub struct ParticipationCoefficient {
+    vertex: usize,
+    participation: f64,
+}
+
+impl ParticipationCoefficient {
+    pub fn new(vertex: usize, participation: f64) -> ParticipationCoefficient {
+        ParticipationCoefficient {
+            vertex,
+            participation,
+        }
+    }
+}
+
+impl PartialOrd for ParticipationCoefficient {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.participation.partial_cmp(&other.participation)
+    }
+}
+
+const EPSILON: f64 = 1e-12;
+
+impl PartialEq<Self> for ParticipationCoefficient {
+    fn eq(&self, other: &Self) -> bool {
+        (self.participation - other.participation).abs() <= EPSILON
+    }
+}
+
+impl Display for ParticipationCoefficient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "vertex {}: participation {}",
+            self.vertex, self.participation
+        )
+    }
+}
+
+impl Centrality<f64> for ParticipationCoefficient {
+    fn vertex(&self) -> usize {
+        self.vertex
+    }
+
+    fn score(&self) -> f64 {
+        self.participation
+    }
+}
+
+/// The within-module degree z-score of a single paper
+///
+/// The z-score measures how much better connected a paper is within its own
+/// community than the other papers of that community.
+#[derive(Clone)]
+pub struct WithinModuleZScore {
+    vertex: usize,
+    z_score: f64,
+}
+
+impl WithinModuleZScore {
+    pub fn new(vertex: usize, z_score: f64) -> WithinModuleZScore {
+        WithinModuleZScore { vertex, z_score }
+    }
+}
+
+impl PartialOrd for WithinModuleZScore {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.z_score.partial_cmp(&other.z_score)
+    }
+}
+
+impl PartialEq<Self> for WithinModuleZScore {
+    fn eq(&self, other: &Self) -> bool {
+        (self.z_score - other.z_score).abs() <= EPSILON
+    }
+}
+
+impl Display for WithinModuleZScore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "vertex {}: within-module z {}",
+            self.vertex, self.z_score
+        )
+    }
+}
+
+impl Centrality<f64> for WithinModuleZScore {
+    fn vertex(&self) -> usize {
+        self.vertex
+    }
+
+    fn score(&self) -> f64 {
+        self.z_score
+    }
+}
+
+/// Returns the communities of the papers adjacent to a paper, one entry per edge
+///
+/// Citations are treated as undirected, self-citations are skipped, and papers
+/// missing from the partition are ignored.
+fn neighbor_communities(
+    network: &CitationNetwork,
+    partition: &HashMap<usize, usize>,
+    vertex: usize,
+) -> Vec<usize> {
+    network
+        .in_edges_to(vertex)
+        .chain(network.out_edges_from(vertex))
+        .filter(|&&neighbor| neighbor != vertex)
+        .filter_map(|neighbor| partition.get(neighbor).copied())
+        .collect()
+}
+
+/// Returns the Guimerà–Amaral participation coefficient of every paper
+///
+/// The participation coefficient of a paper with degree `k` and `k_s` edges to
+/// community `s` is `1 - sum over s of (k_s / k)^2`. Citations are treated as
+/// undirected and counted once per occurrence, and edges to papers missing from
+/// the partition are ignored. Papers without edges have a participation of 0.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `partition` - The community of each paper
+pub fn participation_coefficient(
+    network: &CitationNetwork,
+    partition: &HashMap<usize, usize>,
+) -> HashMap<usize, f64> {
+    network
+        .nodes()
+        .map(|&vertex| {
+            let communities = neighbor_communities(network, partition, vertex);
+            if communities.is_empty() {
+                return (vertex, 0.0);
+            }
+            let mut counts: HashMap<usize, usize> = HashMap::new();
+            for community in &communities {
+                *counts.entry(*community).or_default() += 1;
+            }
+            let degree = communities.len() as f64;
+            let concentration: f64 = counts
+                .values()
+                .map(|&count| (count as f64 / degree).powi(2))
+                .sum();
+            (vertex, 1.0 - concentration)
+        })
+        .collect()
+}
+
+/// Returns the within-module degree z-score of every paper
+///
+/// The within-module degree of a paper is its number of edges to papers of its own
+/// community, and its z-score is the difference from the mean within-module degree
+/// of that community divided by the standard deviation. Citations are treated as
+/// undirected. Papers missing from the partition, and papers of a community whose
+/// within-module degrees are all equal, have a z-score of 0.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `partition` - The community of each paper
+pub fn within_module_degree_zscore(
+    network: &CitationNetwork,
+    partition: &HashMap<usize, usize>,
+) -> HashMap<usize, f64> {
+    let within: HashMap<usize, f64> = network
+        .nodes()
+        .filter_map(|&vertex| {
+            let community = partition.get(&vertex)?;
+            let degree = neighbor_communities(network, partition, vertex)
+                .into_iter()
+                .filter(|c| c == community)
+                .count();
+            Some((vertex, degree as f64))
+        })
+        .collect();
+    let mut degrees_by_community: HashMap<usize, Vec<f64>> = HashMap::new();
+    for (vertex, &degree) in &within {
+        degrees_by_community
+            .entry(partition[vertex])
+            .or_default()
+            .push(degree);
+    }
+    let moments: HashMap<usize, (f64, f64)> = degrees_by_community
+        .into_iter()
+        .map(|(community, degrees)| {
+            let n = degrees.len() as f64;
+            let mean = degrees.iter().sum::<f64>() / n;
+            let variance = degrees.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / n;
+            (community, (mean, variance.sqrt()))
+        })
+        .collect();
+    network
+        .nodes()
+        .map(|&vertex| {
+            let z_score = match within.get(&vertex) {
+                Some(degree) => {
+                    let (mean, deviation) = moments[&partition[&vertex]];
+                    if deviation > 0.0 {
+                        (degree - mean) / deviation
+                    } else {
+                        0.0
+                    }
+                }
+                None => 0.0,
+            };
+            (vertex, z_score)
+        })
+        .collect()
+}
+
+/// Returns the participation coefficients of a network as a rank
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `partition` - The community of each paper
+pub fn calculate_participation_centrality(
+    network: &CitationNetwork,
+    partition: &HashMap<usize, usize>,
+) -> CentralityRank<f64, ParticipationCoefficient> {
+    let mut ranks: Vec<_> = participation_coefficient(network, partition)
+        .into_iter()
+        .map(|(vertex, participation)| ParticipationCoefficient::new(vertex, participation))
+        .collect();
+    ranks.sort_by(|a, b| b.partial_cmp(a).unwrap().then(a.vertex.cmp(&b.vertex)));
+    CentralityRank::new(ranks)
+}
+
+/// Returns the within-module degree z-scores of a network as a rank
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `partition` - The community of each paper
+pub fn calculate_within_module_zscore_centrality(
+    network: &CitationNetwork,
+    partition: &HashMap<usize, usize>,
+) -> CentralityRank<f64, WithinModuleZScore> {
+    let mut ranks: Vec<_> = within_module_degree_zscore(network, partition)
+        .into_iter()
+        .map(|(vertex, z_score)| WithinModuleZScore::new(vertex, z_score))
+        .collect();
+    ranks.sort_by(|a, b| b.partial_cmp(a).unwrap().then(a.vertex.cmp(&b.vertex)));
+    CentralityRank::new(ranks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two 4-cliques, 0..4 and 4..8, joined by a citation from 3 to 4
+    fn build_network() -> (CitationNetwork, HashMap<usize, usize>) {
+        let mut network = CitationNetwork::new();
+        for clique in [0, 4] {
+            for a in clique..clique + 4 {
+                for b in a + 1..clique + 4 {
+                    network.add_edge(a, b);
+                }
+            }
+        }
+        network.add_edge(3, 4);
+        let partition = (0..8).map(|vertex| (vertex, vertex / 4)).collect();
+        (network, partition)
+    }
+
+    #[test]
+    fn test_participation_coefficient() {
+        let (mut network, partition) = build_network();
+        network.add_node(9);
+        let participation = participation_coefficient(&network, &partition);
+        // 3 of the 4 edges of each bridge endpoint stay in its clique
+        assert!((participation[&3] - 0.375).abs() < 1e-12);
+        assert!((participation[&4] - 0.375).abs() < 1e-12);
+        assert_eq!(participation[&0], 0.0);
+        assert_eq!(participation[&9], 0.0);
+        let ranks = calculate_participation_centrality(&network, &partition);
+        assert_eq!(ranks[0].vertex(), 3);
+        assert_eq!(ranks[1].vertex(), 4);
+        assert_eq!(ranks[0].to_string(), "vertex 3: participation 0.375");
+    }
+
+    #[test]
+    fn test_within_module_degree_zscore() {
+        let (mut network, mut partition) = build_network();
+        // Every paper of a clique has 3 edges inside it
+        let z_scores = within_module_degree_zscore(&network, &partition);
+        assert!(z_scores.values().all(|&z| z == 0.0));
+        // A new paper in the first community citing only 0 makes 0 stand out
+        network.add_edge(8, 0);
+        partition.insert(8, 0);
+        let ranks = calculate_within_module_zscore_centrality(&network, &partition);
+        assert_eq!(ranks[0].vertex(), 0);
+        assert!(ranks[0].score() > 0.0);
+        let z_scores = within_module_degree_zscore(&network, &partition);
+        assert!(z_scores[&8] < 0.0);
+        assert_eq!(z_scores[&5], 0.0);
+        // The z-scores of a community have mean 0
+        let sum: f64 = (0..4).chain([8]).map(|vertex| z_scores[&vertex]).sum();
+        assert!(sum.abs() < 1e-12);
+    }
+}
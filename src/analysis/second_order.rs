@@ -0,0 +1,234 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+use crate::centrality::centrality::{Centrality, CentralityRank};
+use crate::centrality::measure::CentralityMeasure;
+use crate::network::CitationNetwork;
+
+/// The number of distinct papers citing a single paper within a few hops
+///
+/// A paper reaches another within `h` hops if a chain of at most `h` citations
+/// leads from it to the other. Counting these papers lies between counting the
+/// direct citations and following the whole cascade of influence.
+#[derive(Clone)]
+pub struct SecondOrderCitations {
+    vertex: usize,
+    citers: i32,
+}
+
+impl SecondOrderCitations {
+    pub fn new(vertex: usize, citers: i32) -> SecondOrderCitations {
+        SecondOrderCitations { vertex, citers }
+    }
+}
+
+impl PartialOrd for SecondOrderCitations {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.citers.partial_cmp(&other.citers)
+    }
+}
+
+impl PartialEq<Self> for SecondOrderCitations {
+    fn eq(&self, other: &Self) -> bool {
+        self.citers == other.citers
+    }
+}
+
+impl Display for SecondOrderCitations {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "vertex {}: distinct citers {}", self.vertex, self.citers)
+    }
+}
+
+impl Centrality<i32> for SecondOrderCitations {
+    fn vertex(&self) -> usize {
+        self.vertex
+    }
+
+    fn score(&self) -> i32 {
+        self.citers
+    }
+}
+
+/// Returns the number of distinct papers citing each paper within `hops` citations
+///
+/// With one hop this is the number of distinct citers, and with two it adds the
+/// citers of the citers. A paper reached along several chains counts once, and a
+/// paper is never counted as its own citer, even through a cycle.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `hops` - The longest chain of citations followed
+pub fn distinct_citers_within(
+    network: &CitationNetwork,
+    hops: usize,
+) -> CentralityRank<i32, SecondOrderCitations> {
+    count_citers(network, hops, false)
+}
+
+/// Returns the number of distinct papers citing each paper within `hops` citations
+/// without citing it directly
+///
+/// This is [`distinct_citers_within`] without the direct citers, so with two hops it
+/// counts only the citers of the citers. A citer of a citer that also cites the
+/// paper directly is not counted.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `hops` - The longest chain of citations followed
+pub fn indirect_citers_within(
+    network: &CitationNetwork,
+    hops: usize,
+) -> CentralityRank<i32, SecondOrderCitations> {
+    count_citers(network, hops, true)
+}
+
+/// Counts the citers of every paper with a breadth-first search over in-edges
+/// bounded to `hops` levels
+///
+/// The searches share one buffer that marks each visited paper with the index of the
+/// search, so it never has to be cleared.
+fn count_citers(
+    network: &CitationNetwork,
+    hops: usize,
+    exclude_direct: bool,
+) -> CentralityRank<i32, SecondOrderCitations> {
+    let index = network.node_index();
+    let citers: Vec<Vec<usize>> = index
+        .vertices()
+        .iter()
+        .map(|&vertex| {
+            network
+                .in_edges_to(vertex)
+                .map(|&citer| index.index_of(citer).unwrap())
+                .collect()
+        })
+        .collect();
+    let mut visited = vec![usize::MAX; index.len()];
+    let mut frontier = Vec::new();
+    let mut next = Vec::new();
+    let mut ranks: Vec<SecondOrderCitations> = Vec::with_capacity(index.len());
+    for (source, direct) in citers.iter().enumerate() {
+        let vertex = index.vertex_at(source);
+        if direct.is_empty() || hops == 0 {
+            ranks.push(SecondOrderCitations::new(vertex, 0));
+            continue;
+        }
+        visited[source] = source;
+        frontier.clear();
+        frontier.push(source);
+        let mut count = 0;
+        for hop in 1..=hops {
+            next.clear();
+            for &paper in &frontier {
+                for &citer in &citers[paper] {
+                    if visited[citer] != source {
+                        visited[citer] = source;
+                        next.push(citer);
+                    }
+                }
+            }
+            if !(exclude_direct && hop == 1) {
+                count += next.len();
+            }
+            if next.is_empty() {
+                break;
+            }
+            std::mem::swap(&mut frontier, &mut next);
+        }
+        ranks.push(SecondOrderCitations::new(vertex, count as i32));
+    }
+    ranks.sort_by(|a, b| b.partial_cmp(a).unwrap().then(a.vertex.cmp(&b.vertex)));
+    CentralityRank::new(ranks)
+}
+
+/// The number of distinct citers within two hops as a [`CentralityMeasure`]
+pub struct DistinctCitersMeasure;
+
+impl CentralityMeasure for DistinctCitersMeasure {
+    fn name(&self) -> &str {
+        "distinct-citers"
+    }
+
+    fn compute(&self, network: &CitationNetwork) -> Vec<(usize, f64)> {
+        distinct_citers_within(network, 2)
+            .iter()
+            .map(|centrality| (centrality.vertex(), centrality.score() as f64))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_graphs::cycle;
+
+    /// Paper 0 is cited by 1 and 2, which are cited by 3, 4 and 5 with 4 citing
+    /// both, and 6 cites 0 and 1
+    fn build_tree() -> CitationNetwork {
+        [
+            (1, 0),
+            (2, 0),
+            (3, 1),
+            (4, 1),
+            (4, 2),
+            (5, 2),
+            (6, 0),
+            (6, 1),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    fn scores(rank: &CentralityRank<i32, SecondOrderCitations>) -> Vec<(usize, i32)> {
+        rank.iter().map(|c| (c.vertex(), c.score())).collect()
+    }
+
+    #[test]
+    fn test_distinct_citers_within_two_hops() {
+        let network = build_tree();
+        let ranks = distinct_citers_within(&network, 2);
+        // Paper 4 cites both citers of 0 but counts once, and 6 is both a direct
+        // citer and a citer of a citer
+        assert_eq!(
+            scores(&ranks),
+            [(0, 6), (1, 3), (2, 2), (3, 0), (4, 0), (5, 0), (6, 0)]
+        );
+        assert_eq!(ranks[0].to_string(), "vertex 0: distinct citers 6");
+        let direct = distinct_citers_within(&network, 1);
+        assert_eq!(scores(&direct)[..3], [(0, 3), (1, 3), (2, 2)]);
+        assert!(distinct_citers_within(&network, 0)
+            .iter()
+            .all(|c| c.score() == 0));
+    }
+
+    #[test]
+    fn test_indirect_citers_within_two_hops() {
+        let network = build_tree();
+        let ranks = indirect_citers_within(&network, 2);
+        // Paper 6 cites 0 directly, so only 3, 4 and 5 are indirect citers of 0
+        assert_eq!(scores(&ranks)[..2], [(0, 3), (1, 0)]);
+        assert!(ranks.iter().skip(1).all(|c| c.score() == 0));
+        assert!(indirect_citers_within(&network, 1)
+            .iter()
+            .all(|c| c.score() == 0));
+    }
+
+    #[test]
+    fn test_repeated_citations_and_cycles() {
+        let mut network = cycle(3);
+        network.add_edge(0, 1);
+        // Each paper reaches the other two, but never counts itself
+        for hops in [2, 5] {
+            assert!(distinct_citers_within(&network, hops)
+                .iter()
+                .all(|c| c.score() == 2));
+        }
+        assert!(distinct_citers_within(&network, 1)
+            .iter()
+            .all(|c| c.score() == 1));
+        assert!(distinct_citers_within(&CitationNetwork::new(), 2).is_empty());
+    }
+}
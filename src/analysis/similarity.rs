@@ -0,0 +1,129 @@
+use std::collections::HashSet;
+
+use crate::network::CitationNetwork;
+
+/// Returns the papers citing a node, or an empty set if it is not in the network
+fn citers(network: &CitationNetwork, vertex: usize) -> HashSet<usize> {
+    if !network.contains_node(vertex) {
+        return HashSet::new();
+    }
+    network.in_edges_to(vertex).copied().collect()
+}
+
+/// Returns the papers cited by a node, or an empty set if it is not in the network
+fn references(network: &CitationNetwork, vertex: usize) -> HashSet<usize> {
+    if !network.contains_node(vertex) {
+        return HashSet::new();
+    }
+    network.out_edges_from(vertex).copied().collect()
+}
+
+/// Returns the Jaccard similarity of two sets, defined as 0 if both are empty
+fn jaccard(a: &HashSet<usize>, b: &HashSet<usize>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Returns the Jaccard similarity between the citers of two papers
+///
+/// Two papers cited by exactly the same papers have similarity 1, and two papers
+/// with no common citer have similarity 0.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `a` - The id of the first paper
+/// * `b` - The id of the second paper
+pub fn jaccard_citers(network: &CitationNetwork, a: usize, b: usize) -> f64 {
+    jaccard(&citers(network, a), &citers(network, b))
+}
+
+/// Returns the Jaccard similarity between the references of two papers
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `a` - The id of the first paper
+/// * `b` - The id of the second paper
+pub fn jaccard_references(network: &CitationNetwork, a: usize, b: usize) -> f64 {
+    jaccard(&references(network, a), &references(network, b))
+}
+
+/// Returns the papers whose citers are most similar to those of a query paper
+///
+/// Only papers sharing at least one citer with the query are examined, since every
+/// other paper has similarity 0. The results are sorted by similarity in
+/// non-ascending order, with ties broken by vertex id.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `query` - The id of the query paper
+/// * `n` - The number of papers to return
+pub fn most_similar_by_citers(
+    network: &CitationNetwork,
+    query: usize,
+    n: usize,
+) -> Vec<(usize, f64)> {
+    let query_citers = citers(network, query);
+    let candidates: HashSet<usize> = query_citers
+        .iter()
+        .flat_map(|&citer| network.out_edges_from(citer).copied())
+        .filter(|&candidate| candidate != query)
+        .collect();
+    let mut similarities: Vec<_> = candidates
+        .into_iter()
+        .map(|candidate| {
+            let similarity = jaccard(&query_citers, &citers(network, candidate));
+            (candidate, similarity)
+        })
+        .collect();
+    similarities.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap().then(a.0.cmp(&b.0)));
+    similarities.truncate(n);
+    similarities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jaccard_identical_and_disjoint() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 2);
+        network.add_edge(0, 3);
+        network.add_edge(1, 2);
+        network.add_edge(1, 3);
+        network.add_edge(4, 5);
+        assert_eq!(jaccard_citers(&network, 2, 3), 1.0);
+        assert_eq!(jaccard_citers(&network, 2, 5), 0.0);
+        assert_eq!(jaccard_references(&network, 0, 1), 1.0);
+        assert_eq!(jaccard_references(&network, 0, 4), 0.0);
+        // Neither paper cites anything
+        assert_eq!(jaccard_references(&network, 2, 3), 0.0);
+        assert_eq!(jaccard_citers(&network, 0, 42), 0.0);
+    }
+
+    #[test]
+    fn test_most_similar_by_citers() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 10);
+        network.add_edge(1, 10);
+        network.add_edge(2, 10);
+        network.add_edge(0, 11);
+        network.add_edge(1, 11);
+        network.add_edge(2, 11);
+        network.add_edge(0, 12);
+        network.add_edge(3, 12);
+        network.add_edge(3, 13);
+        let similar = most_similar_by_citers(&network, 10, 5);
+        assert_eq!(similar.len(), 2);
+        assert_eq!(similar[0], (11, 1.0));
+        assert_eq!(similar[1].0, 12);
+        assert!((similar[1].1 - 0.25).abs() < 1e-12);
+        assert_eq!(most_similar_by_citers(&network, 10, 1), vec![(11, 1.0)]);
+    }
+}
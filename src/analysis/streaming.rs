@@ -0,0 +1,220 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Keeps the most cited papers up to date while citations arrive one at a time
+///
+/// The tracker counts exactly how many times each paper is cited and keeps the `k`
+/// most cited papers sorted, so the top list is available at any time without
+/// rebuilding the network. Papers with the same count are ordered by id, as in
+/// [`crate::centrality::degree_centrality::calculate_degree_centrality`].
+pub struct TopCitedTracker {
+    k: usize,
+    counts: HashMap<usize, u64>,
+    /// The `k` most cited papers, sorted by decreasing count and then by id
+    top: Vec<(usize, u64)>,
+}
+
+impl TopCitedTracker {
+    /// Creates a tracker for the `k` most cited papers
+    pub fn new(k: usize) -> TopCitedTracker {
+        TopCitedTracker {
+            k,
+            counts: HashMap::new(),
+            top: Vec::with_capacity(k),
+        }
+    }
+    /// Records a citation
+    ///
+    /// Counts only grow, so the only paper that can enter the top list is the cited
+    /// one, and it does so as soon as it overtakes the last paper in the list.
+    ///
+    /// # Arguments
+    ///
+    /// * `_from` - The citing paper, which does not affect the counts
+    /// * `to` - The cited paper
+    pub fn observe_edge(&mut self, _from: usize, to: usize) {
+        let count = self.counts.entry(to).or_insert(0);
+        *count += 1;
+        let entry = (to, *count);
+        if let Some(position) = self.top.iter().position(|&(vertex, _)| vertex == to) {
+            self.top[position] = entry;
+        } else if self.top.len() < self.k {
+            self.top.push(entry);
+        } else if self.top.last().is_some_and(|&last| key(entry) < key(last)) {
+            *self.top.last_mut().unwrap() = entry;
+        } else {
+            return;
+        }
+        self.top.sort_by_key(|&entry| key(entry));
+    }
+    /// Returns the most cited papers with their citation counts, most cited first
+    pub fn current_top(&self) -> Vec<(usize, u64)> {
+        self.top.clone()
+    }
+    /// Returns the number of times a paper has been cited so far
+    pub fn count(&self, vertex: usize) -> u64 {
+        self.counts.get(&vertex).copied().unwrap_or(0)
+    }
+    /// Adds the citations counted by another tracker, such as one that processed
+    /// another shard of the same stream
+    ///
+    /// The top list is rebuilt from the combined counts and keeps the size of this
+    /// tracker.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The tracker to merge into this one
+    pub fn merge(&mut self, other: TopCitedTracker) {
+        for (vertex, count) in other.counts {
+            *self.counts.entry(vertex).or_insert(0) += count;
+        }
+        let mut entries: Vec<(usize, u64)> = self.counts.iter().map(|(&v, &c)| (v, c)).collect();
+        entries.sort_unstable_by_key(|&entry| key(entry));
+        entries.truncate(self.k);
+        self.top = entries;
+    }
+}
+
+/// Counts the in-degree and out-degree of every paper while citations arrive one at
+/// a time
+///
+/// Only the two counts of each paper are kept, never the citations themselves, so
+/// the degrees of an edge list too large to load can be computed by feeding it an
+/// [`crate::network::stream::EdgeStream`]. Repeated citations and self-citations
+/// are counted, as in a [`crate::network::CitationNetwork`].
+#[derive(Default)]
+pub struct StreamingDegrees {
+    in_degrees: HashMap<usize, usize>,
+    out_degrees: HashMap<usize, usize>,
+    num_edges: usize,
+}
+
+impl StreamingDegrees {
+    /// Creates a counter that has seen no citations
+    pub fn new() -> StreamingDegrees {
+        StreamingDegrees::default()
+    }
+    /// Records a citation
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The citing paper
+    /// * `to` - The cited paper
+    pub fn observe_edge(&mut self, from: usize, to: usize) {
+        *self.out_degrees.entry(from).or_insert(0) += 1;
+        *self.in_degrees.entry(to).or_insert(0) += 1;
+        self.num_edges += 1;
+    }
+    /// Returns the number of times a paper has been cited so far
+    pub fn in_degree(&self, vertex: usize) -> usize {
+        self.in_degrees.get(&vertex).copied().unwrap_or(0)
+    }
+    /// Returns the number of citations a paper has made so far
+    pub fn out_degree(&self, vertex: usize) -> usize {
+        self.out_degrees.get(&vertex).copied().unwrap_or(0)
+    }
+    /// Returns the in-degrees of the papers cited so far
+    pub fn in_degrees(&self) -> &HashMap<usize, usize> {
+        &self.in_degrees
+    }
+    /// Returns the out-degrees of the papers that have cited another so far
+    pub fn out_degrees(&self) -> &HashMap<usize, usize> {
+        &self.out_degrees
+    }
+    /// Returns the number of citations recorded
+    pub fn num_edges(&self) -> usize {
+        self.num_edges
+    }
+}
+
+impl Extend<(usize, usize)> for StreamingDegrees {
+    fn extend<I: IntoIterator<Item = (usize, usize)>>(&mut self, edges: I) {
+        for (from, to) in edges {
+            self.observe_edge(from, to);
+        }
+    }
+}
+
+/// The order of the top list: decreasing count, then increasing id
+fn key((vertex, count): (usize, u64)) -> (Reverse<u64>, usize) {
+    (Reverse(count), vertex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::centrality::centrality::Centrality;
+    use crate::centrality::degree_centrality::calculate_degree_centrality;
+    use crate::generators::random_edges;
+    use crate::network::CitationNetwork;
+    use crate::random::Random;
+
+    #[test]
+    fn test_tracker_matches_degree_centrality() {
+        let mut edges = random_edges(500, 5_000, 42);
+        Random::new(7).shuffle(&mut edges);
+        let mut network = CitationNetwork::new();
+        let mut tracker = TopCitedTracker::new(10);
+        let mut left = TopCitedTracker::new(10);
+        let mut right = TopCitedTracker::new(10);
+        for (i, &(from, to)) in edges.iter().enumerate() {
+            network.add_edge(from, to);
+            tracker.observe_edge(from, to);
+            if i % 2 == 0 {
+                left.observe_edge(from, to);
+            } else {
+                right.observe_edge(from, to);
+            }
+        }
+        let expected: Vec<(usize, u64)> = calculate_degree_centrality(&network)
+            .top(10)
+            .iter()
+            .map(|c| (c.vertex(), c.score() as u64))
+            .collect();
+        assert_eq!(tracker.current_top(), expected);
+        left.merge(right);
+        assert_eq!(left.current_top(), expected);
+    }
+
+    #[test]
+    fn test_late_climber_enters_top() {
+        let mut tracker = TopCitedTracker::new(2);
+        for to in [1, 1, 1, 2, 2, 3] {
+            tracker.observe_edge(0, to);
+        }
+        assert_eq!(tracker.current_top(), vec![(1, 3), (2, 2)]);
+        tracker.observe_edge(0, 3);
+        // 3 ties with 2 and stays out because its id is larger
+        assert_eq!(tracker.current_top(), vec![(1, 3), (2, 2)]);
+        tracker.observe_edge(0, 3);
+        tracker.observe_edge(0, 3);
+        assert_eq!(tracker.current_top(), vec![(3, 4), (1, 3)]);
+        assert_eq!(tracker.count(2), 2);
+        assert_eq!(tracker.count(42), 0);
+        assert!(TopCitedTracker::new(0).current_top().is_empty());
+    }
+
+    #[test]
+    fn test_streaming_degrees_match_network() {
+        let edges = random_edges(200, 1_000, 3);
+        let input = crate::generators::edge_list(&edges);
+        let mut degrees = StreamingDegrees::new();
+        for edge in CitationNetwork::stream_from(std::io::Cursor::new(input)) {
+            let (from, to) = edge.unwrap();
+            degrees.observe_edge(from, to);
+        }
+        let network: CitationNetwork = edges.into_iter().collect();
+        assert_eq!(degrees.num_edges(), network.num_edges());
+        for vertex in network.nodes_sorted() {
+            assert_eq!(degrees.in_degree(vertex), network.in_degree(vertex));
+            assert_eq!(degrees.out_degree(vertex), network.out_degree(vertex));
+        }
+        assert_eq!(degrees.in_degree(5_000), 0);
+        let cited = network
+            .nodes_sorted()
+            .into_iter()
+            .filter(|&vertex| network.in_degree(vertex) > 0)
+            .count();
+        assert_eq!(degrees.in_degrees().len(), cited);
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::centrality::centrality::CentralityRank;
+use crate::centrality::degree_centrality::DegreeMeasure;
+use crate::centrality::measure::{CentralityMeasure, DynScore};
+use crate::centrality::pagerank_centrality::PageRankMeasure;
+use crate::metadata::MetadataStore;
+use crate::network::CitationNetwork;
+
+/// A centrality measure to follow over time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricKind {
+    /// The in-degree centrality
+    Degree,
+    /// The PageRank centrality with the default parameters
+    PageRank,
+}
+
+impl MetricKind {
+    /// Returns the measure as a [`CentralityMeasure`]
+    pub fn measure(self) -> Box<dyn CentralityMeasure> {
+        match self {
+            MetricKind::Degree => Box::new(DegreeMeasure),
+            MetricKind::PageRank => Box::new(PageRankMeasure),
+        }
+    }
+}
+
+/// Ranks the papers of a network as it stood at several points in time
+///
+/// The snapshot for year `Y` has every paper published before `Y` and every
+/// citation between two such papers. The snapshots are cumulative, so they are
+/// built by growing a single network: each one adds only the papers published since
+/// the previous year and their citations. Papers without a valid date in `metadata`
+/// are in no snapshot.
+///
+/// # Arguments
+///
+/// * `network` - The full network
+/// * `metadata` - The publication dates of the papers
+/// * `years` - The years to take snapshots at, in any order
+/// * `metric` - The measure to rank the papers of each snapshot by
+///
+/// # Returns
+///
+/// The rank of each snapshot by year, with one entry for each distinct year.
+pub fn metric_over_time(
+    network: &CitationNetwork,
+    metadata: &MetadataStore,
+    years: &[i32],
+    metric: MetricKind,
+) -> BTreeMap<i32, CentralityRank<f64, DynScore>> {
+    let measure = metric.measure();
+    let mut dated: Vec<(i64, usize)> = network
+        .nodes()
+        .filter_map(|&vertex| Some((metadata.date(vertex)?.year() as i64, vertex)))
+        .collect();
+    dated.sort_unstable();
+    let mut years = years.to_vec();
+    years.sort_unstable();
+    years.dedup();
+    let mut snapshot = CitationNetwork::new();
+    let mut published = 0;
+    let mut ranks = BTreeMap::new();
+    for year in years {
+        let end = published + dated[published..].partition_point(|&(y, _)| y < year as i64);
+        let added: HashSet<usize> = dated[published..end].iter().map(|&(_, v)| v).collect();
+        for &(_, vertex) in &dated[published..end] {
+            snapshot.add_node(vertex);
+        }
+        for &(_, vertex) in &dated[published..end] {
+            for &reference in network.out_edges_from(vertex) {
+                if snapshot.contains_node(reference) {
+                    snapshot.add_edge(vertex, reference);
+                }
+            }
+            // Citations from papers added in the same year were added above
+            for &citer in network.in_edges_to(vertex) {
+                if snapshot.contains_node(citer) && !added.contains(&citer) {
+                    snapshot.add_edge(citer, vertex);
+                }
+            }
+        }
+        published = end;
+        ranks.insert(year, measure.rank(&snapshot));
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::centrality::centrality::Centrality;
+    use crate::generators::erdos_renyi;
+    use crate::random::Random;
+    use std::collections::HashMap;
+
+    /// Papers 0 and 1 from 1999, 2 and 3 from 2000, 4 and 5 from 2001, and the
+    /// undated paper 6
+    fn build_fixture() -> (CitationNetwork, MetadataStore) {
+        let network: CitationNetwork = [(2, 0), (3, 1), (4, 1), (5, 1), (5, 2), (6, 0)]
+            .into_iter()
+            .collect();
+        let mut metadata = MetadataStore::new();
+        for (vertex, date) in [
+            (0, "1999-03-01"),
+            (1, "1999-07-01"),
+            (2, "2000-01-15"),
+            (3, "2000-06-30"),
+            (4, "2001-02-01"),
+            (5, "2001-11-11"),
+        ] {
+            metadata.set_date(vertex, date);
+        }
+        (network, metadata)
+    }
+
+    fn order(rank: &CentralityRank<f64, DynScore>) -> Vec<usize> {
+        rank.iter().map(|c| c.vertex()).collect()
+    }
+
+    #[test]
+    fn test_degree_over_time() {
+        let (network, metadata) = build_fixture();
+        let ranks = metric_over_time(
+            &network,
+            &metadata,
+            &[2002, 2000, 2001, 2000],
+            MetricKind::Degree,
+        );
+        assert_eq!(
+            ranks.keys().copied().collect::<Vec<_>>(),
+            [2000, 2001, 2002]
+        );
+        // Before 2000 there are no citations, and in 2000 papers 0 and 1 are cited
+        // once each
+        assert_eq!(order(&ranks[&2000]), [0, 1]);
+        assert!(ranks[&2000].iter().all(|c| c.score() == 0.0));
+        assert_eq!(order(&ranks[&2001]), [0, 1, 2, 3]);
+        assert_eq!(ranks[&2001][0].score(), 1.0);
+        assert_eq!(ranks[&2001][1].score(), 1.0);
+        // In 2001 paper 1 receives two more citations and overtakes paper 0
+        assert_eq!(order(&ranks[&2002]), [1, 0, 2, 3, 4, 5]);
+        assert_eq!(ranks[&2002][0].score(), 3.0);
+        assert_eq!(ranks[&2002][1].score(), 1.0);
+    }
+
+    #[test]
+    fn test_pagerank_over_time() {
+        let (network, metadata) = build_fixture();
+        let ranks = metric_over_time(&network, &metadata, &[2001, 2002], MetricKind::PageRank);
+        let position =
+            |year: i32, vertex: usize| order(&ranks[&year]).iter().position(|&v| v == vertex);
+        // Papers 0 and 1 are tied before 2001, and the citations of 2001 lift paper
+        // 1 above paper 0
+        assert_eq!(position(2001, 0), Some(0));
+        assert_eq!(position(2001, 1), Some(1));
+        assert_eq!(position(2002, 1), Some(0));
+        assert_eq!(position(2002, 0), Some(1));
+        assert_eq!(position(2002, 6), None);
+        assert!(ranks
+            .values()
+            .all(|rank| (rank.iter().map(|c| c.score()).sum::<f64>() - 1.0).abs() < 1e-9));
+        assert!(metric_over_time(&network, &metadata, &[], MetricKind::PageRank).is_empty());
+    }
+
+    #[test]
+    fn test_snapshots_match_subgraphs() {
+        let network = erdos_renyi(80, 0.05, 8);
+        let mut random = Random::new(2);
+        let mut metadata = MetadataStore::new();
+        let mut year_of = HashMap::new();
+        for vertex in network.nodes_sorted() {
+            let year = 1990 + random.below(10) as i32;
+            metadata.set_date(vertex, &format!("{}-01-01", year));
+            year_of.insert(vertex, year);
+        }
+        let years = [1992, 1995, 1999, 2005];
+        let ranks = metric_over_time(&network, &metadata, &years, MetricKind::Degree);
+        for year in years {
+            let subgraph = network.subgraph_where(|vertex| year_of[&vertex] < year);
+            let expected = DegreeMeasure.compute(&subgraph);
+            let actual: Vec<(usize, f64)> = ranks[&year]
+                .iter()
+                .map(|c| (c.vertex(), c.score()))
+                .collect();
+            assert_eq!(actual, expected);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::centrality::centrality::{Centrality, CentralityRank};
+use crate::centrality::pagerank_centrality::calculate_pagerank_centrality;
+use crate::network::CitationNetwork;
+
+/// How much more central a paper is than its number of citations predicts
+///
+/// An uplift above 1 means the paper has a higher PageRank than the typical paper
+/// cited as often, for example because it is cited by highly ranked papers.
+#[derive(Clone)]
+pub struct UpliftScore {
+    vertex: usize,
+    uplift: f64,
+}
+
+impl UpliftScore {
+    pub fn new(vertex: usize, uplift: f64) -> UpliftScore {
+        UpliftScore { vertex, uplift }
+    }
+}
+
+impl PartialOrd for UpliftScore {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.uplift.partial_cmp(&other.uplift)
+    }
+}
+
+const EPSILON: f64 = 1e-12;
+
+impl PartialEq<Self> for UpliftScore {
+    fn eq(&self, other: &Self) -> bool {
+        (self.uplift - other.uplift).abs() <= EPSILON
+    }
+}
+
+impl Display for UpliftScore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "vertex {}: uplift {}", self.vertex, self.uplift)
+    }
+}
+
+impl Centrality<f64> for UpliftScore {
+    fn vertex(&self) -> usize {
+        self.vertex
+    }
+
+    fn score(&self) -> f64 {
+        self.uplift
+    }
+}
+
+/// Returns the PageRank of every cited paper relative to papers cited as often
+///
+/// The uplift of a paper is its PageRank divided by the mean PageRank of all papers
+/// with the same in-degree, so a paper whose in-degree is unique has uplift 1.
+/// Papers that are never cited are left out: they all have the same PageRank, so
+/// their uplift would always be 1.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+pub fn pagerank_vs_degree(network: &CitationNetwork) -> CentralityRank<f64, UpliftScore> {
+    let page_ranks = calculate_pagerank_centrality(network);
+    // Summed in rank order so that the means do not depend on hashing
+    let mut totals: HashMap<usize, (f64, usize)> = HashMap::new();
+    for centrality in page_ranks.iter() {
+        let total = totals
+            .entry(network.in_degree(centrality.vertex()))
+            .or_insert((0.0, 0));
+        total.0 += centrality.score();
+        total.1 += 1;
+    }
+    let mut ranks: Vec<_> = page_ranks
+        .iter()
+        .filter(|centrality| network.in_degree(centrality.vertex()) > 0)
+        .map(|centrality| {
+            let (total, count) = totals[&network.in_degree(centrality.vertex())];
+            UpliftScore::new(
+                centrality.vertex(),
+                centrality.score() / (total / count as f64),
+            )
+        })
+        .collect();
+    ranks.sort_by(|a, b| b.partial_cmp(a).unwrap().then(a.vertex.cmp(&b.vertex)));
+    CentralityRank::new(ranks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pagerank_vs_degree() {
+        // 2 is cited by the hubs 0 and 1, and 3 by two uncited papers
+        let mut network = CitationNetwork::new();
+        for leaf in 10..15 {
+            network.add_edge(leaf, 0);
+            network.add_edge(leaf + 10, 1);
+        }
+        network.add_edge(0, 2);
+        network.add_edge(1, 2);
+        network.add_edge(30, 3);
+        network.add_edge(31, 3);
+        let ranks = pagerank_vs_degree(&network);
+        assert_eq!(ranks.len(), 4);
+        let uplift: HashMap<usize, f64> = ranks.iter().map(|c| (c.vertex(), c.score())).collect();
+        assert!(uplift[&2] > 1.0);
+        assert!(uplift[&3] < 1.0);
+        assert!((uplift[&2] + uplift[&3] - 2.0).abs() < 1e-12);
+        assert!((uplift[&0] - 1.0).abs() < 1e-12);
+        assert_eq!(ranks[0].vertex(), 2);
+        assert_eq!(ranks[3].vertex(), 3);
+    }
+}
@@ -0,0 +1,502 @@
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+use crate::centrality::centrality::{Centrality, CentralityRank};
+use crate::checkpoint::{decode_state, encode_state, Checkpointer};
+use crate::network::index::NodeIndex;
+use crate::network::CitationNetwork;
+use crate::random::Random;
+
+/// The betweenness centrality of a single paper
+///
+/// The betweenness of a paper is the number of shortest citation chains between
+/// other pairs of papers that pass through it, where a pair joined by several
+/// shortest chains contributes the fraction of them that do.
+#[derive(Clone)]
+pub struct BetweennessCentrality {
+    vertex: usize,
+    betweenness: f64,
+    /// Whether the score was estimated from a sample of sources
+    approximate: bool,
+}
+
+impl BetweennessCentrality {
+    pub fn new(vertex: usize, betweenness: f64, approximate: bool) -> BetweennessCentrality {
+        BetweennessCentrality {
+            vertex,
+            betweenness,
+            approximate,
+        }
+    }
+    /// Returns whether the score was estimated from a sample of sources
+    pub fn is_approximate(&self) -> bool {
+        self.approximate
+    }
+}
+
+impl PartialOrd for BetweennessCentrality {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.betweenness.partial_cmp(&other.betweenness)
+    }
+}
+
+const EPSILON: f64 = 1e-12;
+
+impl PartialEq<Self> for BetweennessCentrality {
+    fn eq(&self, other: &Self) -> bool {
+        (self.betweenness - other.betweenness).abs() <= EPSILON
+    }
+}
+
+impl Display for BetweennessCentrality {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.approximate {
+            write!(
+                f,
+                "vertex {}: betweenness ~{}",
+                self.vertex, self.betweenness
+            )
+        } else {
+            write!(
+                f,
+                "vertex {}: betweenness {}",
+                self.vertex, self.betweenness
+            )
+        }
+    }
+}
+
+impl Centrality<f64> for BetweennessCentrality {
+    fn vertex(&self) -> usize {
+        self.vertex
+    }
+
+    fn score(&self) -> f64 {
+        self.betweenness
+    }
+}
+
+/// The state of Brandes' algorithm over the compact indices of a network
+///
+/// The buffers used by a single-source search are allocated once and reused for
+/// every source.
+pub struct Brandes {
+    /// The distinct papers cited by each node, excluding itself
+    adjacency: Vec<Vec<usize>>,
+    buffers: SearchBuffers,
+}
+
+/// The buffers of a single-source search of Brandes' algorithm
+struct SearchBuffers {
+    /// The nodes in the order they were settled by the search
+    stack: Vec<usize>,
+    /// The predecessors of each node on shortest paths from the source
+    predecessors: Vec<Vec<usize>>,
+    /// The number of shortest paths from the source to each node
+    sigma: Vec<f64>,
+    /// The distance from the source to each node, or -1 if not reached yet
+    distance: Vec<i64>,
+    /// The dependency of the source on each node
+    delta: Vec<f64>,
+    queue: VecDeque<usize>,
+}
+
+impl Brandes {
+    /// Prepares the algorithm for a network
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network to analyze
+    /// * `index` - The compact indices of the network's nodes
+    pub fn new(network: &CitationNetwork, index: &NodeIndex) -> Brandes {
+        let adjacency = search_adjacency(network, index);
+        let buffers = SearchBuffers::new(adjacency.len());
+        Brandes { adjacency, buffers }
+    }
+    /// Adds the dependencies of a single source to the betweenness scores
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The compact index of the source
+    /// * `scale` - The factor each dependency is multiplied by
+    /// * `scores` - The betweenness scores, indexed by compact index
+    pub fn accumulate(&mut self, source: usize, scale: f64, scores: &mut [f64]) {
+        self.buffers
+            .accumulate(&self.adjacency, source, scale, scores);
+    }
+}
+
+/// Returns the distinct papers cited by each node of a network, excluding itself,
+/// by compact index
+fn search_adjacency(network: &CitationNetwork, index: &NodeIndex) -> Vec<Vec<usize>> {
+    index
+        .vertices()
+        .iter()
+        .map(|&vertex| {
+            let mut targets: Vec<usize> = network
+                .out_edges_from(vertex)
+                .filter(|&&to| to != vertex)
+                .map(|&to| index.index_of(to).unwrap())
+                .collect();
+            targets.sort_unstable();
+            targets.dedup();
+            targets
+        })
+        .collect()
+}
+
+impl SearchBuffers {
+    /// Allocates the buffers for a network of `n` nodes
+    fn new(n: usize) -> SearchBuffers {
+        SearchBuffers {
+            stack: Vec::with_capacity(n),
+            predecessors: vec![Vec::new(); n],
+            sigma: vec![0.0; n],
+            distance: vec![-1; n],
+            delta: vec![0.0; n],
+            queue: VecDeque::with_capacity(n),
+        }
+    }
+    /// Adds the dependencies of a single source to the betweenness scores
+    ///
+    /// The buffers are reallocated first if they were used on a network of another
+    /// size.
+    ///
+    /// # Arguments
+    ///
+    /// * `adjacency` - The distinct papers cited by each node, by compact index
+    /// * `source` - The compact index of the source
+    /// * `scale` - The factor each dependency is multiplied by
+    /// * `scores` - The betweenness scores, indexed by compact index
+    fn accumulate(
+        &mut self,
+        adjacency: &[Vec<usize>],
+        source: usize,
+        scale: f64,
+        scores: &mut [f64],
+    ) {
+        if self.sigma.len() != adjacency.len() {
+            *self = SearchBuffers::new(adjacency.len());
+        }
+        for &vertex in &self.stack {
+            self.predecessors[vertex].clear();
+            self.sigma[vertex] = 0.0;
+            self.distance[vertex] = -1;
+            self.delta[vertex] = 0.0;
+        }
+        self.stack.clear();
+        self.sigma[source] = 1.0;
+        self.distance[source] = 0;
+        self.queue.push_back(source);
+        while let Some(vertex) = self.queue.pop_front() {
+            self.stack.push(vertex);
+            for &next in &adjacency[vertex] {
+                if self.distance[next] < 0 {
+                    self.distance[next] = self.distance[vertex] + 1;
+                    self.queue.push_back(next);
+                }
+                if self.distance[next] == self.distance[vertex] + 1 {
+                    self.sigma[next] += self.sigma[vertex];
+                    self.predecessors[next].push(vertex);
+                }
+            }
+        }
+        for &vertex in self.stack.iter().rev() {
+            for &predecessor in &self.predecessors[vertex] {
+                self.delta[predecessor] +=
+                    self.sigma[predecessor] / self.sigma[vertex] * (1.0 + self.delta[vertex]);
+            }
+            if vertex != source {
+                scores[vertex] += scale * self.delta[vertex];
+            }
+        }
+    }
+}
+
+/// Runs Brandes' algorithm from the given sources and ranks the scaled scores
+fn brandes_ranks(
+    network: &CitationNetwork,
+    index: &NodeIndex,
+    sources: &[usize],
+    scale: f64,
+    approximate: bool,
+) -> CentralityRank<f64, BetweennessCentrality> {
+    let mut brandes = Brandes::new(network, index);
+    let mut scores = vec![0.0; index.len()];
+    for &source in sources {
+        brandes.accumulate(source, scale, &mut scores);
+    }
+    sorted_ranks(index, scores, approximate)
+}
+
+/// Converts the scores of the nodes, by compact index, to a rank sorted by score
+fn sorted_ranks(
+    index: &NodeIndex,
+    scores: Vec<f64>,
+    approximate: bool,
+) -> CentralityRank<f64, BetweennessCentrality> {
+    let mut ranks: Vec<_> = scores
+        .into_iter()
+        .enumerate()
+        .map(|(i, score)| BetweennessCentrality::new(index.vertex_at(i), score, approximate))
+        .collect();
+    ranks.sort_by(|a, b| b.partial_cmp(a).unwrap().then(a.vertex.cmp(&b.vertex)));
+    CentralityRank::new(ranks)
+}
+
+/// Returns the betweenness centrality scores of a network
+///
+/// Shortest paths follow the direction of citations, and the scores are not
+/// normalized. Brandes' algorithm takes O(V * E) time.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+pub fn calculate_betweenness_centrality(
+    network: &CitationNetwork,
+) -> CentralityRank<f64, BetweennessCentrality> {
+    let index = network.node_index();
+    let sources: Vec<usize> = (0..index.len()).collect();
+    brandes_ranks(network, &index, &sources, 1.0, false)
+}
+
+#[cfg(feature = "rayon")]
+thread_local! {
+    /// The search buffers of each thread running parallel betweenness, reused by
+    /// all the sources the thread runs
+    static SEARCH_BUFFERS: std::cell::RefCell<SearchBuffers> =
+        std::cell::RefCell::new(SearchBuffers::new(0));
+}
+
+/// The smallest number of sources run by one parallel job, so the scores of a job
+/// are added up over many sources
+#[cfg(feature = "rayon")]
+const MIN_SOURCES_PER_JOB: usize = 32;
+
+/// Returns the betweenness centrality scores of a network, computed on several
+/// threads
+///
+/// The sources are split into jobs of at least 32 sources run on the rayon thread
+/// pool. Each job accumulates the dependencies of its sources into its own scores,
+/// so no locks are taken, and the scores of the jobs are added up at the end. The
+/// search buffers belong to the threads rather than the jobs: each thread allocates
+/// one set and reuses it for every source it runs, and keeps it for the next call.
+/// The scores are added in a different order than by
+/// [`calculate_betweenness_centrality`], so they can differ from its scores by
+/// rounding, by at most about 1e-12 of their size.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+#[cfg(feature = "rayon")]
+pub fn calculate_betweenness_parallel(
+    network: &CitationNetwork,
+) -> CentralityRank<f64, BetweennessCentrality> {
+    use rayon::prelude::*;
+
+    let index = network.node_index();
+    let n = index.len();
+    let adjacency = search_adjacency(network, &index);
+    let scores = (0..n)
+        .into_par_iter()
+        .with_min_len(MIN_SOURCES_PER_JOB)
+        .fold(
+            || vec![0.0; n],
+            |mut scores, source| {
+                SEARCH_BUFFERS.with_borrow_mut(|buffers| {
+                    buffers.accumulate(&adjacency, source, 1.0, &mut scores)
+                });
+                scores
+            },
+        )
+        .reduce(
+            || vec![0.0; n],
+            |mut total, partial| {
+                for (score, part) in total.iter_mut().zip(partial) {
+                    *score += part;
+                }
+                total
+            },
+        );
+    sorted_ranks(&index, scores, false)
+}
+
+/// Returns betweenness centrality scores estimated from a sample of sources
+///
+/// Brandes' dependency accumulation is run from `num_samples` sources chosen
+/// uniformly at random, and the scores are scaled by `V / num_samples` so they
+/// estimate the exact scores. Every returned score is flagged as approximate. If
+/// `num_samples` is at least the number of nodes, every node is a source and the
+/// result equals [`calculate_betweenness_centrality`].
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `num_samples` - The number of sources to sample
+/// * `seed` - The seed used to choose the sources
+pub fn calculate_betweenness_approx(
+    network: &CitationNetwork,
+    num_samples: usize,
+    seed: u64,
+) -> CentralityRank<f64, BetweennessCentrality> {
+    let index = network.node_index();
+    let all: Vec<usize> = (0..index.len()).collect();
+    let sources = if num_samples >= all.len() {
+        all
+    } else {
+        Random::new(seed).sample(&all, num_samples)
+    };
+    let scale = if sources.is_empty() {
+        0.0
+    } else {
+        index.len() as f64 / sources.len() as f64
+    };
+    brandes_ranks(network, &index, &sources, scale, true)
+}
+
+/// Returns the betweenness centrality scores of a network, saving the progress to a
+/// checkpoint
+///
+/// The scores accumulated so far are saved after every `every` sources, and a run
+/// starts from the saved scores if the checkpointer has any, so an interrupted run
+/// can be resumed and gives the same scores as [`calculate_betweenness_centrality`].
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `checkpointer` - Where the progress is saved and resumed from
+/// * `every` - The number of sources between checkpoints
+///
+/// # Returns
+///
+/// The scores, or the error of a failed save, or an error of kind `InvalidData` if
+/// the saved state does not belong to a network with these papers.
+///
+/// # Panics
+///
+/// Panics if `every` is 0.
+pub fn calculate_betweenness_checkpointed(
+    network: &CitationNetwork,
+    checkpointer: &dyn Checkpointer,
+    every: usize,
+) -> io::Result<CentralityRank<f64, BetweennessCentrality>> {
+    assert!(every > 0, "checkpoints must be at least one source apart");
+    let index = network.node_index();
+    let (start, mut scores) = match checkpointer.load() {
+        Some(state) => decode_state(&state, index.vertices())?,
+        None => (0, vec![0.0; index.len()]),
+    };
+    let mut brandes = Brandes::new(network, &index);
+    for source in start..index.len() {
+        brandes.accumulate(source, 1.0, &mut scores);
+        let done = source + 1;
+        if done % every == 0 && done < index.len() {
+            checkpointer.save(&encode_state(done, index.vertices(), &scores))?;
+        }
+    }
+    Ok(sorted_ranks(&index, scores, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkpoint::MemoryCheckpointer;
+    use crate::generators::erdos_renyi;
+    use std::collections::HashMap;
+
+    fn scores(ranks: &CentralityRank<f64, BetweennessCentrality>) -> HashMap<usize, f64> {
+        ranks.iter().map(|c| (c.vertex(), c.score())).collect()
+    }
+
+    #[test]
+    fn test_betweenness_centrality() {
+        // 0 -> 1 -> 3 and 0 -> 2 -> 3 are the two shortest paths from 0 to 3
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        network.add_edge(0, 2);
+        network.add_edge(1, 3);
+        network.add_edge(2, 3);
+        network.add_edge(3, 4);
+        let ranks = calculate_betweenness_centrality(&network);
+        let scores = scores(&ranks);
+        assert_eq!(ranks[0].vertex(), 3);
+        assert_eq!(scores[&3], 3.0);
+        assert_eq!(scores[&1], 1.0);
+        assert_eq!(scores[&2], 1.0);
+        assert_eq!(scores[&0], 0.0);
+        assert_eq!(scores[&4], 0.0);
+        assert!(!ranks[0].is_approximate());
+    }
+
+    #[test]
+    fn test_approximation_with_all_sources_is_exact() {
+        let network = erdos_renyi(60, 0.05, 3);
+        let exact = calculate_betweenness_centrality(&network);
+        let approx = calculate_betweenness_approx(&network, network.size(), 11);
+        assert!(approx[0].is_approximate());
+        assert_eq!(scores(&approx), scores(&exact));
+    }
+
+    #[test]
+    fn test_approximation_correlates_with_exact() {
+        let network = erdos_renyi(200, 0.03, 5);
+        let exact = calculate_betweenness_centrality(&network);
+        let first = calculate_betweenness_approx(&network, 100, 17);
+        let second = calculate_betweenness_approx(&network, 100, 17);
+        assert_eq!(scores(&first), scores(&second));
+        assert!(exact.spearman_correlation(&first) > 0.9);
+    }
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        let network = erdos_renyi(60, 0.05, 3);
+        let exact = scores(&calculate_betweenness_centrality(&network));
+        // The third save fails, stopping the run after 20 of the 60 sources
+        let interrupted = MemoryCheckpointer::new().fail_after(2);
+        assert!(calculate_betweenness_checkpointed(&network, &interrupted, 10).is_err());
+        let state = interrupted.load().unwrap();
+        let vertices = network.node_index().vertices().to_vec();
+        assert_eq!(decode_state(&state, &vertices).unwrap().0, 20);
+        let resumed = MemoryCheckpointer::new();
+        resumed.save(&state).unwrap();
+        let ranks = calculate_betweenness_checkpointed(&network, &resumed, 10).unwrap();
+        assert_eq!(scores(&ranks), exact);
+        // Resuming with the state of another network fails instead of mixing scores
+        let other = erdos_renyi(30, 0.05, 3);
+        assert!(calculate_betweenness_checkpointed(&other, &resumed, 10).is_err());
+        // A network of the same size with other papers is rejected as well
+        let mut renumbered = CitationNetwork::new();
+        for &vertex in network.nodes() {
+            renumbered.add_node(vertex + 1_000);
+            for &reference in network.out_edges_from(vertex) {
+                renumbered.add_edge(vertex + 1_000, reference + 1_000);
+            }
+        }
+        assert_eq!(renumbered.size(), network.size());
+        let error = calculate_betweenness_checkpointed(&renumbered, &resumed, 10)
+            .err()
+            .unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let network = erdos_renyi(300, 0.02, 8);
+        let sequential = scores(&calculate_betweenness_centrality(&network));
+        for num_threads in [1, 3, 8] {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .unwrap();
+            let parallel = scores(&pool.install(|| calculate_betweenness_parallel(&network)));
+            assert_eq!(parallel.len(), sequential.len());
+            for (vertex, score) in &sequential {
+                assert!((parallel[vertex] - score).abs() <= 1e-12 * score.max(1.0));
+            }
+        }
+        assert!(calculate_betweenness_parallel(&CitationNetwork::new()).is_empty());
+    }
+}
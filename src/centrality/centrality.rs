@@ -1,12 +1,18 @@
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
+use std::io::{self, Write};
 use std::marker::PhantomData;
 use std::ops::Index;
 
+use crate::csv::escape_field;
+use crate::ids::IdMapper;
+use crate::metadata::MetadataStore;
+
 /// A trait for the centrality score of a single paper
 ///
 /// Different centrality scores may be of different types. For example,
 /// degree centrality scores are integers, whereas page ranks are floats.
-pub(crate) trait Centrality<T>: PartialOrd + Display + Clone {
+pub trait Centrality<T>: PartialOrd + Display + Clone {
     /// The id of the paper
     fn vertex(&self) -> usize;
     /// The centrality score
@@ -14,18 +20,72 @@ pub(crate) trait Centrality<T>: PartialOrd + Display + Clone {
 }
 
 /// A data structure that stores the centrality scores of a network
-pub(crate) struct CentralityRank<T, U: Centrality<T>> {
+pub struct CentralityRank<T, U: Centrality<T>> {
     ranks: Vec<U>,
     phantom: PhantomData<T>,
 }
 
 impl<T, U: Centrality<T>> CentralityRank<T, U> {
-    pub(crate) fn new(ranks: Vec<U>) -> CentralityRank<T, U> {
+    pub fn new(ranks: Vec<U>) -> CentralityRank<T, U> {
         CentralityRank {
             ranks,
             phantom: PhantomData,
         }
     }
+    /// Returns the number of centrality scores in the rank
+    pub fn len(&self) -> usize {
+        self.ranks.len()
+    }
+    /// Returns whether the rank is empty
+    pub fn is_empty(&self) -> bool {
+        self.ranks.is_empty()
+    }
+    /// Returns an iterator over the centrality scores in rank order
+    pub fn iter(&self) -> impl Iterator<Item = &U> {
+        self.ranks.iter()
+    }
+    /// Pairs the scores of this rank with those of another by vertex
+    ///
+    /// A vertex missing from one of the ranks gets the given default for that rank,
+    /// so every vertex of either rank appears once.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The rank to pair with, which may use a different measure
+    /// * `default` - The score of a vertex missing from this rank
+    /// * `other_default` - The score of a vertex missing from `other`
+    ///
+    /// # Returns
+    ///
+    /// The `(vertex, score, other_score)` triples in ascending order of vertex.
+    pub fn zip<S, V: Centrality<S>>(
+        &self,
+        other: &CentralityRank<S, V>,
+        default: T,
+        other_default: S,
+    ) -> Vec<(usize, T, S)>
+    where
+        T: Clone,
+        S: Clone,
+    {
+        let mut paired: BTreeMap<usize, (Option<T>, Option<S>)> = BTreeMap::new();
+        for centrality in self.iter() {
+            paired.entry(centrality.vertex()).or_default().0 = Some(centrality.score());
+        }
+        for centrality in other.iter() {
+            paired.entry(centrality.vertex()).or_default().1 = Some(centrality.score());
+        }
+        paired
+            .into_iter()
+            .map(|(vertex, (score, other_score))| {
+                (
+                    vertex,
+                    score.unwrap_or_else(|| default.clone()),
+                    other_score.unwrap_or_else(|| other_default.clone()),
+                )
+            })
+            .collect()
+    }
 }
 
 impl<T, U: Centrality<T>> Index<usize> for CentralityRank<T, U> {
@@ -39,17 +99,150 @@ impl<T, U: Centrality<T>> Index<usize> for CentralityRank<T, U> {
 impl<T, U: Centrality<T>> CentralityRank<T, U> {
     /// Returns the vertices with the top centrality scores in a network
     ///
+    /// If the rank has fewer than `n` vertices, all of them are returned, so the top
+    /// of an empty rank is empty.
+    ///
     /// # Arguments
     ///
     /// * `n` - The number of centrality scores to return
-    pub(crate) fn top(&self, n: usize) -> CentralityRank<T, U> {
+    pub fn top(&self, n: usize) -> CentralityRank<T, U> {
+        CentralityRank {
+            ranks: self.ranks.iter().take(n).cloned().collect(),
+            phantom: PhantomData,
+        }
+    }
+    /// Returns the vertices ranked in positions `[start, end)`
+    ///
+    /// Positions start at 0. The range is clamped to the length of the rank, so a
+    /// range starting past the end, or with `end <= start`, returns an empty rank.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first position to return
+    /// * `end` - The position after the last one to return
+    pub fn rank_range(&self, start: usize, end: usize) -> CentralityRank<T, U> {
+        let end = end.min(self.ranks.len());
+        let start = start.min(end);
         CentralityRank {
-            ranks: self.ranks.iter().cloned().take(n).collect(),
+            ranks: self.ranks[start..end].to_vec(),
             phantom: PhantomData,
         }
     }
 }
 
+impl<T: Into<f64>, U: Centrality<T>> CentralityRank<T, U> {
+    /// Returns the `k` centrality scores closest to the score of a vertex
+    ///
+    /// The distance between two scores is their absolute difference. Since the rank
+    /// is sorted by score, the position of the score is found by binary search and
+    /// the nearest scores by expanding outward from it, so ties are broken in favour
+    /// of the higher ranked vertex. The vertex itself is not returned, and the result
+    /// is empty if the vertex is not in the rank.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex` - The id of the paper whose score to compare with
+    /// * `k` - The number of centrality scores to return
+    ///
+    /// # Returns
+    ///
+    /// * `nearest` - The centrality scores in order of increasing distance
+    pub fn k_nearest_by_score(&self, vertex: usize, k: usize) -> Vec<&U> {
+        let Some(centrality) = self.ranks.iter().find(|c| c.vertex() == vertex) else {
+            return Vec::new();
+        };
+        self.nearest_to_score(centrality.score().into(), k, vertex)
+    }
+    /// Returns the `k` centrality scores closest to a score, skipping a vertex
+    fn nearest_to_score(&self, score: f64, k: usize, skipped: usize) -> Vec<&U> {
+        let distance = |index: usize| (self.ranks[index].score().into() - score).abs();
+        // The first position whose score is not above `score`
+        let start = self.ranks.partition_point(|c| c.score().into() > score);
+        let mut nearest = Vec::with_capacity(k.min(self.ranks.len()));
+        // `above` and `below` are the next candidates on either side of `start`
+        let mut above = start.checked_sub(1);
+        let mut below = start;
+        while nearest.len() < k {
+            if below < self.ranks.len() && self.ranks[below].vertex() == skipped {
+                below += 1;
+                continue;
+            }
+            let take_above = match (above, below < self.ranks.len()) {
+                (Some(a), true) => distance(a) <= distance(below),
+                (Some(_), false) => true,
+                (None, true) => false,
+                (None, false) => break,
+            };
+            if take_above {
+                let a = above.unwrap();
+                nearest.push(&self.ranks[a]);
+                above = a.checked_sub(1);
+            } else {
+                nearest.push(&self.ranks[below]);
+                below += 1;
+            }
+        }
+        nearest
+    }
+}
+
+impl<T: Display, U: Centrality<T>> CentralityRank<T, U> {
+    /// Writes the rank as CSV with the columns `rank,vertex,score`
+    ///
+    /// Ranks start at 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the CSV
+    pub fn to_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "rank,vertex,score")?;
+        for (position, centrality) in self.ranks.iter().enumerate() {
+            writeln!(
+                writer,
+                "{},{},{}",
+                position + 1,
+                centrality.vertex(),
+                centrality.score()
+            )?;
+        }
+        Ok(())
+    }
+    /// Writes the rank as CSV with the columns `rank,vertex,original_id,title,score`
+    ///
+    /// The original identifier comes from `mapper` and the title from `metadata`;
+    /// either column is left blank where it is unknown. Fields are escaped as
+    /// described in RFC 4180.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the CSV
+    /// * `mapper` - The original identifiers of the vertices, if available
+    /// * `metadata` - The titles of the vertices, if available
+    pub fn to_csv_with<W: Write>(
+        &self,
+        mut writer: W,
+        mapper: Option<&IdMapper>,
+        metadata: Option<&MetadataStore>,
+    ) -> io::Result<()> {
+        writeln!(writer, "rank,vertex,original_id,title,score")?;
+        for (position, centrality) in self.ranks.iter().enumerate() {
+            let vertex = centrality.vertex();
+            let original = mapper.and_then(|mapper| mapper.original(vertex));
+            let title = metadata.and_then(|metadata| metadata.title(vertex));
+            writeln!(
+                writer,
+                "{},{},{},{},{}",
+                position + 1,
+                vertex,
+                escape_field(original.unwrap_or("")),
+                escape_field(title.unwrap_or("")),
+                centrality.score()
+            )?;
+        }
+        Ok(())
+    }
+}
+
 impl<T, U: Centrality<T>> Display for CentralityRank<T, U> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         for rank in &self.ranks {
@@ -58,3 +251,142 @@ impl<T, U: Centrality<T>> Display for CentralityRank<T, U> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::centrality::degree_centrality::DegreeCentrality;
+    use crate::centrality::pagerank_centrality::PageRankCentrality;
+
+    fn build_rank() -> CentralityRank<i32, DegreeCentrality> {
+        CentralityRank::new(vec![
+            DegreeCentrality::new(1, 12),
+            DegreeCentrality::new(0, 5),
+            DegreeCentrality::new(2, 3),
+        ])
+    }
+
+    #[test]
+    fn test_rank_range() {
+        let rank = build_rank();
+        let middle = rank.rank_range(1, 3);
+        assert_eq!(middle.len(), 2);
+        assert_eq!(middle[0].vertex(), 0);
+        assert_eq!(middle[1].vertex(), 2);
+        let partial = rank.rank_range(2, 10);
+        assert_eq!(partial.len(), 1);
+        assert_eq!(partial[0].vertex(), 2);
+        assert!(rank.rank_range(3, 5).is_empty());
+        assert!(rank.rank_range(7, 9).is_empty());
+        assert!(rank.rank_range(2, 1).is_empty());
+    }
+
+    #[test]
+    fn test_zip() {
+        let rank = build_rank();
+        let other = CentralityRank::new(vec![
+            PageRankCentrality::new(2, 0.5),
+            PageRankCentrality::new(1, 0.3),
+            PageRankCentrality::new(7, 0.2),
+        ]);
+        assert_eq!(
+            rank.zip(&other, -1, 0.0),
+            vec![(0, 5, 0.0), (1, 12, 0.3), (2, 3, 0.5), (7, -1, 0.2)]
+        );
+        let empty = CentralityRank::<i32, DegreeCentrality>::new(Vec::new());
+        assert_eq!(
+            empty.zip(&rank, 0, 0),
+            vec![(0, 0, 5), (1, 0, 12), (2, 0, 3)]
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let mut output = Vec::new();
+        build_rank().to_csv(&mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "rank,vertex,score\n1,1,12\n2,0,5\n3,2,3\n"
+        );
+    }
+
+    #[test]
+    fn test_to_csv_with_ids_and_titles() {
+        let mut mapper = IdMapper::new();
+        mapper.get_or_insert("hep-th/9201015");
+        mapper.get_or_insert("hep-th/9207016");
+        let mut metadata = MetadataStore::new();
+        metadata.set_title(1, "Strings, \"branes\" and duality");
+        metadata.set_title(2, "Plain title");
+        let mut output = Vec::new();
+        build_rank()
+            .to_csv_with(&mut output, Some(&mapper), Some(&metadata))
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "rank,vertex,original_id,title,score\n\
+             1,1,hep-th/9207016,\"Strings, \"\"branes\"\" and duality\",12\n\
+             2,0,hep-th/9201015,,5\n\
+             3,2,,Plain title,3\n"
+        );
+    }
+
+    #[test]
+    fn test_to_csv_with_nothing() {
+        let mut output = Vec::new();
+        build_rank().to_csv_with(&mut output, None, None).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            output.lines().next(),
+            Some("rank,vertex,original_id,title,score")
+        );
+        assert_eq!(output.lines().nth(1), Some("1,1,,,12"));
+    }
+
+    #[test]
+    fn test_k_nearest_by_score() {
+        let rank = CentralityRank::new(vec![
+            DegreeCentrality::new(4, 20),
+            DegreeCentrality::new(1, 12),
+            DegreeCentrality::new(0, 10),
+            DegreeCentrality::new(3, 9),
+            DegreeCentrality::new(2, 3),
+        ]);
+        let vertices = |nearest: Vec<&DegreeCentrality>| -> Vec<usize> {
+            nearest.iter().map(|c| c.vertex()).collect()
+        };
+        assert_eq!(vertices(rank.k_nearest_by_score(0, 2)), vec![3, 1]);
+        assert_eq!(vertices(rank.k_nearest_by_score(0, 3)), vec![3, 1, 2]);
+        assert_eq!(vertices(rank.k_nearest_by_score(4, 2)), vec![1, 0]);
+        assert_eq!(vertices(rank.k_nearest_by_score(2, 10)), vec![3, 0, 1, 4]);
+        assert!(rank.k_nearest_by_score(2, 0).is_empty());
+        assert!(rank.k_nearest_by_score(42, 3).is_empty());
+    }
+
+    #[test]
+    fn test_k_nearest_by_score_breaks_ties_by_rank() {
+        let rank = build_rank();
+        // 5 is 7 away from 12 and 2 away from 3
+        assert_eq!(rank.k_nearest_by_score(0, 1)[0].vertex(), 2);
+        let tied = CentralityRank::new(vec![
+            DegreeCentrality::new(0, 6),
+            DegreeCentrality::new(1, 4),
+            DegreeCentrality::new(2, 2),
+        ]);
+        assert_eq!(tied.k_nearest_by_score(1, 1)[0].vertex(), 0);
+        // Papers with the same score are nearest, in rank order
+        let same = CentralityRank::new(vec![
+            DegreeCentrality::new(5, 8),
+            DegreeCentrality::new(3, 4),
+            DegreeCentrality::new(6, 4),
+            DegreeCentrality::new(1, 4),
+            DegreeCentrality::new(2, 1),
+        ]);
+        let vertices: Vec<usize> = same
+            .k_nearest_by_score(6, 4)
+            .iter()
+            .map(|c| c.vertex())
+            .collect();
+        assert_eq!(vertices, vec![3, 1, 2, 5]);
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use crate::centrality::centrality::{Centrality, CentralityRank};
+
+/// The change in rank and score of a single paper between two rankings
+///
+/// Ranks count from 1 for the highest score. A paper ranked only after the change
+/// is new and has no old rank or score, and a paper ranked only before it has
+/// disappeared and has no new rank or score. The changes are only known for papers
+/// ranked both times.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RankDelta {
+    /// The id of the paper
+    pub vertex: usize,
+    /// The rank before the change, if the paper was ranked
+    pub old_rank: Option<usize>,
+    /// The rank after the change, if the paper is ranked
+    pub new_rank: Option<usize>,
+    /// The score before the change, if the paper was ranked
+    pub old_score: Option<f64>,
+    /// The score after the change, if the paper is ranked
+    pub new_score: Option<f64>,
+    /// The number of places the paper rose, which is negative if it fell
+    pub rank_change: Option<i64>,
+    /// The new score minus the old one
+    pub score_change: Option<f64>,
+}
+
+impl RankDelta {
+    /// Returns whether the paper is only in the ranking after the change
+    pub fn is_new(&self) -> bool {
+        self.old_rank.is_none()
+    }
+    /// Returns whether the paper is only in the ranking before the change
+    pub fn has_disappeared(&self) -> bool {
+        self.new_rank.is_none()
+    }
+}
+
+/// Compares the rankings of the same measure before and after a change to the
+/// network
+///
+/// # Arguments
+///
+/// * `before` - The ranking before the change
+/// * `after` - The ranking after the change
+///
+/// # Returns
+///
+/// A delta for every paper in either ranking. The papers ranked both times come
+/// first, from the one that rose the most to the one that fell the most, then the
+/// new papers by new rank and then the papers that disappeared by old rank. Papers
+/// that moved equally are ordered by id.
+pub fn rank_delta<T: Into<f64> + Copy, U: Centrality<T>>(
+    before: &CentralityRank<T, U>,
+    after: &CentralityRank<T, U>,
+) -> Vec<RankDelta> {
+    let mut paired: BTreeMap<usize, [Option<(usize, f64)>; 2]> = BTreeMap::new();
+    for (i, rank) in [before, after].into_iter().enumerate() {
+        for (position, centrality) in rank.iter().enumerate() {
+            paired.entry(centrality.vertex()).or_default()[i] =
+                Some((position + 1, centrality.score().into()));
+        }
+    }
+    let mut deltas: Vec<RankDelta> = paired
+        .into_iter()
+        .map(|(vertex, [old, new])| {
+            let changes = old
+                .zip(new)
+                .map(|((old_rank, old_score), (new_rank, new_score))| {
+                    (old_rank as i64 - new_rank as i64, new_score - old_score)
+                });
+            RankDelta {
+                vertex,
+                old_rank: old.map(|(rank, _)| rank),
+                new_rank: new.map(|(rank, _)| rank),
+                old_score: old.map(|(_, score)| score),
+                new_score: new.map(|(_, score)| score),
+                rank_change: changes.map(|(rank, _)| rank),
+                score_change: changes.map(|(_, score)| score),
+            }
+        })
+        .collect();
+    // Sorted by group, then by the key of the group, with ties already in id order
+    deltas.sort_by_key(|delta| match (delta.old_rank, delta.new_rank) {
+        (Some(_), Some(_)) => (0, -delta.rank_change.unwrap()),
+        (None, Some(new_rank)) => (1, new_rank as i64),
+        (Some(old_rank), None) => (2, old_rank as i64),
+        (None, None) => unreachable!(),
+    });
+    deltas
+}
+
+/// Returns the papers that rose the most between two rankings
+///
+/// Only papers ranked both times are considered, so new papers are not movers.
+///
+/// # Arguments
+///
+/// * `deltas` - The deltas returned by [`rank_delta`]
+/// * `n` - The number of papers to return
+pub fn top_movers(deltas: &[RankDelta], n: usize) -> &[RankDelta] {
+    let ranked = deltas.partition_point(|delta| delta.rank_change.is_some());
+    &deltas[..ranked.min(n)]
+}
+
+/// A table of the changes between two rankings, with one row per paper
+///
+/// Each row holds the old and new rank and score of a paper and their changes,
+/// with a dash for a value that is not known. The rank change of a new paper reads
+/// `new` and that of a paper that disappeared reads `gone`.
+pub struct RankDeltaTable<'a> {
+    deltas: &'a [RankDelta],
+}
+
+impl<'a> RankDeltaTable<'a> {
+    /// Creates a table of deltas, in the order given
+    ///
+    /// # Arguments
+    ///
+    /// * `deltas` - The rows of the table
+    pub fn new(deltas: &'a [RankDelta]) -> RankDeltaTable<'a> {
+        RankDeltaTable { deltas }
+    }
+}
+
+impl Display for RankDeltaTable<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let cell = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        let mut table: Vec<[String; 7]> = vec![[
+            "vertex",
+            "old rank",
+            "new rank",
+            "rank change",
+            "old score",
+            "new score",
+            "score change",
+        ]
+        .map(String::from)];
+        for delta in self.deltas {
+            let rank_change = match delta.rank_change {
+                Some(change) => format!("{:+}", change),
+                None if delta.is_new() => "new".to_string(),
+                None => "gone".to_string(),
+            };
+            table.push([
+                delta.vertex.to_string(),
+                cell(delta.old_rank.map(|rank| rank.to_string())),
+                cell(delta.new_rank.map(|rank| rank.to_string())),
+                rank_change,
+                cell(delta.old_score.map(|score| score.to_string())),
+                cell(delta.new_score.map(|score| score.to_string())),
+                cell(delta.score_change.map(|change| format!("{:+}", change))),
+            ]);
+        }
+        let widths: Vec<usize> = (0..7)
+            .map(|i| table.iter().map(|cells| cells[i].len()).max().unwrap())
+            .collect();
+        for cells in table {
+            let line: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+                .collect();
+            writeln!(f, "{}", line.join("  ").trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::centrality::degree_centrality::{calculate_degree_centrality, DegreeCentrality};
+    use crate::centrality::pagerank_centrality::calculate_pagerank_centrality;
+    use crate::example_graphs::complete_dag;
+
+    #[test]
+    fn test_cited_paper_is_top_mover() {
+        // Paper 4 is cited only by paper 5 until five new papers cite it
+        let before = complete_dag(6);
+        let mut after = before.clone();
+        for citer in 6..11 {
+            after.add_edge(citer, 4);
+        }
+        let deltas = rank_delta(
+            &calculate_pagerank_centrality(&before),
+            &calculate_pagerank_centrality(&after),
+        );
+        assert_eq!(deltas.len(), 11);
+        let movers = top_movers(&deltas, 1);
+        assert_eq!(movers.len(), 1);
+        assert_eq!(movers[0].vertex, 4);
+        assert_eq!(movers[0].old_rank, Some(5));
+        assert!(movers[0].rank_change.unwrap() > 0);
+        assert!(movers[0].score_change.unwrap() > 0.0);
+        // The new papers follow the papers ranked both times
+        assert!(deltas[..6].iter().all(|delta| !delta.is_new()));
+        assert!(deltas[6..].iter().all(|delta| delta.is_new()));
+        assert_eq!(top_movers(&deltas, 20).len(), 6);
+
+        let deltas = rank_delta(
+            &calculate_degree_centrality(&before),
+            &calculate_degree_centrality(&after),
+        );
+        assert_eq!(deltas[0].vertex, 4);
+        assert_eq!(deltas[0].old_rank, Some(5));
+        assert_eq!(deltas[0].new_rank, Some(1));
+        assert_eq!(deltas[0].rank_change, Some(4));
+        assert_eq!(deltas[0].old_score, Some(1.0));
+        assert_eq!(deltas[0].new_score, Some(6.0));
+        assert_eq!(deltas[0].score_change, Some(5.0));
+        // Paper 5 keeps its place and papers 0 to 3 each fall one place
+        assert_eq!(deltas[1].vertex, 5);
+        assert_eq!(deltas[1].rank_change, Some(0));
+        let fallen: Vec<usize> = deltas[2..6].iter().map(|delta| delta.vertex).collect();
+        assert_eq!(fallen, [0, 1, 2, 3]);
+        assert!(deltas[2..6]
+            .iter()
+            .all(|delta| delta.rank_change == Some(-1) && delta.score_change == Some(0.0)));
+    }
+
+    #[test]
+    fn test_new_and_disappeared_papers() {
+        let before = CentralityRank::new(vec![
+            DegreeCentrality::new(1, 5),
+            DegreeCentrality::new(2, 3),
+            DegreeCentrality::new(3, 1),
+        ]);
+        let after = CentralityRank::new(vec![
+            DegreeCentrality::new(2, 4),
+            DegreeCentrality::new(4, 2),
+            DegreeCentrality::new(1, 1),
+        ]);
+        let deltas = rank_delta(&before, &after);
+        let order: Vec<usize> = deltas.iter().map(|delta| delta.vertex).collect();
+        assert_eq!(order, [2, 1, 4, 3]);
+        assert!(deltas[2].is_new() && !deltas[2].has_disappeared());
+        assert_eq!(deltas[2].new_rank, Some(2));
+        assert_eq!(deltas[2].rank_change, None);
+        assert!(deltas[3].has_disappeared() && !deltas[3].is_new());
+        assert_eq!(deltas[3].old_score, Some(1.0));
+        assert_eq!(deltas[3].score_change, None);
+        assert_eq!(
+            RankDeltaTable::new(&deltas).to_string(),
+            "vertex  old rank  new rank  rank change  old score  new score  score change\n\
+             2       2         1         +1           3          4          +1\n\
+             1       1         3         -2           5          1          -4\n\
+             4       -         2         new          -          2          -\n\
+             3       3         -         gone         1          -          -\n"
+        );
+    }
+
+    #[test]
+    fn test_rank_delta_of_empty_ranks() {
+        let empty: CentralityRank<i32, DegreeCentrality> = CentralityRank::new(Vec::new());
+        let deltas = rank_delta(&empty, &empty);
+        assert!(deltas.is_empty());
+        assert!(top_movers(&deltas, 3).is_empty());
+        assert_eq!(
+            RankDeltaTable::new(&deltas).to_string(),
+            "vertex  old rank  new rank  rank change  old score  new score  score change\n"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::centrality::centrality::{Centrality, CentralityRank};
+use crate::centrality::measure::CentralityMeasure;
 use crate::network::CitationNetwork;
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
@@ -8,13 +9,13 @@ use std::fmt::{Display, Formatter};
 /// The in-degree centrality score of a paper represents the number of times
 /// it is cited by other papers in the network.
 #[derive(Clone)]
-pub(crate) struct DegreeCentrality {
+pub struct DegreeCentrality {
     vertex: usize,
     in_degree: i32,
 }
 
 impl DegreeCentrality {
-    fn new(vertex: usize, in_degree: i32) -> DegreeCentrality {
+    pub fn new(vertex: usize, in_degree: i32) -> DegreeCentrality {
         DegreeCentrality { vertex, in_degree }
     }
 }
@@ -52,38 +53,48 @@ impl Centrality<i32> for DegreeCentrality {
 /// # Arguments
 ///
 /// * `network` - The network to analyze
-pub(crate) fn calculate_degree_centrality(
+pub fn calculate_degree_centrality(
     network: &CitationNetwork,
 ) -> CentralityRank<i32, DegreeCentrality> {
     let mut ranks: Vec<_> = network
-        .in_edges()
-        .map(|(&vertex, edges)| DegreeCentrality::new(vertex, edges.len() as i32))
+        .nodes()
+        .map(|&vertex| DegreeCentrality::new(vertex, network.in_degree(vertex) as i32))
         .collect();
-    ranks.sort_by(|a, b| b.partial_cmp(a).unwrap());
+    ranks.sort_by(|a, b| b.partial_cmp(a).unwrap().then(a.vertex.cmp(&b.vertex)));
     CentralityRank::new(ranks)
 }
 
+/// The in-degree centrality as a [`CentralityMeasure`]
+pub struct DegreeMeasure;
+
+impl CentralityMeasure for DegreeMeasure {
+    fn name(&self) -> &str {
+        "degree"
+    }
+
+    fn compute(&self, network: &CitationNetwork) -> Vec<(usize, f64)> {
+        calculate_degree_centrality(network)
+            .iter()
+            .map(|centrality| (centrality.vertex(), centrality.score() as f64))
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example_graphs::complete_dag;
 
     #[test]
     fn test_calculate_degree_centrality() {
-        let mut graph = CitationNetwork::new();
-        graph.add_edge(0, 1);
-        graph.add_edge(0, 2);
-        graph.add_edge(0, 3);
-        graph.add_edge(1, 2);
-        graph.add_edge(1, 3);
-        graph.add_edge(2, 3);
-        let ranks = calculate_degree_centrality(&graph);
-        assert_eq!(ranks[0].vertex(), 3);
+        let ranks = calculate_degree_centrality(&complete_dag(4));
+        assert_eq!(ranks[0].vertex(), 0);
         assert_eq!(ranks[0].score(), 3);
-        assert_eq!(ranks[1].vertex(), 2);
+        assert_eq!(ranks[1].vertex(), 1);
         assert_eq!(ranks[1].score(), 2);
-        assert_eq!(ranks[2].vertex(), 1);
+        assert_eq!(ranks[2].vertex(), 2);
         assert_eq!(ranks[2].score(), 1);
-        assert_eq!(ranks[3].vertex(), 0);
+        assert_eq!(ranks[3].vertex(), 3);
         assert_eq!(ranks[3].score(), 0);
     }
 }
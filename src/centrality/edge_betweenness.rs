@@ -0,0 +1,194 @@
+use std::collections::VecDeque;
+
+use crate::network::CitationNetwork;
+
+/// Returns the betweenness of every edge of an undirected graph given as sorted
+/// adjacency lists
+///
+/// This is Brandes' algorithm with the dependencies accumulated on edges instead of
+/// nodes, so it costs O(VE). The dependencies are kept in a buffer shaped like the
+/// adjacency lists, with one slot for each end of an edge, so the memory used is
+/// O(V + E). Each unordered pair of nodes is counted once.
+///
+/// # Arguments
+///
+/// * `adjacency` - The sorted neighbors of each node, with every edge listed at
+///   both of its ends
+///
+/// # Returns
+///
+/// The betweenness of every edge, keyed by its endpoints in ascending order and
+/// ordered by them.
+pub(crate) fn edge_betweenness(adjacency: &[Vec<usize>]) -> Vec<((usize, usize), f64)> {
+    let n = adjacency.len();
+    let mut slots: Vec<Vec<f64>> = adjacency
+        .iter()
+        .map(|neighbors| vec![0.0; neighbors.len()])
+        .collect();
+    let mut distance = vec![usize::MAX; n];
+    let mut paths = vec![0.0; n];
+    let mut dependency = vec![0.0; n];
+    let mut order = Vec::with_capacity(n);
+    let mut queue = VecDeque::with_capacity(n);
+    for source in 0..n {
+        for &vertex in &order {
+            distance[vertex] = usize::MAX;
+            paths[vertex] = 0.0;
+            dependency[vertex] = 0.0;
+        }
+        order.clear();
+        distance[source] = 0;
+        paths[source] = 1.0;
+        queue.push_back(source);
+        while let Some(vertex) = queue.pop_front() {
+            order.push(vertex);
+            for &neighbor in &adjacency[vertex] {
+                if distance[neighbor] == usize::MAX {
+                    distance[neighbor] = distance[vertex] + 1;
+                    queue.push_back(neighbor);
+                }
+                if distance[neighbor] == distance[vertex] + 1 {
+                    paths[neighbor] += paths[vertex];
+                }
+            }
+        }
+        for &vertex in order.iter().rev() {
+            for (slot, &neighbor) in adjacency[vertex].iter().enumerate() {
+                if distance[neighbor] + 1 == distance[vertex] {
+                    let share = paths[neighbor] / paths[vertex] * (1.0 + dependency[vertex]);
+                    slots[vertex][slot] += share;
+                    dependency[neighbor] += share;
+                }
+            }
+        }
+    }
+    // Every pair was counted from both of its ends, at either end of the edge
+    let mut betweenness = Vec::new();
+    for (u, neighbors) in adjacency.iter().enumerate() {
+        for (slot, &v) in neighbors.iter().enumerate() {
+            if u < v {
+                let back = adjacency[v].binary_search(&u).unwrap();
+                betweenness.push(((u, v), (slots[u][slot] + slots[v][back]) / 2.0));
+            }
+        }
+    }
+    betweenness
+}
+
+/// Returns the betweenness of every citation of a network
+///
+/// The betweenness of an edge is the number of shortest paths between pairs of
+/// papers that cross it, where a pair joined by several shortest paths contributes
+/// the fraction of them that do. Edges between research areas carry every path
+/// from one area to the other, so the edges with the highest betweenness are the
+/// weak ties between the areas.
+///
+/// Citations are treated as undirected, with repeated and mutual citations joining
+/// two papers by a single edge and self-citations ignored. The algorithm takes
+/// O(VE) time and O(V + E) memory.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+///
+/// # Returns
+///
+/// Every edge with its betweenness, from the highest to the lowest. Each edge is
+/// given by the ids of its papers in ascending order, and edges with the same
+/// betweenness are ordered by them.
+pub fn calculate_edge_betweenness(network: &CitationNetwork) -> Vec<((usize, usize), f64)> {
+    let index = network.node_index();
+    let adjacency: Vec<Vec<usize>> = index
+        .vertices()
+        .iter()
+        .map(|&vertex| {
+            let mut neighbors: Vec<usize> = network
+                .neighbors(vertex)
+                .into_iter()
+                .map(|neighbor| index.index_of(neighbor).unwrap())
+                .collect();
+            neighbors.sort_unstable();
+            neighbors
+        })
+        .collect();
+    let mut edges: Vec<((usize, usize), f64)> = edge_betweenness(&adjacency)
+        .into_iter()
+        .map(|((u, v), value)| {
+            let (u, v) = (index.vertex_at(u), index.vertex_at(v));
+            ((u.min(v), u.max(v)), value)
+        })
+        .collect();
+    edges.sort_by(|(a, x), (b, y)| y.partial_cmp(x).unwrap().then(a.cmp(b)));
+    edges
+}
+
+/// Returns the `n` edges of a network with the highest betweenness
+///
+/// These are the first edges of [`calculate_edge_betweenness`], the ones most
+/// likely to bridge separate research areas.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `n` - The number of edges to return
+pub fn top_bridging_edges(network: &CitationNetwork, n: usize) -> Vec<((usize, usize), f64)> {
+    let mut edges = calculate_edge_betweenness(network);
+    edges.truncate(n);
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_graphs::{cycle, path, two_cliques_bridge};
+
+    #[test]
+    fn test_bridge_ranks_first() {
+        for k in [3, 4, 6] {
+            let network = two_cliques_bridge(k);
+            let edges = calculate_edge_betweenness(&network);
+            assert_eq!(edges.len(), k * (k - 1) + 1);
+            // Every path between the two cliques of k crosses the bridge
+            assert_eq!(edges[0], ((k - 1, k), (k * k) as f64));
+            // The edges joining the bridge to the rest of its clique carry the paths
+            // from the other clique to that paper, and the others only their ends
+            assert_eq!(edges[1].1, (k + 1) as f64);
+            assert_eq!(edges.last().unwrap().1, 1.0);
+        }
+        let top = top_bridging_edges(&two_cliques_bridge(4), 2);
+        assert_eq!(top, vec![((3, 4), 16.0), ((0, 3), 5.0)]);
+    }
+
+    #[test]
+    fn test_middle_of_path_scores_highest() {
+        // The edge after paper i separates i + 1 papers from the other 6 - i - 1
+        let edges = calculate_edge_betweenness(&path(6));
+        assert_eq!(
+            edges,
+            vec![
+                ((2, 3), 9.0),
+                ((1, 2), 8.0),
+                ((3, 4), 8.0),
+                ((0, 1), 5.0),
+                ((4, 5), 5.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repeated_and_mutual_citations() {
+        let mut network = cycle(4);
+        network.add_edge(1, 0);
+        network.add_edge(0, 1);
+        network.add_edge(2, 2);
+        network.add_node(7);
+        // Each edge of a square carries its ends and half of the two opposite pairs
+        let edges = calculate_edge_betweenness(&network);
+        assert_eq!(edges.len(), 4);
+        assert!(edges.iter().all(|&(_, value)| value == 2.0));
+        assert_eq!(edges[0].0, (0, 1));
+        assert_eq!(edges[3].0, (2, 3));
+        assert!(calculate_edge_betweenness(&CitationNetwork::new()).is_empty());
+        assert!(top_bridging_edges(&path(3), 0).is_empty());
+    }
+}
@@ -0,0 +1,179 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::centrality::centrality::{Centrality, CentralityRank};
+use crate::network::{CitationNetwork, Direction};
+use crate::random::Random;
+
+/// The harmonic centrality of a single paper
+///
+/// The harmonic centrality of a paper is the sum of `1 / d` over every other paper
+/// it reaches at distance `d`. Unlike closeness, unreachable papers simply
+/// contribute nothing, so it is well-defined on disconnected networks.
+#[derive(Clone)]
+pub struct HarmonicCentrality {
+    vertex: usize,
+    harmonic: f64,
+}
+
+impl HarmonicCentrality {
+    pub fn new(vertex: usize, harmonic: f64) -> HarmonicCentrality {
+        HarmonicCentrality { vertex, harmonic }
+    }
+}
+
+impl PartialOrd for HarmonicCentrality {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.harmonic.partial_cmp(&other.harmonic)
+    }
+}
+
+const EPSILON: f64 = 1e-12;
+
+impl PartialEq<Self> for HarmonicCentrality {
+    fn eq(&self, other: &Self) -> bool {
+        (self.harmonic - other.harmonic).abs() <= EPSILON
+    }
+}
+
+impl Display for HarmonicCentrality {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "vertex {}: harmonic {}", self.vertex, self.harmonic)
+    }
+}
+
+impl Centrality<f64> for HarmonicCentrality {
+    fn vertex(&self) -> usize {
+        self.vertex
+    }
+
+    fn score(&self) -> f64 {
+        self.harmonic
+    }
+}
+
+/// Returns the harmonic centrality scores of a network
+///
+/// Runs one breadth-first search per node, so this takes O(V * E) time. Use
+/// [`calculate_harmonic_centrality_sampled`] to approximate the scores on large
+/// networks.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `direction` - Which way distances follow citations: `Outgoing` measures how
+///   closely a paper reaches the work it builds on, `Incoming` how closely it is
+///   reached by the work citing it, and `Undirected` ignores the direction
+pub fn calculate_harmonic_centrality(
+    network: &CitationNetwork,
+    direction: Direction,
+) -> CentralityRank<f64, HarmonicCentrality> {
+    calculate_harmonic_centrality_sampled(network, direction, None, 0)
+}
+
+/// Returns the harmonic centrality scores of a network, optionally approximated
+///
+/// The distance from `v` to `u` in `direction` is the distance from `u` to `v` in
+/// the reverse direction, so a search from each source `u` contributes `1 / d` to
+/// every node it reaches. With sampling, only a uniform sample of sources is
+/// searched and the sums are scaled by `V / sample_sources`, which gives an
+/// unbiased estimate of the exact scores.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `direction` - Which way distances follow citations
+/// * `sample_sources` - The number of sources to search, or `None` for all of them
+/// * `seed` - The seed used to choose the sources
+pub fn calculate_harmonic_centrality_sampled(
+    network: &CitationNetwork,
+    direction: Direction,
+    sample_sources: Option<usize>,
+    seed: u64,
+) -> CentralityRank<f64, HarmonicCentrality> {
+    let vertices = network.nodes_sorted();
+    let sources = match sample_sources {
+        Some(k) if k < vertices.len() => Random::new(seed).sample(&vertices, k),
+        _ => vertices.clone(),
+    };
+    let scale = if sources.is_empty() {
+        0.0
+    } else {
+        vertices.len() as f64 / sources.len() as f64
+    };
+    let mut harmonics: HashMap<usize, f64> = vertices.iter().map(|&v| (v, 0.0)).collect();
+    for &source in &sources {
+        for (vertex, distance) in network.bfs_distances_in(source, direction.reverse()) {
+            if distance > 0 {
+                *harmonics.get_mut(&vertex).unwrap() += scale / distance as f64;
+            }
+        }
+    }
+    let mut ranks: Vec<_> = harmonics
+        .into_iter()
+        .map(|(vertex, harmonic)| HarmonicCentrality::new(vertex, harmonic))
+        .collect();
+    ranks.sort_by(|a, b| b.partial_cmp(a).unwrap().then(a.vertex.cmp(&b.vertex)));
+    CentralityRank::new(ranks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_graphs;
+
+    /// A path from 0 to 3 and the isolated paper 4
+    fn path() -> CitationNetwork {
+        let mut network = example_graphs::path(4);
+        network.add_node(4);
+        network
+    }
+
+    fn scores(ranks: &CentralityRank<f64, HarmonicCentrality>) -> HashMap<usize, f64> {
+        ranks.iter().map(|c| (c.vertex(), c.score())).collect()
+    }
+
+    fn assert_scores(ranks: &CentralityRank<f64, HarmonicCentrality>, expected: [f64; 5]) {
+        let scores = scores(ranks);
+        for (vertex, &score) in expected.iter().enumerate() {
+            assert!((scores[&vertex] - score).abs() < 1e-12, "vertex {}", vertex);
+        }
+    }
+
+    #[test]
+    fn test_harmonic_centrality_outgoing() {
+        let ranks = calculate_harmonic_centrality(&path(), Direction::Outgoing);
+        assert_scores(&ranks, [11.0 / 6.0, 1.5, 1.0, 0.0, 0.0]);
+        assert_eq!(ranks[0].vertex(), 0);
+    }
+
+    #[test]
+    fn test_harmonic_centrality_incoming() {
+        let ranks = calculate_harmonic_centrality(&path(), Direction::Incoming);
+        assert_scores(&ranks, [0.0, 1.0, 1.5, 11.0 / 6.0, 0.0]);
+        assert_eq!(ranks[0].vertex(), 3);
+    }
+
+    #[test]
+    fn test_harmonic_centrality_undirected() {
+        let ranks = calculate_harmonic_centrality(&path(), Direction::Undirected);
+        assert_scores(&ranks, [11.0 / 6.0, 2.5, 2.5, 11.0 / 6.0, 0.0]);
+        assert_eq!(ranks[4].score(), 0.0);
+    }
+
+    #[test]
+    fn test_sampled_harmonic_centrality() {
+        let network = path();
+        let exact = calculate_harmonic_centrality(&network, Direction::Undirected);
+        let all =
+            calculate_harmonic_centrality_sampled(&network, Direction::Undirected, Some(5), 1);
+        assert_eq!(scores(&all), scores(&exact));
+        let first =
+            calculate_harmonic_centrality_sampled(&network, Direction::Undirected, Some(2), 9);
+        let second =
+            calculate_harmonic_centrality_sampled(&network, Direction::Undirected, Some(2), 9);
+        assert_eq!(scores(&first), scores(&second));
+        assert_eq!(scores(&first)[&4], 0.0);
+    }
+}
@@ -0,0 +1,234 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::centrality::centrality::{Centrality, CentralityRank};
+use crate::centrality::degree_centrality::calculate_degree_centrality;
+use crate::centrality::measure::CentralityMeasure;
+use crate::centrality::pagerank_centrality::calculate_pagerank_centrality;
+use crate::network::CitationNetwork;
+
+/// The citation impact score of a single paper
+///
+/// The impact score blends the PageRank and the in-degree of a paper, each
+/// rescaled to `[0, 1]` over the network, so that a paper needs both direct
+/// citations and well-placed citers to score highly.
+#[derive(Clone)]
+pub struct ImpactScore {
+    vertex: usize,
+    impact: f64,
+}
+
+impl ImpactScore {
+    pub fn new(vertex: usize, impact: f64) -> ImpactScore {
+        ImpactScore { vertex, impact }
+    }
+}
+
+impl PartialOrd for ImpactScore {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.impact.partial_cmp(&other.impact)
+    }
+}
+
+const EPSILON: f64 = 1e-12;
+
+impl PartialEq<Self> for ImpactScore {
+    fn eq(&self, other: &Self) -> bool {
+        (self.impact - other.impact).abs() <= EPSILON
+    }
+}
+
+impl Display for ImpactScore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "vertex {}: impact {}", self.vertex, self.impact)
+    }
+}
+
+impl Centrality<f64> for ImpactScore {
+    fn vertex(&self) -> usize {
+        self.vertex
+    }
+
+    fn score(&self) -> f64 {
+        self.impact
+    }
+}
+
+/// The default weight of PageRank in the impact score
+const DEFAULT_ALPHA: f64 = 0.5;
+
+/// The parameters of the impact score
+pub struct ImpactScoreConfig {
+    alpha: f64,
+}
+
+impl Default for ImpactScoreConfig {
+    fn default() -> Self {
+        ImpactScoreConfig {
+            alpha: DEFAULT_ALPHA,
+        }
+    }
+}
+
+impl ImpactScoreConfig {
+    /// Creates a configuration with the default weight of 0.5
+    pub fn new() -> ImpactScoreConfig {
+        ImpactScoreConfig::default()
+    }
+    /// Sets the weight of PageRank in the score
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The weight of PageRank; in-degree gets `1 - alpha`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` is not in `[0, 1]`.
+    pub fn alpha(mut self, alpha: f64) -> ImpactScoreConfig {
+        assert!(
+            (0.0..=1.0).contains(&alpha),
+            "alpha must be in [0, 1], got {}",
+            alpha
+        );
+        self.alpha = alpha;
+        self
+    }
+    /// Calculates the impact scores of a network with this configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network to analyze
+    pub fn calculate(&self, network: &CitationNetwork) -> CentralityRank<f64, ImpactScore> {
+        let pageranks = min_max_normalize(
+            calculate_pagerank_centrality(network)
+                .iter()
+                .map(|c| (c.vertex(), c.score()))
+                .collect(),
+        );
+        let in_degrees = min_max_normalize(
+            calculate_degree_centrality(network)
+                .iter()
+                .map(|c| (c.vertex(), c.score() as f64))
+                .collect(),
+        );
+        let mut ranks: Vec<_> = pageranks
+            .into_iter()
+            .map(|(vertex, pagerank)| {
+                let impact = self.alpha * pagerank + (1.0 - self.alpha) * in_degrees[&vertex];
+                ImpactScore::new(vertex, impact)
+            })
+            .collect();
+        ranks.sort_by(|a, b| b.partial_cmp(a).unwrap().then(a.vertex.cmp(&b.vertex)));
+        CentralityRank::new(ranks)
+    }
+}
+
+/// Rescales scores linearly to `[0, 1]`, mapping constant scores to 0
+fn min_max_normalize(scores: HashMap<usize, f64>) -> HashMap<usize, f64> {
+    let min = scores.values().copied().fold(f64::INFINITY, f64::min);
+    let max = scores.values().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    scores
+        .into_iter()
+        .map(|(vertex, score)| {
+            let normalized = if range > 0.0 {
+                (score - min) / range
+            } else {
+                0.0
+            };
+            (vertex, normalized)
+        })
+        .collect()
+}
+
+/// Returns the citation impact scores of a network
+///
+/// The impact of a paper is `alpha * pagerank + (1 - alpha) * in_degree`, where
+/// both measures are min-max normalized to `[0, 1]` over the network first. Use
+/// [`ImpactScoreConfig`] to calculate it with the default `alpha` of 0.5.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `alpha` - The weight of PageRank, in `[0, 1]`
+pub fn calculate_impact_score(
+    network: &CitationNetwork,
+    alpha: f64,
+) -> CentralityRank<f64, ImpactScore> {
+    ImpactScoreConfig::new().alpha(alpha).calculate(network)
+}
+
+/// The impact score with the default weight as a [`CentralityMeasure`]
+pub struct ImpactMeasure;
+
+impl CentralityMeasure for ImpactMeasure {
+    fn name(&self) -> &str {
+        "impact"
+    }
+
+    fn compute(&self, network: &CitationNetwork) -> Vec<(usize, f64)> {
+        ImpactScoreConfig::new()
+            .calculate(network)
+            .iter()
+            .map(|centrality| (centrality.vertex(), centrality.score()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_network() -> CitationNetwork {
+        // 4 is cited by three papers; 3 is cited only by 2, which everything cites
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 2);
+        network.add_edge(1, 2);
+        network.add_edge(5, 2);
+        network.add_edge(2, 3);
+        network.add_edge(6, 4);
+        network.add_edge(7, 4);
+        network.add_edge(8, 4);
+        network
+    }
+
+    fn scores(ranks: &CentralityRank<f64, ImpactScore>) -> HashMap<usize, f64> {
+        ranks.iter().map(|c| (c.vertex(), c.score())).collect()
+    }
+
+    #[test]
+    fn test_impact_score_extremes() {
+        let network = build_network();
+        let by_degree = scores(&calculate_impact_score(&network, 0.0));
+        assert_eq!(by_degree[&4], 1.0);
+        assert_eq!(by_degree[&2], 1.0);
+        assert!((by_degree[&3] - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(by_degree[&0], 0.0);
+        let by_pagerank = calculate_impact_score(&network, 1.0);
+        assert_eq!(by_pagerank[0].vertex(), 3);
+        assert_eq!(by_pagerank[0].score(), 1.0);
+        assert_eq!(by_pagerank[by_pagerank.len() - 1].score(), 0.0);
+    }
+
+    #[test]
+    fn test_impact_score_default_alpha() {
+        let network = build_network();
+        let default = scores(&ImpactScoreConfig::new().calculate(&network));
+        let explicit = scores(&calculate_impact_score(&network, 0.5));
+        assert_eq!(default, explicit);
+        let by_degree = scores(&calculate_impact_score(&network, 0.0));
+        let by_pagerank = scores(&calculate_impact_score(&network, 1.0));
+        for (vertex, score) in default {
+            let blend = 0.5 * by_degree[&vertex] + 0.5 * by_pagerank[&vertex];
+            assert!((score - blend).abs() < 1e-12);
+            assert!((0.0..=1.0).contains(&score));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_impact_score_rejects_invalid_alpha() {
+        calculate_impact_score(&build_network(), 1.5);
+    }
+}
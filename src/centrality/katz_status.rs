@@ -0,0 +1,206 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+use crate::centrality::centrality::{Centrality, CentralityRank};
+use crate::centrality::measure::CentralityMeasure;
+use crate::network::CitationNetwork;
+
+/// The largest number of path lengths added up
+const MAX_ITERATIONS: usize = 100;
+
+/// The sum of the next terms below which the series is considered converged
+const TOLERANCE: f64 = 1e-12;
+
+/// The attenuation factor as a fraction of the largest factor for which the series
+/// is known to converge
+const ALPHA_FRACTION: f64 = 0.5;
+
+/// The Katz status index of a single paper
+///
+/// The status of a paper is the number of paths of citations ending at it, with a
+/// path of length `k` weighted by `alpha^k`. A citation from a paper that is itself
+/// much cited therefore counts for more than one from an obscure paper.
+#[derive(Clone)]
+pub struct KatzStatus {
+    vertex: usize,
+    status: f64,
+}
+
+impl KatzStatus {
+    pub fn new(vertex: usize, status: f64) -> KatzStatus {
+        KatzStatus { vertex, status }
+    }
+}
+
+impl PartialOrd for KatzStatus {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.status.partial_cmp(&other.status)
+    }
+}
+
+const EPSILON: f64 = 1e-12;
+
+impl PartialEq<Self> for KatzStatus {
+    fn eq(&self, other: &Self) -> bool {
+        (self.status - other.status).abs() <= EPSILON
+    }
+}
+
+impl Display for KatzStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "vertex {}: katz status {}", self.vertex, self.status)
+    }
+}
+
+impl Centrality<f64> for KatzStatus {
+    fn vertex(&self) -> usize {
+        self.vertex
+    }
+
+    fn score(&self) -> f64 {
+        self.status
+    }
+}
+
+/// Returns the Katz status index of every paper of a network
+///
+/// The status of `v` is the sum of `alpha^k` over every path of `k >= 1` citations
+/// ending at `v`, so it counts the papers citing `v`, the papers citing those, and
+/// so on. Katz centrality in its original form sums the paths starting at `v`
+/// instead, which in a citation network rewards papers with long reference lists
+/// rather than influential ones.
+///
+/// The series converges when `alpha` is below `1 / rho`, where `rho` is the
+/// spectral radius of the adjacency matrix. `rho` is at most the smaller of the
+/// largest in-degree and the largest out-degree, so `alpha` is half the inverse of
+/// that bound. The series is truncated after 100 path lengths, or as soon as the
+/// remaining terms are negligible, which in an acyclic network happens once the
+/// longest path has been counted. Repeated citations count once.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+pub fn calculate_katz_status(network: &CitationNetwork) -> CentralityRank<f64, KatzStatus> {
+    let index = network.node_index();
+    let ones = vec![1.0; index.len()];
+    // The number of distinct papers each paper cites and is cited by
+    let out_degrees = network.adj_matvec(&index, &ones);
+    let in_degrees = network.adj_t_matvec(&index, &ones);
+    let max_degree = |degrees: &[f64]| degrees.iter().copied().fold(0.0, f64::max);
+    let radius_bound = max_degree(&out_degrees).min(max_degree(&in_degrees));
+    let alpha = if radius_bound > 0.0 {
+        ALPHA_FRACTION / radius_bound
+    } else {
+        0.0
+    };
+    let mut term: Vec<f64> = in_degrees.iter().map(|&d| alpha * d).collect();
+    let mut status = vec![0.0; index.len()];
+    for _ in 0..MAX_ITERATIONS {
+        for (total, &t) in status.iter_mut().zip(&term) {
+            *total += t;
+        }
+        if term.iter().sum::<f64>() < TOLERANCE {
+            break;
+        }
+        term = network
+            .adj_t_matvec(&index, &term)
+            .into_iter()
+            .map(|t| alpha * t)
+            .collect();
+    }
+    let mut ranks: Vec<KatzStatus> = index
+        .vertices()
+        .iter()
+        .zip(status)
+        .map(|(&vertex, status)| KatzStatus::new(vertex, status))
+        .collect();
+    ranks.sort_by(|a, b| b.partial_cmp(a).unwrap().then(a.vertex.cmp(&b.vertex)));
+    CentralityRank::new(ranks)
+}
+
+/// The Katz status index as a [`CentralityMeasure`]
+pub struct KatzMeasure;
+
+impl CentralityMeasure for KatzMeasure {
+    fn name(&self) -> &str {
+        "katz"
+    }
+
+    fn compute(&self, network: &CitationNetwork) -> Vec<(usize, f64)> {
+        calculate_katz_status(network)
+            .iter()
+            .map(|centrality| (centrality.vertex(), centrality.score()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_graphs::cycle;
+    use crate::generators::erdos_renyi;
+
+    #[test]
+    fn test_katz_status_favors_cited_citers() {
+        // Papers 2 and 3 both have two citations, but 2 cites 3, so 3 also gains
+        // the papers citing 2
+        let network: CitationNetwork = [(0, 2), (1, 2), (2, 3), (4, 3)].into_iter().collect();
+        let ranks = calculate_katz_status(&network);
+        let order: Vec<usize> = ranks.iter().map(|c| c.vertex()).collect();
+        assert_eq!(order, vec![3, 2, 0, 1, 4]);
+        // The largest out-degree is 1, so alpha is 0.5
+        let scores: Vec<f64> = ranks.iter().map(|c| c.score()).collect();
+        assert_eq!(scores, vec![1.5, 1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(
+            ranks.iter().next().unwrap().to_string(),
+            "vertex 3: katz status 1.5"
+        );
+    }
+
+    #[test]
+    fn test_katz_status_of_cycle_converges() {
+        let network = cycle(2);
+        // Each paper is reached by one path of every length: 0.5 + 0.25 + ... = 1
+        for centrality in calculate_katz_status(&network).iter() {
+            assert!((centrality.score() - 1.0).abs() < 1e-11);
+        }
+    }
+
+    #[test]
+    fn test_katz_status_satisfies_recurrence() {
+        // The status is alpha * A^T (1 + status), where alpha is half the inverse of
+        // the smaller maximum degree
+        let network = erdos_renyi(60, 0.08, 3);
+        let index = network.node_index();
+        let ranks = calculate_katz_status(&network);
+        let mut status = vec![0.0; index.len()];
+        for centrality in ranks.iter() {
+            status[index.index_of(centrality.vertex()).unwrap()] = centrality.score();
+        }
+        let ones = vec![1.0; index.len()];
+        let max = |v: Vec<f64>| v.into_iter().fold(0.0, f64::max);
+        let alpha = 0.5
+            / max(network.adj_matvec(&index, &ones)).min(max(network.adj_t_matvec(&index, &ones)));
+        let shifted: Vec<f64> = status.iter().map(|s| 1.0 + s).collect();
+        for (expected, actual) in network
+            .adj_t_matvec(&index, &shifted)
+            .into_iter()
+            .zip(&status)
+        {
+            assert!((alpha * expected - actual).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_katz_status_without_citations() {
+        let mut network = CitationNetwork::new();
+        network.add_node(4);
+        network.add_node(1);
+        let scores: Vec<(usize, f64)> = calculate_katz_status(&network)
+            .iter()
+            .map(|c| (c.vertex(), c.score()))
+            .collect();
+        assert_eq!(scores, vec![(1, 0.0), (4, 0.0)]);
+        assert!(calculate_katz_status(&CitationNetwork::new()).is_empty());
+    }
+}
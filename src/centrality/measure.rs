@@ -0,0 +1,161 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+use crate::analysis::second_order::DistinctCitersMeasure;
+use crate::centrality::centrality::{Centrality, CentralityRank};
+use crate::centrality::core_rank::CoreRankMeasure;
+use crate::centrality::degree_centrality::DegreeMeasure;
+use crate::centrality::impact_score::ImpactMeasure;
+use crate::centrality::katz_status::KatzMeasure;
+use crate::centrality::pagerank_centrality::PageRankMeasure;
+use crate::network::CitationNetwork;
+
+/// A centrality measure whose scores are independent of the score type
+///
+/// `Centrality<T>` keeps the native type of each score, which makes it impossible
+/// to treat different measures uniformly. A `CentralityMeasure` converts every
+/// score to `f64`, so measures can be stored together and selected by name.
+pub trait CentralityMeasure {
+    /// The name used to select the measure, e.g. on the command line
+    fn name(&self) -> &str;
+    /// Computes the score of every node in a network
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network to analyze
+    ///
+    /// # Returns
+    ///
+    /// * `scores` - The vertex and score of every node, sorted by score in non-ascending order
+    fn compute(&self, network: &CitationNetwork) -> Vec<(usize, f64)>;
+    /// Computes the score of every node in a network as a rank
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network to analyze
+    fn rank(&self, network: &CitationNetwork) -> CentralityRank<f64, DynScore> {
+        CentralityRank::new(
+            self.compute(network)
+                .into_iter()
+                .map(|(vertex, score)| DynScore::new(vertex, score))
+                .collect(),
+        )
+    }
+}
+
+/// The score of a single paper under a [`CentralityMeasure`]
+///
+/// The score is a float whatever the native type of the measure, so ranks of
+/// different measures have the same type.
+#[derive(Clone)]
+pub struct DynScore {
+    vertex: usize,
+    score: f64,
+}
+
+impl DynScore {
+    pub fn new(vertex: usize, score: f64) -> DynScore {
+        DynScore { vertex, score }
+    }
+}
+
+impl PartialOrd for DynScore {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.score.partial_cmp(&other.score)
+    }
+}
+
+const EPSILON: f64 = 1e-12;
+
+impl PartialEq<Self> for DynScore {
+    fn eq(&self, other: &Self) -> bool {
+        (self.score - other.score).abs() <= EPSILON
+    }
+}
+
+impl Display for DynScore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "vertex {}: score {}", self.vertex, self.score)
+    }
+}
+
+impl Centrality<f64> for DynScore {
+    fn vertex(&self) -> usize {
+        self.vertex
+    }
+
+    fn score(&self) -> f64 {
+        self.score
+    }
+}
+
+/// Returns the built-in centrality measures
+pub fn registry() -> Vec<Box<dyn CentralityMeasure>> {
+    vec![
+        Box::new(DegreeMeasure),
+        Box::new(PageRankMeasure),
+        Box::new(ImpactMeasure),
+        Box::new(KatzMeasure),
+        Box::new(DistinctCitersMeasure),
+        Box::new(CoreRankMeasure),
+    ]
+}
+
+/// Returns the built-in centrality measure with the given name
+///
+/// # Arguments
+///
+/// * `name` - The name of the measure
+pub fn find_measure(name: &str) -> Option<Box<dyn CentralityMeasure>> {
+    registry()
+        .into_iter()
+        .find(|measure| measure.name() == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_scores_every_node() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(1, 2);
+        graph.add_edge(3, 2);
+        let measures = registry();
+        assert_eq!(measures.len(), 6);
+        for measure in measures {
+            let scores = measure.compute(&graph);
+            assert_eq!(scores.len(), graph.size(), "{}", measure.name());
+            for &vertex in graph.nodes() {
+                assert!(scores.iter().any(|&(v, _)| v == vertex));
+            }
+            assert_eq!(scores[0].0, 2, "{}", measure.name());
+        }
+    }
+
+    #[test]
+    fn test_rank() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(2, 1);
+        let rank = find_measure("degree").unwrap().rank(&graph);
+        let scores: Vec<(usize, f64)> = rank.iter().map(|c| (c.vertex(), c.score())).collect();
+        assert_eq!(scores, vec![(1, 2.0), (0, 0.0), (2, 0.0)]);
+        assert_eq!(rank[0].to_string(), "vertex 1: score 2");
+    }
+
+    #[test]
+    fn test_find_measure() {
+        assert_eq!(find_measure("degree").unwrap().name(), "degree");
+        assert_eq!(find_measure("pagerank").unwrap().name(), "pagerank");
+        assert_eq!(find_measure("katz").unwrap().name(), "katz");
+        assert_eq!(
+            find_measure("distinct-citers").unwrap().name(),
+            "distinct-citers"
+        );
+        assert_eq!(find_measure("core-rank").unwrap().name(), "core-rank");
+        assert!(find_measure("closeness").is_none());
+    }
+}
@@ -0,0 +1,221 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::centrality::centrality::{Centrality, CentralityRank};
+
+/// A way of rescaling centrality scores
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Normalization {
+    /// Rescales the scores linearly to `[0, 1]`
+    MinMax,
+    /// Divides the scores by their sum
+    SumToOne,
+    /// Subtracts the mean and divides by the standard deviation
+    ZScore,
+}
+
+/// A rescaled centrality score of a single paper
+#[derive(Clone)]
+pub struct NormalizedScore {
+    vertex: usize,
+    score: f64,
+}
+
+impl NormalizedScore {
+    pub fn new(vertex: usize, score: f64) -> NormalizedScore {
+        NormalizedScore { vertex, score }
+    }
+}
+
+impl PartialOrd for NormalizedScore {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.score.partial_cmp(&other.score)
+    }
+}
+
+const EPSILON: f64 = 1e-12;
+
+impl PartialEq<Self> for NormalizedScore {
+    fn eq(&self, other: &Self) -> bool {
+        (self.score - other.score).abs() <= EPSILON
+    }
+}
+
+impl Display for NormalizedScore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "vertex {}: normalized {}", self.vertex, self.score)
+    }
+}
+
+impl Centrality<f64> for NormalizedScore {
+    fn vertex(&self) -> usize {
+        self.vertex
+    }
+
+    fn score(&self) -> f64 {
+        self.score
+    }
+}
+
+impl<T: Into<f64>, U: Centrality<T>> CentralityRank<T, U> {
+    /// Returns the scores in rank order as floats
+    fn float_scores(&self) -> Vec<(usize, f64)> {
+        self.iter()
+            .map(|c| (c.vertex(), c.score().into()))
+            .collect()
+    }
+    /// Returns the percentile of every paper within the rank
+    ///
+    /// The percentile of a paper is the fraction of papers with a strictly lower
+    /// score. Tied papers share the average of the percentiles they would have if the
+    /// tie were broken, so a group of `k` tied papers above `m` others each get
+    /// `(m + (k - 1) / 2) / n`.
+    pub fn percentiles(&self) -> HashMap<usize, f64> {
+        let mut scores = self.float_scores();
+        scores.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        let n = scores.len() as f64;
+        let mut percentiles = HashMap::with_capacity(scores.len());
+        for group in scores.chunk_by(|a, b| a.1 == b.1) {
+            let lower = percentiles.len() as f64;
+            let percentile = (lower + (group.len() - 1) as f64 / 2.0) / n;
+            percentiles.extend(group.iter().map(|&(vertex, _)| (vertex, percentile)));
+        }
+        percentiles
+    }
+    /// Returns the z-score of every paper within the rank
+    ///
+    /// The z-score is the difference from the mean score divided by the population
+    /// standard deviation. If every score is equal, every z-score is 0.
+    pub fn z_scores(&self) -> HashMap<usize, f64> {
+        self.normalized(Normalization::ZScore).into_iter().collect()
+    }
+    /// Returns a new rank with the scores rescaled
+    ///
+    /// Rescaling never divides by zero: if every score is equal, min-max and
+    /// z-score normalization give every paper 0, and if the scores sum to 0,
+    /// sum-to-one normalization gives every paper 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - How to rescale the scores
+    pub fn normalize(&self, method: Normalization) -> CentralityRank<f64, NormalizedScore> {
+        let mut ranks: Vec<_> = self
+            .normalized(method)
+            .into_iter()
+            .map(|(vertex, score)| NormalizedScore::new(vertex, score))
+            .collect();
+        ranks.sort_by(|a, b| b.partial_cmp(a).unwrap().then(a.vertex.cmp(&b.vertex)));
+        CentralityRank::new(ranks)
+    }
+    /// Returns the rescaled scores in rank order
+    fn normalized(&self, method: Normalization) -> Vec<(usize, f64)> {
+        let scores = self.float_scores();
+        let n = scores.len() as f64;
+        let rescale: Box<dyn Fn(f64) -> f64> = match method {
+            Normalization::MinMax => {
+                let min = scores.iter().map(|s| s.1).fold(f64::INFINITY, f64::min);
+                let max = scores.iter().map(|s| s.1).fold(f64::NEG_INFINITY, f64::max);
+                let range = max - min;
+                Box::new(move |score| {
+                    if range > 0.0 {
+                        (score - min) / range
+                    } else {
+                        0.0
+                    }
+                })
+            }
+            Normalization::SumToOne => {
+                let sum: f64 = scores.iter().map(|s| s.1).sum();
+                Box::new(move |score| if sum != 0.0 { score / sum } else { 0.0 })
+            }
+            Normalization::ZScore => {
+                let mean = scores.iter().map(|s| s.1).sum::<f64>() / n;
+                let variance = scores.iter().map(|s| (s.1 - mean).powi(2)).sum::<f64>() / n;
+                let deviation = variance.sqrt();
+                Box::new(move |score| {
+                    if deviation > 0.0 {
+                        (score - mean) / deviation
+                    } else {
+                        0.0
+                    }
+                })
+            }
+        };
+        scores
+            .into_iter()
+            .map(|(vertex, score)| (vertex, rescale(score)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::centrality::degree_centrality::DegreeCentrality;
+    use crate::centrality::pagerank_centrality::calculate_pagerank_centrality;
+    use crate::network::CitationNetwork;
+
+    fn build_rank() -> CentralityRank<i32, DegreeCentrality> {
+        CentralityRank::new(vec![
+            DegreeCentrality::new(0, 4),
+            DegreeCentrality::new(1, 2),
+            DegreeCentrality::new(2, 2),
+            DegreeCentrality::new(3, 0),
+        ])
+    }
+
+    #[test]
+    fn test_percentiles_with_ties() {
+        let percentiles = build_rank().percentiles();
+        assert_eq!(percentiles[&3], 0.0);
+        // 1 and 2 are above one paper and tied with each other
+        assert_eq!(percentiles[&1], 0.375);
+        assert_eq!(percentiles[&2], 0.375);
+        assert_eq!(percentiles[&0], 0.75);
+        let constant = CentralityRank::new(vec![
+            DegreeCentrality::new(0, 1),
+            DegreeCentrality::new(1, 1),
+        ]);
+        assert_eq!(constant.percentiles()[&0], 0.25);
+    }
+
+    #[test]
+    fn test_z_scores() {
+        // The mean is 2 and the standard deviation is sqrt(2)
+        let z_scores = build_rank().z_scores();
+        assert!((z_scores[&0] - 2f64.sqrt()).abs() < 1e-12);
+        assert_eq!(z_scores[&1], 0.0);
+        assert!((z_scores[&3] + 2f64.sqrt()).abs() < 1e-12);
+        let constant = CentralityRank::new(vec![DegreeCentrality::new(0, 3)]);
+        assert_eq!(constant.z_scores()[&0], 0.0);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let min_max = build_rank().normalize(Normalization::MinMax);
+        let scores: Vec<(usize, f64)> = min_max.iter().map(|c| (c.vertex(), c.score())).collect();
+        assert_eq!(scores, vec![(0, 1.0), (1, 0.5), (2, 0.5), (3, 0.0)]);
+        let sum_to_one = build_rank().normalize(Normalization::SumToOne);
+        assert_eq!(sum_to_one[0].score(), 0.5);
+        let constant = CentralityRank::new(vec![DegreeCentrality::new(0, 0)]);
+        assert_eq!(constant.normalize(Normalization::MinMax)[0].score(), 0.0);
+        assert_eq!(constant.normalize(Normalization::SumToOne)[0].score(), 0.0);
+        assert_eq!(constant.normalize(Normalization::ZScore)[0].score(), 0.0);
+    }
+
+    #[test]
+    fn test_normalize_pagerank_to_one_is_a_no_op() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        network.add_edge(0, 2);
+        network.add_edge(1, 2);
+        network.add_edge(3, 2);
+        let ranks = calculate_pagerank_centrality(&network);
+        let normalized = ranks.normalize(Normalization::SumToOne);
+        for (original, normalized) in ranks.iter().zip(normalized.iter()) {
+            assert_eq!(original.vertex(), normalized.vertex());
+            assert!((original.score() - normalized.score()).abs() < 1e-9);
+        }
+    }
+}
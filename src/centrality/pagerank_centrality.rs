@@ -1,19 +1,26 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::f64::consts::LN_2;
 use std::fmt::{Display, Formatter};
+use std::io::{self, Write};
 
 use crate::centrality::centrality::{Centrality, CentralityRank};
-use crate::network::CitationNetwork;
+use crate::centrality::measure::CentralityMeasure;
+use crate::checkpoint::{decode_state, encode_state, Checkpointer};
+use crate::network::index::NodeIndex;
+use crate::network::matrix::SparseMatrix;
+use crate::network::temporal::TimestampedCitationNetwork;
+use crate::network::{CitationNetwork, UnknownVertex};
 
 /// The PageRank of a single node in the network.
 #[derive(Clone)]
-pub(crate) struct PageRankCentrality {
+pub struct PageRankCentrality {
     vertex: usize,
     pagerank: f64,
 }
 
 impl PageRankCentrality {
-    fn new(vertex: usize, pagerank: f64) -> PageRankCentrality {
+    pub fn new(vertex: usize, pagerank: f64) -> PageRankCentrality {
         PageRankCentrality { vertex, pagerank }
     }
 }
@@ -52,90 +59,1233 @@ const DAMPING_FACTOR: f64 = 0.85;
 const MAX_ITERATIONS: usize = 100;
 const TOLERANCE: f64 = 1e-9;
 
-/// Performs one iteration of the PageRank algorithm.
+/// The parameters of the PageRank algorithm
+#[derive(Clone, Debug)]
+pub struct PageRankConfig {
+    damping_factor: f64,
+    max_iterations: usize,
+    tolerance: f64,
+    /// The scores the iterations start from, or `None` for the uniform distribution
+    initial_scores: Option<HashMap<usize, f64>>,
+}
+
+/// Where the random surfer of PageRank goes next
+struct Surfer<'a> {
+    /// The probability of jumping to each node, summing to 1
+    teleport: &'a dyn Fn(usize) -> f64,
+    /// The probability of following a citation from each node rather than jumping
+    damping: &'a dyn Fn(usize) -> f64,
+    /// The probability of following each citation, given the citing and cited nodes,
+    /// when the surfer follows one; `None` chooses among the citations uniformly
+    share: Option<&'a dyn Fn(usize, usize) -> f64>,
+}
+
+/// The moves of the random surfer of PageRank, precomputed for every paper at the
+/// compact indices given by [`CitationNetwork::node_index`]
+///
+/// The iterations only read these vectors and the transition matrix, so they never
+/// look up a paper by id.
+struct Walk {
+    index: NodeIndex,
+    /// The probability of following each citation when the surfer follows one
+    transition: SparseMatrix,
+    teleport: Vec<f64>,
+    damping: Vec<f64>,
+    /// Whether each paper has no references, so the surfer always jumps from it
+    sink: Vec<bool>,
+}
+
+impl Surfer<'_> {
+    /// Evaluates where the surfer goes next from every paper of a network
+    fn walk(&self, network: &CitationNetwork) -> Walk {
+        let index = network.node_index();
+        let transition = match self.share {
+            Some(share) => network.build_transition_matrix_with(share),
+            None => network.build_transition_matrix(),
+        };
+        let vertices = index.vertices();
+        Walk {
+            transition,
+            teleport: vertices.iter().map(|&v| (self.teleport)(v)).collect(),
+            damping: vertices.iter().map(|&v| (self.damping)(v)).collect(),
+            sink: vertices
+                .iter()
+                .map(|&v| network.out_degree(v) == 0)
+                .collect(),
+            index,
+        }
+    }
+}
+
+impl Default for PageRankConfig {
+    fn default() -> Self {
+        PageRankConfig {
+            damping_factor: DAMPING_FACTOR,
+            max_iterations: MAX_ITERATIONS,
+            tolerance: TOLERANCE,
+            initial_scores: None,
+        }
+    }
+}
+
+impl PageRankConfig {
+    /// Creates a configuration with a damping factor of 0.85, at most 100 iterations
+    /// and a tolerance of 1e-9
+    pub fn new() -> PageRankConfig {
+        PageRankConfig::default()
+    }
+    /// Sets the probability of following a citation rather than jumping to a random
+    /// paper
+    ///
+    /// # Arguments
+    ///
+    /// * `damping_factor` - The damping factor, in `[0, 1]`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `damping_factor` is not in `[0, 1]`.
+    pub fn damping_factor(mut self, damping_factor: f64) -> PageRankConfig {
+        assert!(
+            (0.0..=1.0).contains(&damping_factor),
+            "damping factor must be in [0, 1], got {}",
+            damping_factor
+        );
+        self.damping_factor = damping_factor;
+        self
+    }
+    /// Sets the maximum number of iterations
+    ///
+    /// # Arguments
+    ///
+    /// * `max_iterations` - The number of iterations after which to stop
+    pub fn max_iterations(mut self, max_iterations: usize) -> PageRankConfig {
+        self.max_iterations = max_iterations;
+        self
+    }
+    /// Sets the total change in scores below which an iteration has converged
+    ///
+    /// A tolerance of 0 never converges, so exactly `max_iterations` iterations run.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - The convergence threshold
+    pub fn tolerance(mut self, tolerance: f64) -> PageRankConfig {
+        self.tolerance = tolerance;
+        self
+    }
+    /// Sets the scores the iterations start from instead of the uniform distribution
+    ///
+    /// Vertices missing from the map start at 0, and the scores are divided by their
+    /// sum before the first iteration. Every vertex in the map must be in the network
+    /// the configuration is used on.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_scores` - The starting score of each vertex
+    ///
+    /// # Panics
+    ///
+    /// Panics if a score is negative or not finite, or if the scores do not have a
+    /// positive sum.
+    pub fn initial_scores(mut self, initial_scores: HashMap<usize, f64>) -> PageRankConfig {
+        assert!(
+            initial_scores
+                .values()
+                .all(|&score| score.is_finite() && score >= 0.0),
+            "initial scores must be finite and non-negative"
+        );
+        assert!(
+            initial_scores.values().sum::<f64>() > 0.0,
+            "initial scores must have a positive sum"
+        );
+        self.initial_scores = Some(initial_scores);
+        self
+    }
+    /// Calculates the PageRank centrality scores of a network with this
+    /// configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network to analyze
+    ///
+    /// # Returns
+    ///
+    /// The scores, or the first vertex of the initial scores that is not in the
+    /// network.
+    pub fn calculate(
+        &self,
+        network: &CitationNetwork,
+    ) -> Result<CentralityRank<f64, PageRankCentrality>, UnknownVertex> {
+        self.initial_page_ranks(&network.node_index())?;
+        Ok(self.calculate_checked(network))
+    }
+    /// Calculates the PageRank centrality scores of a network whose initial scores,
+    /// if any, have been checked against it
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network to analyze
+    fn calculate_checked(
+        &self,
+        network: &CitationNetwork,
+    ) -> CentralityRank<f64, PageRankCentrality> {
+        let num_nodes = network.size() as f64;
+        let surfer = Surfer {
+            teleport: &|_| 1.0 / num_nodes,
+            damping: &|_| self.damping_factor,
+            share: None,
+        };
+        sorted_ranks(self.run(network, &surfer, &mut |_, _| {}).0)
+    }
+    /// Calculates the PageRank centrality scores of a network, saving the progress to
+    /// a checkpoint
+    ///
+    /// The scores and the number of iterations run are saved after every `every`
+    /// iterations, and a run starts from the saved state if the checkpointer has one,
+    /// so an interrupted run can be resumed and gives the same scores as
+    /// [`PageRankConfig::calculate`].
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network to analyze
+    /// * `checkpointer` - Where the progress is saved and resumed from
+    /// * `every` - The number of iterations between checkpoints
+    ///
+    /// # Returns
+    ///
+    /// The scores, or the error of a failed save, or an error of kind `InvalidData`
+    /// if the saved state does not belong to a network with these papers, or an
+    /// error of kind `InvalidInput` if the initial scores contain a vertex that is
+    /// not in the network.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `every` is 0.
+    pub fn calculate_checkpointed(
+        &self,
+        network: &CitationNetwork,
+        checkpointer: &dyn Checkpointer,
+        every: usize,
+    ) -> io::Result<CentralityRank<f64, PageRankCentrality>> {
+        assert!(
+            every > 0,
+            "checkpoints must be at least one iteration apart"
+        );
+        let num_nodes = network.size() as f64;
+        let surfer = Surfer {
+            teleport: &|_| 1.0 / num_nodes,
+            damping: &|_| self.damping_factor,
+            share: None,
+        };
+        let walk = surfer.walk(network);
+        let (mut num_iterations, mut page_ranks) = match checkpointer.load() {
+            Some(state) => decode_state(&state, walk.index.vertices())?,
+            None => {
+                let page_ranks = self
+                    .initial_page_ranks(&walk.index)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+                (0, page_ranks)
+            }
+        };
+        let mut converged = false;
+        while !converged && num_iterations < self.max_iterations {
+            converged = self.iterate(&walk, &mut page_ranks);
+            num_iterations += 1;
+            if !converged && num_iterations % every == 0 && num_iterations < self.max_iterations {
+                checkpointer.save(&encode_state(
+                    num_iterations,
+                    walk.index.vertices(),
+                    &page_ranks,
+                ))?;
+            }
+        }
+        renormalize(&mut page_ranks);
+        Ok(sorted_ranks(
+            walk.index.vertices().iter().copied().zip(page_ranks),
+        ))
+    }
+    /// Runs the PageRank algorithm
+    ///
+    /// The initial scores must have been checked against the network with
+    /// [`PageRankConfig::initial_page_ranks`].
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network to analyze
+    /// * `surfer` - Where the random surfer goes next
+    /// * `on_iteration` - Called with the scores after each iteration, at the
+    ///   compact indices of the network
+    ///
+    /// # Returns
+    ///
+    /// * `(page_ranks, iterations, converged, mass)` - The scores in ascending order
+    ///   of id, renormalized to sum to 1, the number of iterations run, whether the
+    ///   last one converged and the sum of the scores before renormalization
+    fn run(
+        &self,
+        network: &CitationNetwork,
+        surfer: &Surfer,
+        on_iteration: &mut dyn FnMut(&NodeIndex, &[f64]),
+    ) -> (Vec<(usize, f64)>, usize, bool, f64) {
+        let walk = surfer.walk(network);
+        let mut page_ranks = self
+            .initial_page_ranks(&walk.index)
+            .expect("initial scores are checked before running");
+        let mut converged = false;
+        let mut num_iterations = 0;
+        while !converged && num_iterations < self.max_iterations {
+            converged = self.iterate(&walk, &mut page_ranks);
+            num_iterations += 1;
+            on_iteration(&walk.index, &page_ranks);
+        }
+        let mass = renormalize(&mut page_ranks);
+        let page_ranks = walk.index.vertices().iter().copied().zip(page_ranks);
+        (page_ranks.collect(), num_iterations, converged, mass)
+    }
+    /// Returns the scores the iterations start from, summing to 1
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The compact indices of the papers of the network
+    ///
+    /// # Returns
+    ///
+    /// The initial score at the index of each paper, uniform unless set with
+    /// [`PageRankConfig::initial_scores`], or the first vertex of the initial scores
+    /// that is not in the network.
+    fn initial_page_ranks(&self, index: &NodeIndex) -> Result<Vec<f64>, UnknownVertex> {
+        let Some(initial_scores) = &self.initial_scores else {
+            return Ok(vec![1.0 / index.len() as f64; index.len()]);
+        };
+        if let Some(vertex) = initial_scores
+            .keys()
+            .copied()
+            .filter(|&vertex| index.index_of(vertex).is_none())
+            .min()
+        {
+            return Err(UnknownVertex(vertex));
+        }
+        let mut scores: Vec<(usize, f64)> = initial_scores.iter().map(|(&v, &s)| (v, s)).collect();
+        scores.sort_unstable_by_key(|&(vertex, _)| vertex);
+        let total: f64 = scores.iter().map(|&(_, score)| score).sum();
+        let mut page_ranks = vec![0.0; index.len()];
+        for (vertex, score) in scores {
+            page_ranks[index.index_of(vertex).unwrap()] = score / total;
+        }
+        Ok(page_ranks)
+    }
+    /// Performs one iteration of the PageRank algorithm.
+    ///
+    /// At each paper the random surfer follows one of its citations with the
+    /// probability given by `walk.damping`, and otherwise jumps to a paper chosen
+    /// according to `walk.teleport`. It always jumps from a paper without
+    /// references. The citations are followed with a single product of the transition
+    /// matrix and the damped scores.
+    ///
+    /// # Arguments
+    ///
+    /// * `walk` - Where the random surfer goes next
+    /// * `page_ranks` - The PageRank scores at the compact indices of the network
+    ///
+    /// # Returns
+    ///
+    /// * `converged` - Whether or not this iteration has converged
+    fn iterate(&self, walk: &Walk, page_ranks: &mut Vec<f64>) -> bool {
+        // The mass that jumps is taken entirely from the previous scores: all of it at
+        // sinks, and the undamped share elsewhere
+        let mut jump_mass = 0.0;
+        let followed: Vec<f64> = page_ranks
+            .iter()
+            .zip(&walk.damping)
+            .zip(&walk.sink)
+            .map(|((&page_rank, &damping), &sink)| {
+                if sink {
+                    jump_mass += page_rank;
+                    0.0
+                } else {
+                    jump_mass += (1.0 - damping) * page_rank;
+                    damping * page_rank
+                }
+            })
+            .collect();
+        let mut new_page_ranks = walk.transition.matvec(&followed);
+        let mut delta = 0.0; // used to check convergence
+        for ((new, &teleport), &old) in new_page_ranks
+            .iter_mut()
+            .zip(&walk.teleport)
+            .zip(page_ranks.iter())
+        {
+            *new += jump_mass * teleport;
+            delta += (*new - old).abs();
+        }
+        *page_ranks = new_page_ranks;
+        delta < self.tolerance
+    }
+}
+
+/// Divides PageRank scores by their sum, so rounding in the iterations does not
+/// leave them summing to slightly more or less than 1
+///
+/// # Returns
+///
+/// * `mass` - The sum of the scores before renormalization, or 0 if there are none
+fn renormalize(page_ranks: &mut [f64]) -> f64 {
+    let mass: f64 = page_ranks.iter().sum();
+    debug_assert!(
+        page_ranks.is_empty() || (mass - 1.0).abs() < 1e-6,
+        "PageRank lost mass: the scores sum to {}",
+        mass
+    );
+    if mass > 0.0 {
+        for page_rank in page_ranks.iter_mut() {
+            *page_rank /= mass;
+        }
+    }
+    mass
+}
+
+/// Converts PageRank scores to a rank sorted by score
+fn sorted_ranks(
+    page_ranks: impl IntoIterator<Item = (usize, f64)>,
+) -> CentralityRank<f64, PageRankCentrality> {
+    let mut ranks: Vec<_> = page_ranks
+        .into_iter()
+        .map(|(vertex, rank)| PageRankCentrality::new(vertex, rank))
+        .collect();
+    ranks.sort_by(|a, b| b.partial_cmp(a).unwrap().then(a.vertex.cmp(&b.vertex)));
+    CentralityRank::new(ranks)
+}
+
+/// Returns the PageRank centrality scores of a network
+///
+/// The PageRank measures the relative importance of a node in the network. It is
+/// computed with the power method on the transition matrix of the network, built
+/// once by [`CitationNetwork::build_transition_matrix`], and the final scores are
+/// divided by their sum so they add up to 1 despite rounding. Use [`PageRankConfig`] to change
+/// the parameters of the algorithm.
+///
+/// The rank of an empty network is empty, and a single paper scores 1.
 ///
 /// # Arguments
 ///
 /// * `network` - The network to analyze
-/// * `page_ranks` - The PageRank scores of the network
+pub fn calculate_pagerank_centrality(
+    network: &CitationNetwork,
+) -> CentralityRank<f64, PageRankCentrality> {
+    PageRankConfig::new().calculate_checked(network)
+}
+
+/// Estimates how the PageRank scores of a network change when a citation is added
+///
+/// The estimate is the first-order perturbation of the PageRank linear system: the
+/// change in the transition probabilities of `from`, applied once to the current
+/// scores and damped by 0.85. Adding the citation takes a share of the score of
+/// `from` from each paper it already cites and gives it to `to`, or, if `from` has
+/// no references, moves the score it spread over every paper to `to`. The effect
+/// of the change on later steps of the surfer is left out, so the error is of order
+/// the square of the damping factor, and the full network is never iterated.
+///
+/// # Arguments
+///
+/// * `network` - The network before the citation is added
+/// * `current_ranks` - The PageRank scores of the network, as from
+///   [`calculate_pagerank_centrality`]
+/// * `from` - The id of the citing paper
+/// * `to` - The id of the cited paper
 ///
 /// # Returns
 ///
-/// * `converged` - Whether or not this iteration has converged
-fn pagerank_iterate(network: &CitationNetwork, page_ranks: &mut HashMap<usize, f64>) -> bool {
-    let mut new_page_ranks: HashMap<usize, f64> = HashMap::new();
-    let num_nodes = network.size();
-    let mut delta = 0.0; // used to check convergence
-    let mut sink_node_contributions: f64 = 0.0; // Handle sink nodes
-    for &vertex in network.nodes() {
-        if network.out_edges_from(vertex).count() == 0 {
-            sink_node_contributions += page_ranks.get(&vertex).unwrap_or(&0.0);
+/// The change in score of each paper whose score changes; papers left out keep
+/// their score.
+///
+/// # Panics
+///
+/// Panics if `from` or `to` is not in the network, or if `from` has no score in
+/// `current_ranks`.
+pub fn delta_pagerank_after_add(
+    network: &CitationNetwork,
+    current_ranks: &HashMap<usize, f64>,
+    from: usize,
+    to: usize,
+) -> HashMap<usize, f64> {
+    for vertex in [from, to] {
+        assert!(network.contains_node(vertex), "{}", UnknownVertex(vertex));
+    }
+    let moved = DAMPING_FACTOR * current_ranks[&from];
+    let out_degree = network.out_degree(from);
+    let mut deltas: HashMap<usize, f64> = HashMap::new();
+    if out_degree == 0 {
+        let share = moved / network.size() as f64;
+        for &vertex in network.nodes() {
+            deltas.insert(vertex, -share);
+        }
+    } else {
+        let share = moved / (out_degree * (out_degree + 1)) as f64;
+        for &reference in network.out_edges_from(from) {
+            *deltas.entry(reference).or_insert(0.0) -= share;
         }
     }
-    sink_node_contributions /= num_nodes as f64;
-    // Update the PageRank scores
-    for &vertex in network.nodes() {
-        let mut sum = 0.0;
-        for &in_edge in network.in_edges_to(vertex) {
-            sum += page_ranks.get(&in_edge).unwrap_or(&0.0)
-                / network.out_edges_from(in_edge).count() as f64;
+    let gained = moved / (out_degree + 1) as f64;
+    *deltas.entry(to).or_insert(0.0) += gained;
+    deltas
+}
+
+/// The outcome of a run of the PageRank algorithm
+pub struct PageRankResult {
+    /// The PageRank centrality scores
+    pub ranks: CentralityRank<f64, PageRankCentrality>,
+    /// The number of iterations run
+    pub iterations: usize,
+    /// Whether the scores converged before the iteration limit
+    pub converged: bool,
+    /// The sum of the scores before they were renormalized to sum to 1, which
+    /// differs from 1 only by rounding
+    pub mass: f64,
+}
+
+/// Calculates PageRank while recording the scores of some vertices after every
+/// iteration
+///
+/// Only the tracked vertices are recorded, so the history stays small even for
+/// large networks.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `config` - The parameters of the algorithm
+/// * `track` - The vertices whose scores to record
+///
+/// # Returns
+///
+/// * `(result, history)` - The final scores, and the scores of the tracked vertices
+///   after each iteration, or the first tracked vertex or vertex of the initial
+///   scores that is not in the network
+pub fn calculate_pagerank_with_history(
+    network: &CitationNetwork,
+    config: &PageRankConfig,
+    track: &[usize],
+) -> Result<(PageRankResult, Vec<HashMap<usize, f64>>), UnknownVertex> {
+    if let Some(&vertex) = track.iter().find(|&&v| !network.contains_node(v)) {
+        return Err(UnknownVertex(vertex));
+    }
+    config.initial_page_ranks(&network.node_index())?;
+    let num_nodes = network.size() as f64;
+    let surfer = Surfer {
+        teleport: &|_| 1.0 / num_nodes,
+        damping: &|_| config.damping_factor,
+        share: None,
+    };
+    let mut history = Vec::new();
+    let (page_ranks, iterations, converged, mass) =
+        config.run(network, &surfer, &mut |index, page_ranks| {
+            let scores = track
+                .iter()
+                .map(|&v| (v, page_ranks[index.index_of(v).unwrap()]));
+            history.push(scores.collect());
+        });
+    let result = PageRankResult {
+        ranks: sorted_ranks(page_ranks),
+        iterations,
+        converged,
+        mass,
+    };
+    Ok((result, history))
+}
+
+/// Writes a PageRank history as CSV with one row per iteration
+///
+/// The columns are `iteration` followed by one column per tracked vertex, headed by
+/// its id. Iterations start at 1.
+///
+/// # Arguments
+///
+/// * `writer` - Where to write the CSV
+/// * `track` - The tracked vertices, in column order
+/// * `history` - The history from [`calculate_pagerank_with_history`]
+///
+/// # Returns
+///
+/// The error of the first failed write, or an error of kind `InvalidInput` if a
+/// tracked vertex has no score in the history.
+pub fn write_pagerank_history_csv<W: Write>(
+    mut writer: W,
+    track: &[usize],
+    history: &[HashMap<usize, f64>],
+) -> io::Result<()> {
+    write!(writer, "iteration")?;
+    for vertex in track {
+        write!(writer, ",{}", vertex)?;
+    }
+    writeln!(writer)?;
+    for (iteration, scores) in history.iter().enumerate() {
+        write!(writer, "{}", iteration + 1)?;
+        for vertex in track {
+            let score = scores.get(vertex).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("vertex {} is not in the history", vertex),
+                )
+            })?;
+            write!(writer, ",{}", score)?;
         }
-        sum += sink_node_contributions;
-        sum = (1.0 - DAMPING_FACTOR) / (num_nodes as f64) + DAMPING_FACTOR * sum;
-        delta += (sum - page_ranks.get(&vertex).unwrap_or(&0.0)).abs();
-        new_page_ranks.insert(vertex, sum);
+        writeln!(writer)?;
     }
-    *page_ranks = new_page_ranks;
-    delta < TOLERANCE
+    Ok(())
 }
 
-/// Returns the PageRank centrality scores of a network
+/// Returns the topic-sensitive PageRank centrality scores of a network
 ///
-/// The PageRank measures the relative importance of a node in the network. It is
-/// computed using an iterative algorithm.
+/// This is Haveliwala's topic-sensitive PageRank: the random surfer only jumps to
+/// papers in the given community, uniformly, so the scores measure relevance to
+/// that research field. Papers without a community never receive a jump.
 ///
 /// # Arguments
 ///
 /// * `network` - The network to analyze
-pub(crate) fn calculate_pagerank_centrality(
+/// * `communities` - The community of each paper
+/// * `topic` - The community to jump to
+///
+/// # Panics
+///
+/// Panics if no paper in the network belongs to `topic`.
+pub fn calculate_topic_sensitive_pagerank(
     network: &CitationNetwork,
+    communities: &HashMap<usize, usize>,
+    topic: usize,
 ) -> CentralityRank<f64, PageRankCentrality> {
-    let mut page_ranks: HashMap<usize, f64> = HashMap::new();
-    for &vertex in network.nodes() {
-        page_ranks.insert(vertex, 1.0 / (network.size() as f64));
+    let in_topic = |vertex: usize| communities.get(&vertex) == Some(&topic);
+    let topic_size = network.nodes().filter(|&&vertex| in_topic(vertex)).count();
+    assert!(
+        topic_size > 0,
+        "topic {} has no papers in the network",
+        topic
+    );
+    let teleport = |vertex: usize| {
+        if in_topic(vertex) {
+            1.0 / topic_size as f64
+        } else {
+            0.0
+        }
+    };
+    let surfer = Surfer {
+        teleport: &teleport,
+        damping: &|_| DAMPING_FACTOR,
+        share: None,
+    };
+    sorted_ranks(
+        PageRankConfig::new()
+            .run(network, &surfer, &mut |_, _| {})
+            .0,
+    )
+}
+
+/// Returns the PageRank centrality scores of a network with a damping factor per
+/// paper
+///
+/// The damping factor of a paper is the probability that a random surfer reading it
+/// follows one of its citations rather than jumping to a random paper, so a paper
+/// with a low damping factor passes on little of its own score to the papers it
+/// cites. A paper without references passes on nothing and the surfer always jumps.
+/// With the same damping factor for every paper this is the standard PageRank.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `damping` - The damping factor of each paper; papers without an entry use 0.85
+///
+/// # Panics
+///
+/// Panics if a damping factor is not in `(0, 1)`.
+pub fn calculate_personalized_damped_pagerank(
+    network: &CitationNetwork,
+    damping: &HashMap<usize, f64>,
+) -> PageRankResult {
+    for (vertex, damping_factor) in damping {
+        assert!(
+            *damping_factor > 0.0 && *damping_factor < 1.0,
+            "damping factor of vertex {} must be in (0, 1), got {}",
+            vertex,
+            damping_factor
+        );
     }
-    let mut converged = false;
-    let mut num_iterations = 0;
-    while !converged && num_iterations < MAX_ITERATIONS {
-        converged = pagerank_iterate(network, &mut page_ranks);
-        num_iterations += 1;
+    let num_nodes = network.size() as f64;
+    let surfer = Surfer {
+        teleport: &|_| 1.0 / num_nodes,
+        damping: &|vertex| *damping.get(&vertex).unwrap_or(&DAMPING_FACTOR),
+        share: None,
+    };
+    let (page_ranks, iterations, converged, mass) =
+        PageRankConfig::new().run(network, &surfer, &mut |_, _| {});
+    PageRankResult {
+        ranks: sorted_ranks(page_ranks),
+        iterations,
+        converged,
+        mass,
+    }
+}
+
+/// Returns the PageRank centrality scores of a network in which old citations
+/// count less
+///
+/// A citation made in year `y` has weight `exp(-lambda * (reference_year - y))`
+/// with `lambda = ln(2) / half_life_years`, so its weight halves every
+/// `half_life_years`; citations made after `reference_year` have weight 1. The
+/// surfer follows the citations of a paper with probability proportional to their
+/// weights, so recent citations pass on more of the citing paper's score than old
+/// ones. Repeated citations add their weights. Timestamps are read as
+/// `year * 100 + month`, as described for [`TimestampedCitationNetwork`].
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `reference_year` - The year from which the age of citations is measured
+/// * `half_life_years` - The number of years after which a citation counts half
+///
+/// # Panics
+///
+/// Panics if `half_life_years` is not positive.
+pub fn calculate_time_decayed_pagerank(
+    network: &TimestampedCitationNetwork,
+    reference_year: u32,
+    half_life_years: f64,
+) -> CentralityRank<f64, PageRankCentrality> {
+    assert!(
+        half_life_years > 0.0,
+        "half-life must be positive, got {}",
+        half_life_years
+    );
+    let lambda = LN_2 / half_life_years;
+    let mut graph = CitationNetwork::new();
+    let mut weights: HashMap<(usize, usize), f64> = HashMap::new();
+    let mut totals: HashMap<usize, f64> = HashMap::new();
+    for (from, to, timestamp) in network.edges() {
+        let age = reference_year.saturating_sub(timestamp / 100);
+        let weight = (-lambda * age as f64).exp();
+        if !weights.contains_key(&(from, to)) {
+            graph.add_edge(from, to);
+        }
+        *weights.entry((from, to)).or_insert(0.0) += weight;
+        *totals.entry(from).or_insert(0.0) += weight;
+    }
+    let num_nodes = graph.size() as f64;
+    let share = |from: usize, to: usize| {
+        // Citations so old that every weight underflows are followed uniformly
+        if totals[&from] > 0.0 {
+            weights[&(from, to)] / totals[&from]
+        } else {
+            1.0 / graph.out_degree(from) as f64
+        }
+    };
+    let surfer = Surfer {
+        teleport: &|_| 1.0 / num_nodes,
+        damping: &|_| DAMPING_FACTOR,
+        share: Some(&share),
+    };
+    sorted_ranks(PageRankConfig::new().run(&graph, &surfer, &mut |_, _| {}).0)
+}
+
+/// The PageRank centrality as a [`CentralityMeasure`]
+pub struct PageRankMeasure;
+
+impl CentralityMeasure for PageRankMeasure {
+    fn name(&self) -> &str {
+        "pagerank"
+    }
+
+    fn compute(&self, network: &CitationNetwork) -> Vec<(usize, f64)> {
+        calculate_pagerank_centrality(network)
+            .iter()
+            .map(|centrality| (centrality.vertex(), centrality.score()))
+            .collect()
     }
-    // Convert the HashMap to a sorted vector
-    let mut ranks: Vec<_> = page_ranks
-        .into_iter()
-        .map(|(vertex, rank)| PageRankCentrality::new(vertex, rank))
-        .collect();
-    ranks.sort_by(|a, b| b.partial_cmp(a).unwrap());
-    CentralityRank::new(ranks)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::example_graphs::complete_dag;
 
     #[test]
     fn test_calculate_pagerank_centrality() {
-        let mut network = CitationNetwork::new();
-        network.add_edge(0, 1);
-        network.add_edge(0, 2);
-        network.add_edge(1, 2);
-        let pagerank_ranks = calculate_pagerank_centrality(&network);
+        let pagerank_ranks = calculate_pagerank_centrality(&complete_dag(3));
         println!("{}", pagerank_ranks);
-        assert_eq!(pagerank_ranks[0].vertex(), 2);
+        assert_eq!(pagerank_ranks[0].vertex(), 0);
         assert!((pagerank_ranks[0].score() - 0.521).abs() < 0.001);
         assert_eq!(pagerank_ranks[1].vertex(), 1);
         assert!((pagerank_ranks[1].score() - 0.281).abs() < 0.001);
-        assert_eq!(pagerank_ranks[2].vertex(), 0);
+        assert_eq!(pagerank_ranks[2].vertex(), 2);
         assert!((pagerank_ranks[2].score() - 0.198).abs() < 0.001);
     }
+
+    /// Returns the PageRank scores of a network, converged far below the size of
+    /// the changes being tested
+    fn exact_pagerank(network: &CitationNetwork) -> HashMap<usize, f64> {
+        PageRankConfig::new()
+            .tolerance(1e-14)
+            .max_iterations(1_000)
+            .calculate(network)
+            .unwrap()
+            .iter()
+            .map(|c| (c.vertex(), c.score()))
+            .collect()
+    }
+
+    /// Moves a change in scores one step of the surfer along `network`: each paper
+    /// passes the damped part of its change on to its references and the rest, or
+    /// all of it at a paper without references, evenly to every paper
+    fn propagate(network: &CitationNetwork, change: &HashMap<usize, f64>) -> HashMap<usize, f64> {
+        let alpha = DAMPING_FACTOR;
+        let mut propagated: HashMap<usize, f64> = network.nodes().map(|&v| (v, 0.0)).collect();
+        let mut jump = 0.0;
+        for (&vertex, &amount) in change {
+            let out_degree = network.out_degree(vertex);
+            if out_degree == 0 {
+                jump += amount;
+                continue;
+            }
+            jump += (1.0 - alpha) * amount;
+            for reference in network.out_edges_from(vertex) {
+                *propagated.get_mut(reference).unwrap() += alpha * amount / out_degree as f64;
+            }
+        }
+        let share = jump / network.size() as f64;
+        propagated.values_mut().for_each(|value| *value += share);
+        propagated
+    }
+
+    /// Checks an estimated change against the exact change in PageRank
+    fn check_delta(network: &CitationNetwork, from: usize, to: usize) {
+        let before = exact_pagerank(network);
+        let deltas = delta_pagerank_after_add(network, &before, from, to);
+        let mut changed = network.clone();
+        changed.add_edge(from, to);
+        let after = exact_pagerank(&changed);
+        let exact: HashMap<usize, f64> = network
+            .nodes()
+            .map(|&v| (v, after[&v] - before[&v]))
+            .collect();
+        // Subtracting the PageRank equations before and after the change gives
+        // exact = estimate + the exact change moved one step along the new network,
+        // so the estimate is checked to the precision of the scores themselves
+        let propagated = propagate(&changed, &exact);
+        let residual: f64 = network
+            .nodes()
+            .map(|v| {
+                let estimate = deltas.get(v).copied().unwrap_or(0.0);
+                (exact[v] - estimate - propagated[v]).abs()
+            })
+            .sum();
+        assert!(deltas.values().sum::<f64>().abs() < 1e-12);
+        assert!(deltas[&to] > 0.0 && after[&to] > before[&to]);
+        assert!(residual < 1e-10, "residual {residual}");
+    }
+
+    #[test]
+    fn test_delta_pagerank_after_add() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        network.add_edge(0, 2);
+        network.add_edge(1, 2);
+        network.add_edge(2, 3);
+        network.add_edge(3, 0);
+        network.add_edge(4, 3);
+        let ranks = exact_pagerank(&network);
+        let deltas = delta_pagerank_after_add(&network, &ranks, 0, 4);
+        // Paper 0 now cites three papers, so 1 and 2 each give up a sixth of its share
+        let moved = DAMPING_FACTOR * ranks[&0];
+        assert_eq!(deltas.len(), 3);
+        assert!((deltas[&1] + moved / 6.0).abs() < 1e-15);
+        assert!((deltas[&2] + moved / 6.0).abs() < 1e-15);
+        assert!((deltas[&4] - moved / 3.0).abs() < 1e-15);
+        check_delta(&network, 0, 4);
+        check_delta(&crate::generators::erdos_renyi(100, 0.05, 2), 7, 3);
+    }
+
+    #[test]
+    fn test_delta_pagerank_from_sink() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        network.add_edge(2, 1);
+        network.add_edge(3, 2);
+        let ranks = exact_pagerank(&network);
+        let deltas = delta_pagerank_after_add(&network, &ranks, 1, 3);
+        assert_eq!(deltas.len(), 4);
+        assert!(deltas[&0] < 0.0);
+        check_delta(&network, 1, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "vertex 9 is not in the network")]
+    fn test_delta_pagerank_rejects_unknown_vertex() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        let ranks = exact_pagerank(&network);
+        delta_pagerank_after_add(&network, &ranks, 0, 9);
+    }
+
+    #[test]
+    fn test_pagerank_config() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        network.add_edge(0, 2);
+        network.add_edge(1, 2);
+        let default = calculate_pagerank_centrality(&network);
+        let configured = PageRankConfig::new().calculate(&network).unwrap();
+        for (a, b) in default.iter().zip(configured.iter()) {
+            assert_eq!(a.vertex(), b.vertex());
+            assert!(a == b);
+        }
+        // Without any iterations every node keeps its initial uniform score
+        let initial = PageRankConfig::new()
+            .max_iterations(0)
+            .calculate(&network)
+            .unwrap();
+        assert!(initial
+            .iter()
+            .all(|c| (c.score() - 1.0 / 3.0).abs() < 1e-12));
+        // Without damping the walk only jumps, so the scores stay uniform
+        let undamped = PageRankConfig::new()
+            .damping_factor(0.0)
+            .calculate(&network)
+            .unwrap();
+        assert!(undamped
+            .iter()
+            .all(|c| (c.score() - 1.0 / 3.0).abs() < 1e-12));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pagerank_config_rejects_invalid_damping_factor() {
+        PageRankConfig::new().damping_factor(1.5);
+    }
+
+    #[test]
+    fn test_topic_sensitive_pagerank() {
+        // Two chains, 0 -> 1 -> 2 and 3 -> 4 -> 5, in two communities
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        network.add_edge(1, 2);
+        network.add_edge(3, 4);
+        network.add_edge(4, 5);
+        let communities: HashMap<usize, usize> =
+            [(0, 0), (1, 0), (2, 0), (3, 1), (4, 1), (5, 1)].into();
+        let ranks = calculate_topic_sensitive_pagerank(&network, &communities, 0);
+        let scores: HashMap<usize, f64> = ranks.iter().map(|c| (c.vertex(), c.score())).collect();
+        assert_eq!(ranks[0].vertex(), 2);
+        for vertex in 3..6 {
+            assert_eq!(scores[&vertex], 0.0);
+        }
+        assert!((scores.values().sum::<f64>() - 1.0).abs() < 1e-9);
+        // With every paper in the topic it is the ordinary PageRank
+        let everything: HashMap<usize, usize> = (0..6).map(|vertex| (vertex, 0)).collect();
+        let uniform = calculate_topic_sensitive_pagerank(&network, &everything, 0);
+        for (a, b) in uniform
+            .iter()
+            .zip(calculate_pagerank_centrality(&network).iter())
+        {
+            assert_eq!(a.vertex(), b.vertex());
+            assert!((a.score() - b.score()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_topic_sensitive_pagerank_rejects_empty_topic() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        let communities: HashMap<usize, usize> = [(0, 0), (1, 0)].into();
+        calculate_topic_sensitive_pagerank(&network, &communities, 1);
+    }
+
+    #[test]
+    fn test_pagerank_with_history() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        network.add_edge(0, 2);
+        network.add_edge(1, 2);
+        network.add_edge(2, 0);
+        let config = PageRankConfig::new().max_iterations(10).tolerance(0.0);
+        let (result, history) =
+            calculate_pagerank_with_history(&network, &config, &[2, 0]).unwrap();
+        assert_eq!(result.iterations, 10);
+        assert!(!result.converged);
+        assert_eq!(history.len(), 10);
+        let last = &history[9];
+        assert_eq!(last.len(), 2);
+        // The final scores are renormalized, which moves them only by rounding
+        for centrality in result.ranks.iter().filter(|c| c.vertex() != 1) {
+            assert!((last[&centrality.vertex()] - centrality.score()).abs() < 1e-12);
+        }
+        let mut output = Vec::new();
+        write_pagerank_history_csv(&mut output, &[2, 0], &history).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), 11);
+        assert_eq!(output.lines().next(), Some("iteration,2,0"));
+        assert!(output.lines().nth(10).unwrap().starts_with("10,"));
+        let error = write_pagerank_history_csv(Vec::new(), &[2, 1], &history).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(error.to_string(), "vertex 1 is not in the history");
+    }
+
+    #[test]
+    fn test_pagerank_with_history_of_unknown_vertex() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        let result = calculate_pagerank_with_history(&network, &PageRankConfig::new(), &[1, 7]);
+        assert_eq!(result.err(), Some(UnknownVertex(7)));
+    }
+
+    #[test]
+    fn test_pagerank_from_initial_scores() {
+        let network = crate::generators::erdos_renyi(200, 0.03, 5);
+        let config = PageRankConfig::new();
+        let (converged, _) = calculate_pagerank_with_history(&network, &config, &[]).unwrap();
+        assert!(converged.converged);
+        let fixed_point: HashMap<usize, f64> = converged
+            .ranks
+            .iter()
+            .map(|c| (c.vertex(), c.score()))
+            .collect();
+        let config = PageRankConfig::new().initial_scores(fixed_point.clone());
+        let (restarted, _) = calculate_pagerank_with_history(&network, &config, &[]).unwrap();
+        assert_eq!(restarted.iterations, 1);
+        let start = network.nodes_sorted()[0];
+        let config = PageRankConfig::new().initial_scores([(start, 5.0)].into());
+        let (result, history) =
+            calculate_pagerank_with_history(&network, &config, &[start]).unwrap();
+        assert!(result.converged);
+        assert!(result.iterations > restarted.iterations);
+        assert!(history[0][&start] < 1.0);
+        for centrality in result.ranks.iter() {
+            assert!((centrality.score() - fixed_point[&centrality.vertex()]).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_pagerank_initial_scores_of_unknown_vertex() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        let config = PageRankConfig::new().initial_scores([(1, 1.0), (9, 1.0), (7, 1.0)].into());
+        let result = calculate_pagerank_with_history(&network, &config, &[]);
+        assert_eq!(result.err(), Some(UnknownVertex(7)));
+        assert_eq!(config.calculate(&network).err(), Some(UnknownVertex(7)));
+        let checkpointer = crate::checkpoint::MemoryCheckpointer::new();
+        let error = config
+            .calculate_checkpointed(&network, &checkpointer, 1)
+            .err()
+            .unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(error.to_string(), UnknownVertex(7).to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "initial scores must have a positive sum")]
+    fn test_pagerank_rejects_zero_initial_scores() {
+        PageRankConfig::new().initial_scores([(1, 0.0)].into());
+    }
+
+    #[test]
+    fn test_personalized_damped_pagerank() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        network.add_edge(0, 2);
+        network.add_edge(1, 2);
+        network.add_edge(3, 0);
+        let standard = calculate_pagerank_centrality(&network);
+        let uniform: HashMap<usize, f64> = (0..4).map(|vertex| (vertex, 0.85)).collect();
+        for damping in [HashMap::new(), uniform] {
+            let result = calculate_personalized_damped_pagerank(&network, &damping);
+            assert!(result.converged);
+            for (a, b) in result.ranks.iter().zip(standard.iter()) {
+                assert_eq!(a.vertex(), b.vertex());
+                assert!((a.score() - b.score()).abs() < 1e-9);
+            }
+        }
+        // 1 passes on little of its score, so 2 receives less
+        let damping: HashMap<usize, f64> = [(1, 0.1)].into();
+        let result = calculate_personalized_damped_pagerank(&network, &damping);
+        let scores: HashMap<usize, f64> = result
+            .ranks
+            .iter()
+            .map(|c| (c.vertex(), c.score()))
+            .collect();
+        let standard: HashMap<usize, f64> =
+            standard.iter().map(|c| (c.vertex(), c.score())).collect();
+        assert!(scores[&2] < standard[&2]);
+        assert!((scores.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_personalized_damped_pagerank_rejects_invalid_damping() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        calculate_personalized_damped_pagerank(&network, &[(0, 1.0)].into());
+    }
+
+    fn build_timestamped_network() -> TimestampedCitationNetwork {
+        // 10, 11 and 12 cite 1 in 1990 and 2 in 2000, and 13 cites only 1
+        let mut network = TimestampedCitationNetwork::new();
+        for citer in 10..13 {
+            network.add_timestamped_edge(citer, 1, 199001);
+            network.add_timestamped_edge(citer, 2, 200001);
+        }
+        network.add_timestamped_edge(13, 1, 199006);
+        network
+    }
+
+    #[test]
+    fn test_time_decayed_pagerank() {
+        let network = build_timestamped_network();
+        let standard = calculate_pagerank_centrality(&network.to_network());
+        assert_eq!(standard[0].vertex(), 1);
+        let decayed = calculate_time_decayed_pagerank(&network, 2000, 2.0);
+        assert_eq!(decayed[0].vertex(), 2);
+        assert!((decayed.iter().map(|c| c.score()).sum::<f64>() - 1.0).abs() < 1e-9);
+        // Without noticeable decay every citation counts the same
+        let undecayed = calculate_time_decayed_pagerank(&network, 2000, 1e12);
+        for (a, b) in undecayed.iter().zip(standard.iter()) {
+            assert_eq!(a.vertex(), b.vertex());
+            assert!((a.score() - b.score()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_time_decayed_pagerank_rejects_zero_half_life() {
+        calculate_time_decayed_pagerank(&build_timestamped_network(), 2000, 0.0);
+    }
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        let network = crate::generators::erdos_renyi(80, 0.05, 7);
+        let config = PageRankConfig::new().tolerance(0.0).max_iterations(40);
+        let expected = config.calculate(&network).unwrap();
+        // The second save fails, stopping the run after 10 of the 40 iterations
+        let interrupted = crate::checkpoint::MemoryCheckpointer::new().fail_after(1);
+        assert!(config
+            .calculate_checkpointed(&network, &interrupted, 10)
+            .is_err());
+        let state = interrupted.load().unwrap();
+        let vertices = network.node_index().vertices().to_vec();
+        assert_eq!(decode_state(&state, &vertices).unwrap().0, 10);
+        let resumed = crate::checkpoint::MemoryCheckpointer::new();
+        resumed.save(&state).unwrap();
+        let ranks = config
+            .calculate_checkpointed(&network, &resumed, 10)
+            .unwrap();
+        assert_eq!(
+            decode_state(&resumed.load().unwrap(), &vertices).unwrap().0,
+            30
+        );
+        for (a, b) in expected.iter().zip(ranks.iter()) {
+            assert_eq!(a.vertex(), b.vertex());
+            assert!(a == b);
+        }
+    }
+
+    /// Computes PageRank densely, as a reference for the sparse implementation
+    fn reference_pagerank(network: &CitationNetwork, iterations: usize) -> HashMap<usize, f64> {
+        let nodes = network.nodes_sorted();
+        let n = nodes.len();
+        let position: HashMap<usize, usize> =
+            nodes.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+        let mut scores = vec![1.0 / n as f64; n];
+        for _ in 0..iterations {
+            let mut next = vec![0.0; n];
+            let mut jump = 0.0;
+            for (i, &vertex) in nodes.iter().enumerate() {
+                let out_degree = network.out_degree(vertex);
+                if out_degree == 0 {
+                    jump += scores[i];
+                    continue;
+                }
+                jump += (1.0 - DAMPING_FACTOR) * scores[i];
+                for reference in network.out_edges_from(vertex) {
+                    next[position[reference]] += DAMPING_FACTOR * scores[i] / out_degree as f64;
+                }
+            }
+            for score in &mut next {
+                *score += jump / n as f64;
+            }
+            scores = next;
+        }
+        nodes.into_iter().zip(scores).collect()
+    }
+
+    /// Computes PageRank with one map lookup per citation, as the implementation
+    /// before the transition matrix did
+    fn hashmap_pagerank(network: &CitationNetwork) -> HashMap<usize, f64> {
+        let n = network.size() as f64;
+        let mut page_ranks: HashMap<usize, f64> =
+            network.nodes().map(|&vertex| (vertex, 1.0 / n)).collect();
+        for _ in 0..MAX_ITERATIONS {
+            let mut jump = 0.0;
+            for &vertex in network.nodes() {
+                let damping = if network.out_degree(vertex) == 0 {
+                    1.0
+                } else {
+                    1.0 - DAMPING_FACTOR
+                };
+                jump += damping * page_ranks[&vertex];
+            }
+            let mut delta = 0.0;
+            let mut next = HashMap::new();
+            for &vertex in network.nodes() {
+                let mut sum = jump / n;
+                for citer in network.in_edges_to(vertex) {
+                    sum += DAMPING_FACTOR * page_ranks[citer] / network.out_degree(*citer) as f64;
+                }
+                delta += (sum - page_ranks[&vertex]).abs();
+                next.insert(vertex, sum);
+            }
+            page_ranks = next;
+            if delta < TOLERANCE {
+                break;
+            }
+        }
+        page_ranks
+    }
+
+    #[test]
+    fn test_pagerank_matches_hashmap_implementation_on_hepth() {
+        let file = std::fs::File::open("data/cit-HepTh.txt").unwrap();
+        let network = CitationNetwork::load_from_file(file);
+        let expected = hashmap_pagerank(&network);
+        let ranks = calculate_pagerank_centrality(&network);
+        assert_eq!(ranks.len(), network.size());
+        for centrality in ranks.iter() {
+            assert!((centrality.score() - expected[&centrality.vertex()]).abs() < TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn test_pagerank_mass_with_mostly_sinks() {
+        // Only the first 30 of 300 papers cite anything
+        let mut network = CitationNetwork::new();
+        let mut random = crate::random::Random::new(5);
+        for vertex in 0..300 {
+            network.add_node(vertex);
+        }
+        for from in 0..30 {
+            for _ in 0..8 {
+                network.add_edge(from, random.below(300));
+            }
+        }
+        let sinks = (0..300).filter(|&v| network.out_degree(v) == 0).count();
+        assert!(sinks >= 270);
+        let ranks = calculate_pagerank_centrality(&network);
+        let total: f64 = ranks.iter().map(|c| c.score()).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+        let reference = reference_pagerank(&network, 200);
+        for centrality in ranks.iter() {
+            assert!((centrality.score() - reference[&centrality.vertex()]).abs() < 1e-9);
+        }
+        for pair in ranks.iter().collect::<Vec<_>>().windows(2) {
+            assert!(reference[&pair[0].vertex()] >= reference[&pair[1].vertex()] - 1e-9);
+        }
+        let config = PageRankConfig::new();
+        let (result, _) = calculate_pagerank_with_history(&network, &config, &[]).unwrap();
+        assert!((result.mass - 1.0).abs() < 1e-9);
+    }
 }
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::centrality::centrality::{Centrality, CentralityRank};
+
+/// A single centrality measure in a combined report
+struct Column {
+    /// The name of the measure
+    name: String,
+    /// The formatted score of every vertex in the ranking
+    scores: HashMap<usize, String>,
+}
+
+/// A table comparing several centrality rankings side by side
+///
+/// The rows are the union of the top vertices of every ranking, in the order they
+/// first appear when the rankings are visited in the order they were added. Each
+/// cell holds the vertex's score in that ranking, or a dash if the vertex is not
+/// ranked by it.
+pub struct CombinedReport {
+    /// The number of top vertices taken from each ranking
+    n: usize,
+    /// The vertices shown in the report
+    rows: Vec<usize>,
+    columns: Vec<Column>,
+}
+
+impl CombinedReport {
+    /// Creates an empty report
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of top vertices taken from each ranking
+    pub fn new(n: usize) -> CombinedReport {
+        CombinedReport {
+            n,
+            rows: Vec::new(),
+            columns: Vec::new(),
+        }
+    }
+    /// Adds a ranking to the report
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The column header of the ranking
+    /// * `rank` - The centrality scores to add
+    pub fn add<T: Display, U: Centrality<T>>(
+        mut self,
+        name: &str,
+        rank: &CentralityRank<T, U>,
+    ) -> CombinedReport {
+        for centrality in rank.iter().take(self.n) {
+            if !self.rows.contains(&centrality.vertex()) {
+                self.rows.push(centrality.vertex());
+            }
+        }
+        self.columns.push(Column {
+            name: name.to_string(),
+            scores: rank
+                .iter()
+                .map(|centrality| (centrality.vertex(), centrality.score().to_string()))
+                .collect(),
+        });
+        self
+    }
+}
+
+impl Display for CombinedReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut table: Vec<Vec<&str>> = Vec::with_capacity(self.rows.len() + 1);
+        let vertices: Vec<String> = self.rows.iter().map(|v| v.to_string()).collect();
+        let mut header = vec!["vertex"];
+        header.extend(self.columns.iter().map(|column| column.name.as_str()));
+        table.push(header);
+        for (row, vertex) in self.rows.iter().zip(&vertices) {
+            let mut cells = vec![vertex.as_str()];
+            cells.extend(
+                self.columns
+                    .iter()
+                    .map(|column| column.scores.get(row).map_or("-", |score| score.as_str())),
+            );
+            table.push(cells);
+        }
+        let widths: Vec<usize> = (0..=self.columns.len())
+            .map(|i| table.iter().map(|cells| cells[i].len()).max().unwrap())
+            .collect();
+        for cells in table {
+            let line: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+                .collect();
+            writeln!(f, "{}", line.join("  ").trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::centrality::degree_centrality::DegreeCentrality;
+    use crate::centrality::pagerank_centrality::PageRankCentrality;
+
+    #[test]
+    fn test_combined_report() {
+        let degree_ranks = CentralityRank::new(vec![
+            DegreeCentrality::new(10, 120),
+            DegreeCentrality::new(2, 7),
+            DegreeCentrality::new(3, 1),
+        ]);
+        let pagerank_ranks = CentralityRank::new(vec![
+            PageRankCentrality::new(4, 0.5),
+            PageRankCentrality::new(10, 0.25),
+            PageRankCentrality::new(2, 0.125),
+        ]);
+        let report = CombinedReport::new(2)
+            .add("in-degree", &degree_ranks)
+            .add("PageRank", &pagerank_ranks);
+        assert_eq!(
+            report.to_string(),
+            "vertex  in-degree  PageRank\n\
+             10      120        0.25\n\
+             2       7          0.125\n\
+             4       -          0.5\n"
+        );
+    }
+
+    #[test]
+    fn test_empty_report() {
+        assert_eq!(CombinedReport::new(5).to_string(), "vertex\n");
+    }
+}
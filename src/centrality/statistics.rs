@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::centrality::centrality::{Centrality, CentralityRank};
+
+/// A summary of the distribution of the scores in a rank
+///
+/// The moments are those of the population, so the variance divides by the number
+/// of scores rather than one less.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScoreStatistics {
+    pub mean: f64,
+    pub variance: f64,
+    pub std_dev: f64,
+    /// The third standardized moment, which is positive for a long tail of high scores
+    pub skewness: f64,
+    /// The excess kurtosis, which is 0 for a normal distribution
+    pub kurtosis: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Display for ScoreStatistics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "mean: {}", self.mean)?;
+        writeln!(f, "standard deviation: {}", self.std_dev)?;
+        writeln!(f, "skewness: {}", self.skewness)?;
+        writeln!(f, "kurtosis: {}", self.kurtosis)?;
+        writeln!(f, "range: [{}, {}]", self.min, self.max)
+    }
+}
+
+/// The error returned when the scores of an empty rank are summarized
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptyRank;
+
+impl Display for EmptyRank {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cannot summarize an empty rank")
+    }
+}
+
+impl Error for EmptyRank {}
+
+impl<T: Into<f64>, U: Centrality<T>> CentralityRank<T, U> {
+    /// Returns a summary of the distribution of the scores
+    ///
+    /// Works for any rank whose scores convert to floats, such as in-degrees and
+    /// PageRank scores. If every score is equal, the skewness and kurtosis are 0.
+    ///
+    /// A single score has variance, skewness and kurtosis 0.
+    ///
+    /// # Returns
+    ///
+    /// The summary, or [`EmptyRank`] if the rank has no scores, such as the rank of
+    /// an empty network.
+    pub fn statistics(&self) -> Result<ScoreStatistics, EmptyRank> {
+        if self.is_empty() {
+            return Err(EmptyRank);
+        }
+        let scores: Vec<f64> = self.iter().map(|c| c.score().into()).collect();
+        let n = scores.len() as f64;
+        let mean = scores.iter().sum::<f64>() / n;
+        let moment = |k: i32| scores.iter().map(|s| (s - mean).powi(k)).sum::<f64>() / n;
+        let variance = moment(2);
+        let (skewness, kurtosis) = if variance > 0.0 {
+            (
+                moment(3) / variance.powf(1.5),
+                moment(4) / variance.powi(2) - 3.0,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+        Ok(ScoreStatistics {
+            mean,
+            variance,
+            std_dev: variance.sqrt(),
+            skewness,
+            kurtosis,
+            min: scores.iter().copied().fold(f64::INFINITY, f64::min),
+            max: scores.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+    /// Returns the Spearman rank correlation between the scores of this rank and
+    /// another
+    ///
+    /// Only the vertices in both ranks are compared. The scores of each rank are
+    /// replaced by their positions among those vertices, with tied scores sharing
+    /// the average of their positions, and the result is the Pearson correlation of
+    /// the positions. It is 1 when both ranks order the vertices the same way and -1
+    /// when they order them in reverse. It is 0 if fewer than two vertices are
+    /// shared or either rank gives them all the same score, where the correlation is
+    /// undefined.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The rank to compare with, which may use a different measure
+    pub fn spearman_correlation<S: Into<f64>, V: Centrality<S>>(
+        &self,
+        other: &CentralityRank<S, V>,
+    ) -> f64 {
+        let other_scores: HashMap<usize, f64> = other
+            .iter()
+            .map(|c| (c.vertex(), c.score().into()))
+            .collect();
+        let mut pairs: Vec<(usize, f64, f64)> = self
+            .iter()
+            .filter_map(|c| {
+                let other_score = other_scores.get(&c.vertex())?;
+                Some((c.vertex(), c.score().into(), *other_score))
+            })
+            .collect();
+        pairs.sort_by_key(|&(vertex, _, _)| vertex);
+        let x = average_positions(pairs.iter().map(|p| p.1).collect());
+        let y = average_positions(pairs.iter().map(|p| p.2).collect());
+        let n = pairs.len() as f64;
+        let mean = (n - 1.0) / 2.0;
+        let mut covariance = 0.0;
+        let mut variance_x = 0.0;
+        let mut variance_y = 0.0;
+        for (a, b) in x.iter().zip(&y) {
+            covariance += (a - mean) * (b - mean);
+            variance_x += (a - mean).powi(2);
+            variance_y += (b - mean).powi(2);
+        }
+        if variance_x > 0.0 && variance_y > 0.0 {
+            covariance / (variance_x * variance_y).sqrt()
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Returns the position of every score in ascending order, starting at 0, with tied
+/// scores sharing the average of their positions
+fn average_positions(scores: Vec<f64>) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&a, &b| scores[a].partial_cmp(&scores[b]).unwrap());
+    let mut positions = vec![0.0; scores.len()];
+    let mut start = 0;
+    for group in order.chunk_by(|&a, &b| scores[a] == scores[b]) {
+        let position = start as f64 + (group.len() - 1) as f64 / 2.0;
+        for &index in group {
+            positions[index] = position;
+        }
+        start += group.len();
+    }
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::centrality::degree_centrality::DegreeCentrality;
+    use crate::centrality::normalize::NormalizedScore;
+
+    #[test]
+    fn test_statistics() {
+        let scores = [9, 7, 5, 5, 4, 4, 4, 2];
+        let rank = CentralityRank::new(
+            scores
+                .iter()
+                .enumerate()
+                .map(|(vertex, &score)| DegreeCentrality::new(vertex, score))
+                .collect(),
+        );
+        let statistics = rank.statistics().unwrap();
+        assert_eq!(statistics.mean, 5.0);
+        assert_eq!(statistics.variance, 4.0);
+        assert_eq!(statistics.std_dev, 2.0);
+        assert!((statistics.skewness - 0.65625).abs() < 1e-12);
+        assert!((statistics.kurtosis + 0.21875).abs() < 1e-12);
+        assert_eq!(statistics.min, 2.0);
+        assert_eq!(statistics.max, 9.0);
+    }
+
+    #[test]
+    fn test_statistics_of_equal_scores() {
+        let rank = CentralityRank::new(vec![
+            NormalizedScore::new(0, 0.5),
+            NormalizedScore::new(1, 0.5),
+        ]);
+        let statistics = rank.statistics().unwrap();
+        assert_eq!(statistics.mean, 0.5);
+        assert_eq!(statistics.variance, 0.0);
+        assert_eq!(statistics.skewness, 0.0);
+        assert_eq!(statistics.kurtosis, 0.0);
+        assert_eq!(statistics.min, statistics.max);
+    }
+
+    #[test]
+    fn test_spearman_correlation() {
+        let degrees = CentralityRank::new(vec![
+            DegreeCentrality::new(3, 9),
+            DegreeCentrality::new(1, 4),
+            DegreeCentrality::new(2, 4),
+            DegreeCentrality::new(0, 1),
+        ]);
+        let same_order = CentralityRank::new(vec![
+            NormalizedScore::new(3, 0.9),
+            NormalizedScore::new(2, 0.5),
+            NormalizedScore::new(1, 0.4),
+            NormalizedScore::new(0, 0.1),
+        ]);
+        // The tie between 1 and 2 keeps the correlation below 1
+        let correlation = degrees.spearman_correlation(&same_order);
+        assert!((correlation - 0.9f64.sqrt()).abs() < 1e-12);
+        assert!((same_order.spearman_correlation(&same_order) - 1.0).abs() < 1e-12);
+        let reversed = CentralityRank::new(vec![
+            NormalizedScore::new(0, 0.9),
+            NormalizedScore::new(1, 0.5),
+            NormalizedScore::new(2, 0.4),
+            NormalizedScore::new(3, 0.1),
+            NormalizedScore::new(8, 0.0),
+        ]);
+        // Vertex 8 is only in one rank and is ignored
+        assert!((same_order.spearman_correlation(&reversed) + 1.0).abs() < 1e-12);
+        let constant = CentralityRank::new(vec![
+            DegreeCentrality::new(0, 2),
+            DegreeCentrality::new(1, 2),
+        ]);
+        assert_eq!(constant.spearman_correlation(&same_order), 0.0);
+        assert_eq!(
+            CentralityRank::<i32, DegreeCentrality>::new(Vec::new())
+                .spearman_correlation(&same_order),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_statistics_of_empty_rank() {
+        assert_eq!(
+            CentralityRank::<i32, DegreeCentrality>::new(Vec::new()).statistics(),
+            Err(EmptyRank)
+        );
+    }
+}
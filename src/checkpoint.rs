@@ -0,0 +1,216 @@
+//! Saving the progress of long computations so they can be resumed
+//!
+//! Algorithms that support checkpoints take a [`Checkpointer`], save their state to
+//! it every so many units of work, and start from the saved state if there is one.
+//! A failed save stops the computation with the error, which leaves the last saved
+//! state in place for the next run.
+
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Somewhere the state of a computation can be saved and loaded from
+pub trait Checkpointer {
+    /// Saves the state, replacing any state saved before
+    fn save(&self, state: &[u8]) -> io::Result<()>;
+    /// Returns the last saved state, or `None` if there is none
+    fn load(&self) -> Option<Vec<u8>>;
+}
+
+/// Saves checkpoints to a file
+///
+/// Each state is written to a temporary file next to the checkpoint, which then
+/// replaces the checkpoint, so an interrupted save never leaves a partial state.
+pub struct FileCheckpointer {
+    path: PathBuf,
+}
+
+impl FileCheckpointer {
+    /// Creates a checkpointer saving to a file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to save to, which is loaded from if it exists
+    pub fn new(path: impl AsRef<Path>) -> FileCheckpointer {
+        FileCheckpointer {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+    /// Removes the checkpoint, for example once the computation has finished
+    pub fn clear(&self) -> io::Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Checkpointer for FileCheckpointer {
+    fn save(&self, state: &[u8]) -> io::Result<()> {
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        let mut file = fs::File::create(&temporary)?;
+        file.write_all(state)?;
+        file.sync_all()?;
+        fs::rename(&temporary, &self.path)
+    }
+
+    fn load(&self) -> Option<Vec<u8>> {
+        fs::read(&self.path).ok()
+    }
+}
+
+/// Keeps checkpoints in memory
+///
+/// It can be limited to a number of saves, after which saving fails, to interrupt a
+/// computation at a known point.
+#[derive(Default)]
+pub struct MemoryCheckpointer {
+    state: RefCell<Option<Vec<u8>>>,
+    saves_left: Cell<Option<usize>>,
+}
+
+impl MemoryCheckpointer {
+    /// Creates a checkpointer without a saved state
+    pub fn new() -> MemoryCheckpointer {
+        MemoryCheckpointer::default()
+    }
+    /// Makes every save after the next `saves` fail
+    pub fn fail_after(self, saves: usize) -> MemoryCheckpointer {
+        self.saves_left.set(Some(saves));
+        self
+    }
+}
+
+impl Checkpointer for MemoryCheckpointer {
+    fn save(&self, state: &[u8]) -> io::Result<()> {
+        match self.saves_left.get() {
+            Some(0) => return Err(io::Error::other("checkpoint save limit reached")),
+            Some(saves) => self.saves_left.set(Some(saves - 1)),
+            None => {}
+        }
+        *self.state.borrow_mut() = Some(state.to_vec());
+        Ok(())
+    }
+
+    fn load(&self) -> Option<Vec<u8>> {
+        self.state.borrow().clone()
+    }
+}
+
+/// Encodes the progress of a computation as a count of finished units of work and a
+/// score for each vertex
+///
+/// The vertex ids are saved with the scores, so that a state is only resumed on the
+/// network it was saved for.
+///
+/// # Arguments
+///
+/// * `done` - The number of units of work finished
+/// * `vertices` - The ids of the vertices, in the order of the scores
+/// * `values` - The intermediate scores
+///
+/// # Panics
+///
+/// Panics if `vertices` and `values` differ in length.
+pub fn encode_state(done: usize, vertices: &[usize], values: &[f64]) -> Vec<u8> {
+    assert_eq!(
+        vertices.len(),
+        values.len(),
+        "expected one score per vertex"
+    );
+    let mut state = Vec::with_capacity(16 + 16 * values.len());
+    state.extend_from_slice(&(done as u64).to_le_bytes());
+    state.extend_from_slice(&(values.len() as u64).to_le_bytes());
+    for &vertex in vertices {
+        state.extend_from_slice(&(vertex as u64).to_le_bytes());
+    }
+    for value in values {
+        state.extend_from_slice(&value.to_le_bytes());
+    }
+    state
+}
+
+/// Decodes a state written by [`encode_state`]
+///
+/// # Arguments
+///
+/// * `state` - The saved state
+/// * `vertices` - The ids of the vertices the computation expects scores for, in
+///   order
+///
+/// # Returns
+///
+/// * `(done, values)` - The number of units of work finished and the scores, or an
+///   error of kind `InvalidData` if the state is malformed or has scores for other
+///   vertices, as when it was saved for another network
+pub fn decode_state(state: &[u8], vertices: &[usize]) -> io::Result<(usize, Vec<f64>)> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "checkpoint does not match");
+    let words: Vec<[u8; 8]> = state
+        .chunks(8)
+        .map(|chunk| chunk.try_into().map_err(|_| invalid()))
+        .collect::<io::Result<_>>()?;
+    let [done, count, rest @ ..] = words.as_slice() else {
+        return Err(invalid());
+    };
+    let len = vertices.len();
+    if u64::from_le_bytes(*count) != len as u64 || rest.len() != 2 * len {
+        return Err(invalid());
+    }
+    let (ids, values) = rest.split_at(len);
+    if !ids
+        .iter()
+        .zip(vertices)
+        .all(|(&id, &vertex)| u64::from_le_bytes(id) == vertex as u64)
+    {
+        return Err(invalid());
+    }
+    let values = values
+        .iter()
+        .map(|&word| f64::from_le_bytes(word))
+        .collect();
+    Ok((u64::from_le_bytes(*done) as usize, values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_round_trip() {
+        let state = encode_state(7, &[4, 2, 9], &[0.5, -1.25, f64::MAX]);
+        assert_eq!(
+            decode_state(&state, &[4, 2, 9]).unwrap(),
+            (7, vec![0.5, -1.25, f64::MAX])
+        );
+        assert!(decode_state(&state, &[4, 2]).is_err());
+        assert!(decode_state(&state, &[4, 2, 8]).is_err());
+        assert!(decode_state(&state[..state.len() - 1], &[4, 2, 9]).is_err());
+        assert!(decode_state(&[], &[]).is_err());
+    }
+
+    #[test]
+    fn test_file_checkpointer() {
+        let path = std::env::temp_dir().join(format!("checkpoint-test-{}", std::process::id()));
+        let checkpointer = FileCheckpointer::new(&path);
+        checkpointer.clear().unwrap();
+        assert_eq!(checkpointer.load(), None);
+        checkpointer.save(b"first").unwrap();
+        checkpointer.save(b"second").unwrap();
+        assert_eq!(
+            FileCheckpointer::new(&path).load(),
+            Some(b"second".to_vec())
+        );
+        checkpointer.clear().unwrap();
+        assert_eq!(checkpointer.load(), None);
+    }
+
+    #[test]
+    fn test_memory_checkpointer_save_limit() {
+        let checkpointer = MemoryCheckpointer::new().fail_after(1);
+        checkpointer.save(b"kept").unwrap();
+        assert!(checkpointer.save(b"lost").is_err());
+        assert_eq!(checkpointer.load(), Some(b"kept".to_vec()));
+    }
+}
@@ -0,0 +1,28 @@
+use std::borrow::Cow;
+
+/// Escapes a field for a CSV file as described in RFC 4180
+///
+/// Fields containing a comma, a double quote or a line break are enclosed in
+/// double quotes, with each double quote inside doubled. Other fields are
+/// returned unchanged.
+pub fn escape_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\r', '\n']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_field() {
+        assert_eq!(escape_field("plain"), "plain");
+        assert_eq!(escape_field(""), "");
+        assert_eq!(escape_field("a, b"), "\"a, b\"");
+        assert_eq!(escape_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_field("two\nlines"), "\"two\nlines\"");
+    }
+}
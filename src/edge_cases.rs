@@ -0,0 +1,282 @@
+//! Runs the public algorithms on the smallest networks: the empty network and a
+//! network of a single isolated paper
+
+use std::collections::HashMap;
+
+use crate::analysis::co_citation::{bibliographic_coupling_network, co_citation_network};
+use crate::analysis::community::community_report;
+use crate::analysis::hindex::calculate_citer_hindex_centrality;
+use crate::analysis::reachability::ReachabilityIndex;
+use crate::analysis::rings::{citation_rings, mutual_citation_pairs};
+use crate::analysis::roles::{
+    calculate_participation_centrality, calculate_within_module_zscore_centrality,
+};
+use crate::analysis::second_order::{distinct_citers_within, indirect_citers_within};
+use crate::analysis::uplift::pagerank_vs_degree;
+use crate::centrality::betweenness_centrality::{
+    calculate_betweenness_approx, calculate_betweenness_centrality,
+    calculate_betweenness_checkpointed,
+};
+use crate::centrality::centrality::Centrality;
+use crate::centrality::citation_velocity::calculate_citation_velocity;
+use crate::centrality::core_rank::calculate_core_rank;
+use crate::centrality::degree_centrality::calculate_degree_centrality;
+use crate::centrality::edge_betweenness::{calculate_edge_betweenness, top_bridging_edges};
+use crate::centrality::harmonic_centrality::{
+    calculate_harmonic_centrality, calculate_harmonic_centrality_sampled,
+};
+use crate::centrality::impact_score::calculate_impact_score;
+use crate::centrality::katz_status::calculate_katz_status;
+use crate::centrality::measure::registry;
+use crate::centrality::normalize::Normalization;
+use crate::centrality::pagerank_centrality::{
+    calculate_pagerank_centrality, calculate_pagerank_with_history,
+    calculate_personalized_damped_pagerank, calculate_time_decayed_pagerank, PageRankConfig,
+};
+use crate::centrality::report::CombinedReport;
+use crate::centrality::statistics::EmptyRank;
+use crate::checkpoint::MemoryCheckpointer;
+use crate::metadata::{Date, MetadataStore};
+use crate::network::temporal::TimestampedCitationNetwork;
+use crate::network::{CitationNetwork, Direction};
+use crate::stats::{
+    component_size_distribution, describe_components, significance_test, StatisticKind,
+};
+
+/// The isolated paper of the single-node network
+const PAPER: usize = 7;
+
+fn single_node() -> CitationNetwork {
+    let mut network = CitationNetwork::new();
+    network.add_node(PAPER);
+    network
+}
+
+/// Returns the scores of a rank by vertex
+fn scores<T, U: Centrality<T>>(
+    rank: &crate::centrality::centrality::CentralityRank<T, U>,
+) -> Vec<(usize, T)> {
+    rank.iter().map(|c| (c.vertex(), c.score())).collect()
+}
+
+#[test]
+fn test_centrality_of_empty_network() {
+    let network = CitationNetwork::new();
+    assert!(calculate_degree_centrality(&network).is_empty());
+    assert!(calculate_pagerank_centrality(&network).is_empty());
+    assert!(calculate_betweenness_centrality(&network).is_empty());
+    assert!(calculate_betweenness_approx(&network, 10, 1).is_empty());
+    assert!(calculate_harmonic_centrality(&network, Direction::Incoming).is_empty());
+    assert!(calculate_impact_score(&network, 0.5).is_empty());
+    assert!(calculate_katz_status(&network).is_empty());
+    assert!(calculate_citer_hindex_centrality(&network).is_empty());
+    assert!(calculate_core_rank(&network).is_empty());
+    assert!(distinct_citers_within(&network, 2).is_empty());
+    assert!(indirect_citers_within(&network, 2).is_empty());
+    assert!(calculate_edge_betweenness(&network).is_empty());
+    assert!(top_bridging_edges(&network, 3).is_empty());
+    assert!(pagerank_vs_degree(&network).is_empty());
+    let (result, history) =
+        calculate_pagerank_with_history(&network, &PageRankConfig::new(), &[]).unwrap();
+    assert!(result.ranks.is_empty());
+    assert_eq!(result.mass, 0.0);
+    assert_eq!(history.len(), result.iterations);
+    assert!(
+        calculate_personalized_damped_pagerank(&network, &HashMap::new())
+            .ranks
+            .is_empty()
+    );
+    assert!(
+        calculate_harmonic_centrality_sampled(&network, Direction::Outgoing, Some(3), 1).is_empty()
+    );
+    assert!(PageRankConfig::new()
+        .calculate_checkpointed(&network, &MemoryCheckpointer::new(), 1)
+        .unwrap()
+        .is_empty());
+    assert!(
+        calculate_betweenness_checkpointed(&network, &MemoryCheckpointer::new(), 1)
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        calculate_time_decayed_pagerank(&TimestampedCitationNetwork::new(), 2020, 5.0).is_empty()
+    );
+    let velocity = calculate_citation_velocity(
+        &network,
+        &MetadataStore::new(),
+        Date::new(2020, 1, 1).unwrap(),
+    );
+    assert!(velocity.ranks.is_empty());
+    assert_eq!(velocity.undated, 0);
+    for measure in registry() {
+        assert!(measure.compute(&network).is_empty(), "{}", measure.name());
+    }
+}
+
+#[test]
+fn test_centrality_of_single_node() {
+    let network = single_node();
+    assert_eq!(
+        scores(&calculate_degree_centrality(&network)),
+        vec![(PAPER, 0)]
+    );
+    assert_eq!(
+        scores(&calculate_pagerank_centrality(&network)),
+        vec![(PAPER, 1.0)]
+    );
+    assert_eq!(
+        scores(&calculate_betweenness_centrality(&network)),
+        vec![(PAPER, 0.0)]
+    );
+    assert_eq!(
+        scores(&calculate_harmonic_centrality(
+            &network,
+            Direction::Incoming
+        )),
+        vec![(PAPER, 0.0)]
+    );
+    assert_eq!(scores(&calculate_impact_score(&network, 0.5)).len(), 1);
+    assert_eq!(scores(&calculate_katz_status(&network)), vec![(PAPER, 0.0)]);
+    assert_eq!(
+        scores(&calculate_citer_hindex_centrality(&network)),
+        vec![(PAPER, 0)]
+    );
+    assert_eq!(
+        scores(&calculate_harmonic_centrality_sampled(
+            &network,
+            Direction::Outgoing,
+            Some(3),
+            1
+        )),
+        vec![(PAPER, 0.0)]
+    );
+    let checkpointed = PageRankConfig::new()
+        .calculate_checkpointed(&network, &MemoryCheckpointer::new(), 1)
+        .unwrap();
+    assert_eq!(scores(&checkpointed), vec![(PAPER, 1.0)]);
+    let velocity = calculate_citation_velocity(
+        &network,
+        &MetadataStore::new(),
+        Date::new(2020, 1, 1).unwrap(),
+    );
+    assert!(velocity.ranks.is_empty());
+    assert_eq!(velocity.undated, 1);
+    assert_eq!(scores(&calculate_core_rank(&network)), vec![(PAPER, 0.0)]);
+    assert_eq!(
+        scores(&distinct_citers_within(&network, 2)),
+        vec![(PAPER, 0)]
+    );
+    assert!(calculate_edge_betweenness(&network).is_empty());
+    for measure in registry() {
+        let computed = measure.compute(&network);
+        assert_eq!(computed.len(), 1, "{}", measure.name());
+        assert!(computed[0].1.is_finite(), "{}", measure.name());
+    }
+}
+
+#[test]
+fn test_rank_display_and_statistics() {
+    for network in [CitationNetwork::new(), single_node()] {
+        let ranks = calculate_pagerank_centrality(&network);
+        assert_eq!(ranks.top(5).len(), network.size());
+        assert!(ranks.k_nearest_by_score(PAPER, 3).is_empty());
+        ranks.top(5).to_string();
+        ranks.rank_range(3, 10).to_string();
+        let mut csv = Vec::new();
+        ranks.to_csv(&mut csv).unwrap();
+        for method in [
+            Normalization::MinMax,
+            Normalization::SumToOne,
+            Normalization::ZScore,
+        ] {
+            let normalized = ranks.normalize(method);
+            assert!(normalized.iter().all(|c| c.score().is_finite()));
+        }
+        assert!(ranks.z_scores().values().all(|z| z.is_finite()));
+        assert!(ranks.percentiles().values().all(|p| p.is_finite()));
+        match ranks.statistics() {
+            Ok(statistics) => {
+                assert_eq!(statistics.mean, 1.0);
+                assert_eq!(statistics.variance, 0.0);
+                statistics.to_string();
+            }
+            Err(error) => assert_eq!(error, EmptyRank),
+        }
+        assert_eq!(ranks.statistics().is_ok(), network.size() == 1);
+        CombinedReport::new(5)
+            .add("pagerank", &ranks)
+            .add("degree", &calculate_degree_centrality(&network))
+            .to_string();
+    }
+}
+
+#[test]
+fn test_network_measures() {
+    for network in [CitationNetwork::new(), single_node()] {
+        let size = network.size();
+        assert_eq!(network.degree_assortativity(), 0.0);
+        assert_eq!(network.average_clustering_coefficient(), 0.0);
+        assert_eq!(network.triangle_count(), 0);
+        assert_eq!(network.reciprocity(), 0.0);
+        assert_eq!(network.connected_components().len(), size);
+        assert_eq!(network.strongly_connected_components().len(), size);
+        assert!(network.articulation_points().is_empty());
+        assert!(network.bridges().is_empty());
+        assert_eq!(network.topological_order().unwrap().len(), size);
+        assert_eq!(network.transitive_reduction().unwrap().size(), size);
+        assert_eq!(network.greedy_coloring().len(), size);
+        assert_eq!(network.chromatic_number_upper_bound(), size);
+        assert!(network.is_bipartite().is_some());
+        assert_eq!(network.eccentricities().len(), size);
+        assert_eq!(network.louvain(1).len(), size);
+        assert_eq!(network.louvain_result(1).num_communities(), size);
+        assert_eq!(network.modularity(&network.louvain(1), 1.0), 0.0);
+        assert_eq!(network.approx_spectral_gap(10), 0.0);
+        let (first, second) = network.spectral_bisect();
+        assert_eq!(first.size() + second.size(), size);
+        assert_eq!(network.complement().num_edges(), 0);
+        assert_eq!(network.to_undirected().size(), size);
+        assert_eq!(network.diffuse(&HashMap::new(), 3).len(), size);
+        assert_eq!(network.compact().0.size(), size);
+        assert_eq!(network.filter_by_min_in_degree(1).0.size(), 0);
+        assert_eq!(network.sample_nodes(0.5, 1).num_edges(), 0);
+        assert_eq!(network.rewire_preserving_degrees(10, 1).size(), size);
+        assert!(network.find_hubs_and_spokes(1).is_empty());
+        assert!(network.targeted_attack_sequence(3).len() <= size);
+        assert_eq!(co_citation_network(&network, 1).network().size(), size);
+        assert_eq!(
+            bibliographic_coupling_network(&network, 1).network().size(),
+            size
+        );
+        assert!(mutual_citation_pairs(&network).is_empty());
+        assert!(citation_rings(&network, 4).is_empty());
+        let components = component_size_distribution(&network);
+        assert_eq!(components.values().sum::<usize>(), size);
+        assert!(!describe_components(&components).is_empty());
+        let partition = network.louvain(1);
+        assert_eq!(community_report(&network, &partition).len(), size);
+        assert_eq!(
+            calculate_participation_centrality(&network, &partition).len(),
+            size
+        );
+        assert_eq!(
+            calculate_within_module_zscore_centrality(&network, &partition).len(),
+            size
+        );
+        let reachability = ReachabilityIndex::build(&network);
+        assert_eq!(reachability.reaches(PAPER, PAPER), size == 1);
+        for statistic in [
+            StatisticKind::Clustering,
+            StatisticKind::Assortativity,
+            StatisticKind::Reciprocity,
+        ] {
+            let result = significance_test(&network, statistic, 5, 1);
+            assert!(result.observed.is_finite());
+        }
+        let mut output = Vec::new();
+        network.write_edge_list(&mut output).unwrap();
+        network.to_gml(&mut output).unwrap();
+        network.to_graphml(&mut output).unwrap();
+        network.to_matrix_market(&mut output).unwrap();
+    }
+}
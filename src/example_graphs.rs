@@ -0,0 +1,293 @@
+//! Small networks of known structure for tests, examples and benchmarks
+//!
+//! Papers are numbered from 0. In the star, the complete networks and the karate
+//! club, later papers cite earlier ones, as in a real citation network. In a path
+//! and a cycle paper `i` cites paper `i + 1`, and the bridge between two cliques
+//! runs from the first clique to the second, so those citations point from an
+//! earlier paper to a later one.
+//!
+//! ```
+//! use citation_network_analysis::centrality::degree_centrality::calculate_degree_centrality;
+//! use citation_network_analysis::example_graphs::star;
+//!
+//! // Five papers cite the hub 0, so it has the highest in-degree
+//! let network = star(5);
+//! let ranks = calculate_degree_centrality(&network);
+//! assert_eq!(ranks.top(1).to_string(), "vertex 0: in-degree 5\n");
+//! ```
+
+use crate::network::CitationNetwork;
+
+/// The friendships of Zachary's karate club, as pairs of members with the
+/// larger id first
+const KARATE_CLUB: [(usize, usize); 78] = [
+    (1, 0),
+    (2, 0),
+    (3, 0),
+    (4, 0),
+    (5, 0),
+    (6, 0),
+    (7, 0),
+    (8, 0),
+    (10, 0),
+    (11, 0),
+    (12, 0),
+    (13, 0),
+    (17, 0),
+    (19, 0),
+    (21, 0),
+    (31, 0),
+    (2, 1),
+    (3, 1),
+    (7, 1),
+    (13, 1),
+    (17, 1),
+    (19, 1),
+    (21, 1),
+    (30, 1),
+    (3, 2),
+    (7, 2),
+    (8, 2),
+    (9, 2),
+    (13, 2),
+    (27, 2),
+    (28, 2),
+    (32, 2),
+    (7, 3),
+    (12, 3),
+    (13, 3),
+    (6, 4),
+    (10, 4),
+    (6, 5),
+    (10, 5),
+    (16, 5),
+    (16, 6),
+    (30, 8),
+    (32, 8),
+    (33, 8),
+    (33, 9),
+    (33, 13),
+    (32, 14),
+    (33, 14),
+    (32, 15),
+    (33, 15),
+    (32, 18),
+    (33, 18),
+    (33, 19),
+    (32, 20),
+    (33, 20),
+    (32, 22),
+    (33, 22),
+    (25, 23),
+    (27, 23),
+    (29, 23),
+    (32, 23),
+    (33, 23),
+    (25, 24),
+    (27, 24),
+    (31, 24),
+    (31, 25),
+    (29, 26),
+    (33, 26),
+    (33, 27),
+    (31, 28),
+    (33, 28),
+    (32, 29),
+    (33, 29),
+    (32, 30),
+    (33, 30),
+    (32, 31),
+    (33, 31),
+    (33, 32),
+];
+
+/// Returns a path of `n` papers in which paper `i` cites paper `i + 1`
+///
+/// The network has `n` papers and `n - 1` citations. Paper 0 is cited by nobody
+/// and paper `n - 1` cites nothing.
+///
+/// # Arguments
+///
+/// * `n` - The number of papers
+pub fn path(n: usize) -> CitationNetwork {
+    let mut network = CitationNetwork::new();
+    for i in 0..n {
+        network.add_node(i);
+        if i > 0 {
+            network.add_edge(i - 1, i);
+        }
+    }
+    network
+}
+
+/// Returns a star of `n` papers citing the hub 0
+///
+/// The network has `n + 1` papers and `n` citations. The hub has in-degree `n` and
+/// every leaf `1..=n` has out-degree 1.
+///
+/// # Arguments
+///
+/// * `n` - The number of leaves
+pub fn star(n: usize) -> CitationNetwork {
+    let mut network = CitationNetwork::new();
+    network.add_node(0);
+    for leaf in 1..=n {
+        network.add_edge(leaf, 0);
+    }
+    network
+}
+
+/// Returns a cycle of `n` papers in which paper `i` cites paper `(i + 1) % n`
+///
+/// The network has `n` papers and `n` citations, and is a single strongly
+/// connected component.
+///
+/// # Arguments
+///
+/// * `n` - The number of papers
+///
+/// # Panics
+///
+/// Panics if `n` is less than 2.
+pub fn cycle(n: usize) -> CitationNetwork {
+    assert!(n >= 2, "a cycle needs at least two papers");
+    (0..n).map(|i| (i, (i + 1) % n)).collect()
+}
+
+/// Returns `n` papers each citing every earlier paper
+///
+/// The network has `n` papers and `n (n - 1) / 2` citations. Paper `i` has
+/// in-degree `n - 1 - i` and out-degree `i`, and the network is acyclic.
+///
+/// # Arguments
+///
+/// * `n` - The number of papers
+pub fn complete_dag(n: usize) -> CitationNetwork {
+    let mut network = CitationNetwork::new();
+    add_complete_dag(&mut network, 0, n);
+    network
+}
+
+/// Adds papers `start..start + n` to a network, each citing every earlier one of
+/// them
+fn add_complete_dag(network: &mut CitationNetwork, start: usize, n: usize) {
+    for i in start..start + n {
+        network.add_node(i);
+        for j in i + 1..start + n {
+            network.add_edge(j, i);
+        }
+    }
+}
+
+/// Returns two groups of `k` papers, each citing every earlier paper of its group,
+/// joined by a single citation
+///
+/// The groups are `0..k` and `k..2k`, each a [`complete_dag`], and the last paper of
+/// the first group, `k - 1`, cites the first paper of the second, `k`. The network
+/// has `2k` papers and `k (k - 1) + 1` citations, and its two groups are the
+/// communities found by community detection.
+///
+/// # Arguments
+///
+/// * `k` - The number of papers in each group
+///
+/// # Panics
+///
+/// Panics if `k` is 0.
+pub fn two_cliques_bridge(k: usize) -> CitationNetwork {
+    assert!(k > 0, "the cliques need at least one paper");
+    let mut network = CitationNetwork::new();
+    add_complete_dag(&mut network, 0, k);
+    add_complete_dag(&mut network, k, k);
+    network.add_edge(k - 1, k);
+    network
+}
+
+/// Returns Zachary's karate club, with each friendship as a citation from the
+/// member with the larger id to the one with the smaller id
+///
+/// The network has 34 members and 78 citations, is acyclic, and is connected when
+/// citations are treated as undirected. The instructor, member 0, has degree 16,
+/// all of it cited, and the administrator, member 33, has degree 17, all of it
+/// citing. The club split into factions around these two members.
+pub fn zachary_like() -> CitationNetwork {
+    KARATE_CLUB.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        let network = path(5);
+        assert_eq!(network.size(), 5);
+        assert_eq!(network.num_edges(), 4);
+        assert!(network.contains_edge(0, 1));
+        assert!(network.contains_edge(3, 4));
+        assert_eq!(network.in_degree(0), 0);
+        assert_eq!(network.out_degree(4), 0);
+        assert_eq!(path(1).size(), 1);
+        assert_eq!(path(0).size(), 0);
+    }
+
+    #[test]
+    fn test_star() {
+        let network = star(6);
+        assert_eq!(network.size(), 7);
+        assert_eq!(network.num_edges(), 6);
+        assert_eq!(network.in_degree(0), 6);
+        assert!((1..=6).all(|leaf| network.out_degree(leaf) == 1));
+        assert_eq!(star(0).size(), 1);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let network = cycle(4);
+        assert_eq!(network.size(), 4);
+        assert_eq!(network.num_edges(), 4);
+        assert!(network.contains_edge(3, 0));
+        assert_eq!(network.strongly_connected_components().len(), 1);
+        assert!(network.topological_order().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "a cycle needs at least two papers")]
+    fn test_cycle_of_one_paper() {
+        cycle(1);
+    }
+
+    #[test]
+    fn test_complete_dag() {
+        let network = complete_dag(5);
+        assert_eq!(network.size(), 5);
+        assert_eq!(network.num_edges(), 10);
+        assert!((0..5).all(|i| network.in_degree(i) == 4 - i && network.out_degree(i) == i));
+        assert!(network.topological_order().is_ok());
+    }
+
+    #[test]
+    fn test_two_cliques_bridge() {
+        let network = two_cliques_bridge(5);
+        assert_eq!(network.size(), 10);
+        assert_eq!(network.num_edges(), 21);
+        assert!(network.contains_edge(4, 5));
+        assert_eq!(network.bridges().len(), 1);
+        let communities = network.louvain(1);
+        assert!((0..5).all(|i| communities[&i] == communities[&0]));
+        assert!((5..10).all(|i| communities[&i] == communities[&5]));
+        assert_ne!(communities[&0], communities[&5]);
+    }
+
+    #[test]
+    fn test_zachary_like() {
+        let network = zachary_like();
+        assert_eq!(network.size(), 34);
+        assert_eq!(network.num_edges(), 78);
+        assert_eq!(network.in_degree(0), 16);
+        assert_eq!(network.out_degree(33), 17);
+        assert_eq!(network.connected_components().len(), 1);
+        assert!(network.topological_order().is_ok());
+        assert!(KARATE_CLUB.iter().all(|&(from, to)| from > to));
+    }
+}
@@ -0,0 +1,328 @@
+//! Analyses of edge lists too large to load into memory
+//!
+//! The edges are streamed from the file, and partial results that outgrow a memory
+//! budget are spilled to sorted temporary files, which are merged at the end.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::centrality::centrality::{Centrality, CentralityRank};
+use crate::centrality::degree_centrality::DegreeCentrality;
+use crate::network::stream::EdgeStream;
+use crate::network::LoadError;
+
+/// The default memory budget of the partial counts, in bytes
+const MEMORY_BUDGET: usize = 256 * 1024 * 1024;
+
+/// The estimated memory taken by one entry of a count map, in bytes
+const BYTES_PER_ENTRY: usize = 32;
+
+/// Numbers the spill files, so concurrent counts never share a file name
+static NEXT_SPILL: AtomicUsize = AtomicUsize::new(0);
+
+/// The spill files of a count, which are removed when it finishes or fails
+struct SpillFiles {
+    paths: Vec<PathBuf>,
+}
+
+impl Drop for SpillFiles {
+    fn drop(&mut self) {
+        for path in &self.paths {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// A reader of the `(vertex, count)` records of a spill file, in ascending order of
+/// vertex
+struct SpillReader {
+    reader: BufReader<File>,
+}
+
+impl SpillReader {
+    /// Returns the next record, or `None` at the end of the file
+    fn next_record(&mut self) -> io::Result<Option<(usize, usize)>> {
+        let mut record = [0; 16];
+        match self.reader.read_exact(&mut record) {
+            Ok(()) => {
+                let vertex = u64::from_le_bytes(record[..8].try_into().unwrap());
+                let count = u64::from_le_bytes(record[8..].try_into().unwrap());
+                Ok(Some((vertex as usize, count as usize)))
+            }
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// The parameters of an out-of-core in-degree count
+pub struct ExternalDegreeCounter {
+    memory_budget: usize,
+}
+
+impl Default for ExternalDegreeCounter {
+    fn default() -> Self {
+        ExternalDegreeCounter {
+            memory_budget: MEMORY_BUDGET,
+        }
+    }
+}
+
+impl ExternalDegreeCounter {
+    /// Creates a counter with a memory budget of 256 MiB
+    pub fn new() -> ExternalDegreeCounter {
+        ExternalDegreeCounter::default()
+    }
+    /// Sets the memory the partial counts may take before they are spilled to disk
+    ///
+    /// The memory is estimated from the number of papers counted, so the actual use
+    /// can differ somewhat from the budget.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The memory budget, in bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is 0.
+    pub fn memory_budget(mut self, bytes: usize) -> ExternalDegreeCounter {
+        assert!(bytes > 0, "the memory budget must be positive");
+        self.memory_budget = bytes;
+        self
+    }
+    /// Counts the in-degrees of the papers of an edge list file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The edge list, in the format read by [`EdgeStream`]
+    /// * `temp_dir` - The directory to spill partial counts to
+    ///
+    /// # Returns
+    ///
+    /// The in-degree centrality of every paper in the file, as
+    /// [`crate::centrality::degree_centrality::calculate_degree_centrality`] gives
+    /// for the loaded network, or the error of the first malformed line or failed
+    /// read or write.
+    pub fn count(
+        &self,
+        path: &Path,
+        temp_dir: &Path,
+    ) -> Result<CentralityRank<i32, DegreeCentrality>, LoadError> {
+        Ok(self.count_with_spills(path, temp_dir)?.0)
+    }
+    /// Counts the in-degrees of the papers of an edge list file
+    ///
+    /// # Returns
+    ///
+    /// * `(ranks, spills)` - The in-degree centralities and the number of times the
+    ///   partial counts were spilled to disk
+    fn count_with_spills(
+        &self,
+        path: &Path,
+        temp_dir: &Path,
+    ) -> Result<(CentralityRank<i32, DegreeCentrality>, usize), LoadError> {
+        let mut spills = SpillFiles { paths: Vec::new() };
+        let mut counts: HashMap<usize, usize> = HashMap::new();
+        for edge in EdgeStream::new(File::open(path)?) {
+            let (from, to) = edge?;
+            counts.entry(from).or_insert(0);
+            *counts.entry(to).or_insert(0) += 1;
+            if counts.len() * BYTES_PER_ENTRY > self.memory_budget {
+                spill(&mut counts, temp_dir, &mut spills)?;
+            }
+        }
+        if !counts.is_empty() && !spills.paths.is_empty() {
+            spill(&mut counts, temp_dir, &mut spills)?;
+        }
+        let num_spills = spills.paths.len();
+        let merged = if spills.paths.is_empty() {
+            counts.into_iter().collect()
+        } else {
+            merge(&spills.paths)?
+        };
+        let mut ranks: Vec<DegreeCentrality> = merged
+            .into_iter()
+            .map(|(vertex, count)| DegreeCentrality::new(vertex, count as i32))
+            .collect();
+        ranks.sort_by(|a, b| b.partial_cmp(a).unwrap().then(a.vertex().cmp(&b.vertex())));
+        Ok((CentralityRank::new(ranks), num_spills))
+    }
+}
+
+/// Writes partial counts to a new spill file in ascending order of vertex, leaving
+/// the map empty
+///
+/// The file is added to the spill files before it is created, so it is removed
+/// even if writing it fails.
+///
+/// # Arguments
+///
+/// * `counts` - The partial counts
+/// * `temp_dir` - The directory of the spill file
+/// * `spills` - The spill files of the count
+fn spill(
+    counts: &mut HashMap<usize, usize>,
+    temp_dir: &Path,
+    spills: &mut SpillFiles,
+) -> io::Result<()> {
+    let mut records: Vec<(usize, usize)> = counts.drain().collect();
+    records.sort_unstable();
+    let path = temp_dir.join(format!(
+        "degree-spill-{}-{}",
+        process::id(),
+        NEXT_SPILL.fetch_add(1, Ordering::Relaxed)
+    ));
+    spills.paths.push(path.clone());
+    write_records(&records, File::create(&path)?)
+}
+
+/// Writes sorted counts in the format of a spill file
+fn write_records<W: Write>(records: &[(usize, usize)], writer: W) -> io::Result<()> {
+    let mut writer = BufWriter::new(writer);
+    for &(vertex, count) in records {
+        writer.write_all(&(vertex as u64).to_le_bytes())?;
+        writer.write_all(&(count as u64).to_le_bytes())?;
+    }
+    writer.flush()
+}
+
+/// Merges sorted spill files with a k-way merge, adding up the counts of each vertex
+fn merge(paths: &[PathBuf]) -> io::Result<Vec<(usize, usize)>> {
+    let mut readers = paths
+        .iter()
+        .map(|path| {
+            Ok(SpillReader {
+                reader: BufReader::new(File::open(path)?),
+            })
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    let mut heads = BinaryHeap::new();
+    for (file, reader) in readers.iter_mut().enumerate() {
+        if let Some((vertex, count)) = reader.next_record()? {
+            heads.push(Reverse((vertex, file, count)));
+        }
+    }
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    while let Some(Reverse((vertex, file, count))) = heads.pop() {
+        match merged.last_mut() {
+            Some((last, total)) if *last == vertex => *total += count,
+            _ => merged.push((vertex, count)),
+        }
+        if let Some((vertex, count)) = readers[file].next_record()? {
+            heads.push(Reverse((vertex, file, count)));
+        }
+    }
+    Ok(merged)
+}
+
+/// Counts the in-degrees of the papers of an edge list file too large to load
+///
+/// The file is streamed one line at a time. Counts beyond a memory budget of
+/// 256 MiB are spilled to sorted files in `temp_dir` and merged at the end, and the
+/// files are removed whether the count succeeds or fails. Use
+/// [`ExternalDegreeCounter`] to change the budget.
+///
+/// # Arguments
+///
+/// * `path` - The edge list, in the format read by [`EdgeStream`]
+/// * `temp_dir` - The directory to spill partial counts to
+pub fn external_degree_count(
+    path: &Path,
+    temp_dir: &Path,
+) -> Result<CentralityRank<i32, DegreeCentrality>, LoadError> {
+    ExternalDegreeCounter::new().count(path, temp_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::centrality::degree_centrality::calculate_degree_centrality;
+    use crate::generators::{edge_list, random_edges};
+    use crate::network::CitationNetwork;
+
+    /// Creates an empty directory that is unique to a test
+    fn temporary_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_spilled_count_matches_in_memory() {
+        let dir = temporary_dir("external-degree");
+        let text = edge_list(&random_edges(2_000, 10_000, 4));
+        let path = dir.join("edges.txt");
+        fs::write(&path, &text).unwrap();
+        let spill_dir = temporary_dir("external-degree-spills");
+        let (ranks, spills) = ExternalDegreeCounter::new()
+            .memory_budget(500 * BYTES_PER_ENTRY)
+            .count_with_spills(&path, &spill_dir)
+            .unwrap();
+        assert!(spills > 2);
+        let expected =
+            calculate_degree_centrality(&CitationNetwork::load_from_reader(text.as_bytes()));
+        assert_eq!(ranks.len(), expected.len());
+        for (actual, expected) in ranks.iter().zip(expected.iter()) {
+            assert_eq!(actual.vertex(), expected.vertex());
+            assert_eq!(actual.score(), expected.score());
+        }
+        assert_eq!(fs::read_dir(&spill_dir).unwrap().count(), 0);
+        let unspilled = external_degree_count(&path, &spill_dir).unwrap();
+        assert!(unspilled
+            .iter()
+            .map(|c| (c.vertex(), c.score()))
+            .eq(ranks.iter().map(|c| (c.vertex(), c.score()))));
+        fs::remove_dir_all(dir).unwrap();
+        fs::remove_dir_all(spill_dir).unwrap();
+    }
+
+    #[test]
+    fn test_spill_files_removed_on_error() {
+        let dir = temporary_dir("external-degree-error");
+        let mut text = edge_list(&random_edges(2_000, 5_000, 4));
+        text.push_str("1 x\n");
+        let path = dir.join("edges.txt");
+        fs::write(&path, &text).unwrap();
+        let spill_dir = temporary_dir("external-degree-error-spills");
+        let result = ExternalDegreeCounter::new()
+            .memory_budget(100 * BYTES_PER_ENTRY)
+            .count(&path, &spill_dir);
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 5005: invalid paper id: x"
+        );
+        assert_eq!(fs::read_dir(&spill_dir).unwrap().count(), 0);
+        fs::remove_dir_all(dir).unwrap();
+        fs::remove_dir_all(spill_dir).unwrap();
+    }
+
+    #[test]
+    fn test_spill_write_error() {
+        // A buffer with room for one record fails like a full disk
+        let mut buffer = [0u8; 16];
+        let result = write_records(&[(1, 2), (3, 4)], &mut buffer[..]);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::WriteZero);
+        assert_eq!(buffer[..8], 1u64.to_le_bytes());
+        assert_eq!(buffer[8..], 2u64.to_le_bytes());
+    }
+
+    #[test]
+    fn test_failed_spill_is_tracked() {
+        let spill_dir = temporary_dir("external-degree-missing");
+        let missing = spill_dir.join("missing");
+        let mut counts: HashMap<usize, usize> = (0..100).map(|v| (v, 1)).collect();
+        let mut spills = SpillFiles { paths: Vec::new() };
+        let result = spill(&mut counts, &missing, &mut spills);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+        assert_eq!(spills.paths.len(), 1);
+        assert!(counts.is_empty());
+        drop(spills);
+        fs::remove_dir_all(spill_dir).unwrap();
+    }
+}
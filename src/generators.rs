@@ -0,0 +1,118 @@
+//! Random networks for tests and benchmarks
+//!
+//! The generators are seeded, so tests and benchmarks can build large networks
+//! reproducibly without checking data files into the repository.
+
+use std::fmt::Write;
+
+use crate::network::CitationNetwork;
+use crate::random::Random;
+
+/// Generates an Erdős–Rényi random network
+///
+/// Every ordered pair of distinct nodes `0..n` is joined by an edge independently
+/// with probability `p`, and every node is in the network even if it has no edges.
+/// The same seed always generates the same network.
+///
+/// # Arguments
+///
+/// * `n` - The number of nodes
+/// * `p` - The probability of each edge
+/// * `seed` - The seed of the random number generator
+pub fn erdos_renyi(n: usize, p: f64, seed: u64) -> CitationNetwork {
+    let mut random = Random::new(seed);
+    let mut network = CitationNetwork::new();
+    for from in 0..n {
+        network.add_node(from);
+        for to in 0..n {
+            if from != to && random.next_f64() < p {
+                network.add_edge(from, to);
+            }
+        }
+    }
+    network
+}
+
+/// Generates random citations between papers with uniformly chosen endpoints
+///
+/// Each of the `m` edges joins two distinct nodes of `0..n` chosen uniformly at
+/// random, so repeated citations are possible. Unlike [`erdos_renyi`] this runs in
+/// time linear in the number of edges, which makes it suitable for large networks.
+///
+/// # Arguments
+///
+/// * `n` - The number of nodes
+/// * `m` - The number of edges
+/// * `seed` - The seed of the random number generator
+///
+/// # Panics
+///
+/// Panics if `m` is positive and `n` is less than 2.
+pub fn random_edges(n: usize, m: usize, seed: u64) -> Vec<(usize, usize)> {
+    assert!(m == 0 || n >= 2, "edges need at least two nodes");
+    let mut random = Random::new(seed);
+    (0..m)
+        .map(|_| {
+            let from = random.below(n);
+            // Skip over `from` so that no edge is a self-citation
+            let to = random.below(n - 1);
+            (from, if to >= from { to + 1 } else { to })
+        })
+        .collect()
+}
+
+/// Formats edges as an edge list in the SNAP format read by
+/// [`CitationNetwork::load_from_reader`]
+///
+/// # Arguments
+///
+/// * `edges` - The (citing, cited) pairs to write
+pub fn edge_list(edges: &[(usize, usize)]) -> String {
+    let mut text = String::from("# Directed graph\n# Randomly generated\n");
+    let nodes = edges.iter().flat_map(|&(from, to)| [from, to]).max();
+    let num_nodes = nodes.map_or(0, |max| max + 1);
+    writeln!(text, "# Nodes: {} Edges: {}", num_nodes, edges.len()).unwrap();
+    text.push_str("# FromNodeId\tToNodeId\n");
+    for (from, to) in edges {
+        writeln!(text, "{}\t{}", from, to).unwrap();
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_erdos_renyi() {
+        let network = erdos_renyi(100, 0.1, 42);
+        assert_eq!(network.size(), 100);
+        // The expected number of edges is 990 with a standard deviation of about 30
+        assert!((800..1200).contains(&network.num_edges()));
+        let again = erdos_renyi(100, 0.1, 42);
+        assert!(network.is_isomorphic_as_labeled(&again));
+        assert_eq!(erdos_renyi(10, 0.0, 1).num_edges(), 0);
+        assert_eq!(erdos_renyi(10, 1.0, 1).num_edges(), 90);
+    }
+
+    #[test]
+    fn test_random_edges() {
+        let edges = random_edges(50, 1000, 42);
+        assert_eq!(edges.len(), 1000);
+        assert!(edges
+            .iter()
+            .all(|&(from, to)| from != to && from < 50 && to < 50));
+        assert_eq!(edges, random_edges(50, 1000, 42));
+        assert!(random_edges(0, 0, 1).is_empty());
+    }
+
+    #[test]
+    fn test_edge_list_round_trip() {
+        let edges = random_edges(100, 500, 7);
+        let network = CitationNetwork::load_from_reader(edge_list(&edges).as_bytes());
+        assert_eq!(network.num_edges(), 500);
+        let mut expected = edges.clone();
+        expected.sort_unstable();
+        assert_eq!(network.edges_iter().collect::<Vec<_>>(), expected);
+    }
+}
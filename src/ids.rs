@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+/// A mapping between original paper identifiers and the ids used in a network
+///
+/// Datasets often identify papers by strings such as `hep-th/9207016`, whereas a
+/// `CitationNetwork` uses integer ids. The mapper assigns ids `0, 1, 2, ...` in
+/// the order identifiers are first seen.
+#[derive(Default)]
+pub struct IdMapper {
+    /// The original identifier of each id
+    originals: Vec<String>,
+    /// The id of each original identifier
+    ids: HashMap<String, usize>,
+}
+
+impl IdMapper {
+    /// Creates an empty mapper
+    pub fn new() -> IdMapper {
+        IdMapper::default()
+    }
+    /// Returns the id of an identifier, assigning the next free id if it is new
+    ///
+    /// # Arguments
+    ///
+    /// * `original` - The original identifier of the paper
+    pub fn get_or_insert(&mut self, original: &str) -> usize {
+        if let Some(&id) = self.ids.get(original) {
+            return id;
+        }
+        let id = self.originals.len();
+        self.originals.push(original.to_string());
+        self.ids.insert(original.to_string(), id);
+        id
+    }
+    /// Returns the id of an identifier, if it has one
+    pub fn id(&self, original: &str) -> Option<usize> {
+        self.ids.get(original).copied()
+    }
+    /// Returns the original identifier of an id, if it has one
+    pub fn original(&self, id: usize) -> Option<&str> {
+        self.originals.get(id).map(String::as_str)
+    }
+    /// Returns the number of identifiers in the mapper
+    pub fn len(&self) -> usize {
+        self.originals.len()
+    }
+    /// Returns whether the mapper is empty
+    pub fn is_empty(&self) -> bool {
+        self.originals.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id_mapper() {
+        let mut mapper = IdMapper::new();
+        assert_eq!(mapper.get_or_insert("hep-th/9207016"), 0);
+        assert_eq!(mapper.get_or_insert("hep-th/9201015"), 1);
+        assert_eq!(mapper.get_or_insert("hep-th/9207016"), 0);
+        assert_eq!(mapper.len(), 2);
+        assert_eq!(mapper.id("hep-th/9201015"), Some(1));
+        assert_eq!(mapper.id("hep-th/0000000"), None);
+        assert_eq!(mapper.original(0), Some("hep-th/9207016"));
+        assert_eq!(mapper.original(2), None);
+    }
+}
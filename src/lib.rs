@@ -0,0 +1,55 @@
+//! Analysis of citation networks
+//!
+//! A [`network::CitationNetwork`] holds the citations between papers, and the
+//! `centrality` and `analysis` modules compute rankings and statistics over it.
+
+pub mod checkpoint;
+pub mod csv;
+pub mod example_graphs;
+pub mod external;
+pub mod generators;
+pub mod ids;
+pub mod lookup;
+pub mod metadata;
+pub mod network;
+pub mod random;
+pub mod repl;
+pub mod stats;
+
+#[cfg(test)]
+mod edge_cases;
+
+pub mod analysis {
+    pub mod co_citation;
+    pub mod community;
+    pub mod hindex;
+    pub mod paths;
+    pub mod profile;
+    pub mod reachability;
+    pub mod rings;
+    pub mod roles;
+    pub mod second_order;
+    pub mod similarity;
+    pub mod streaming;
+    pub mod temporal;
+    pub mod uplift;
+}
+
+#[allow(clippy::module_inception)]
+pub mod centrality {
+    pub mod betweenness_centrality;
+    pub mod centrality;
+    pub mod citation_velocity;
+    pub mod compare;
+    pub mod core_rank;
+    pub mod degree_centrality;
+    pub mod edge_betweenness;
+    pub mod harmonic_centrality;
+    pub mod impact_score;
+    pub mod katz_status;
+    pub mod measure;
+    pub mod normalize;
+    pub mod pagerank_centrality;
+    pub mod report;
+    pub mod statistics;
+}
@@ -0,0 +1,229 @@
+//! Lookup of the papers of a network by their metadata
+//!
+//! Each query type is backed by a sorted index searched with binary search. An
+//! index is only built the first time its query is run, so a lookup that is only
+//! ever queried by year never sorts the titles.
+
+use std::cell::OnceCell;
+
+use crate::metadata::MetadataStore;
+use crate::network::pajek::LabeledCitationNetwork;
+
+/// An index of the papers of a labeled network by publication year, author and
+/// title
+///
+/// The years and authors come from a [`MetadataStore`]. The title of a paper is its
+/// title in the store if it has one, and otherwise its label, since the labels of
+/// a Pajek file are usually titles. Author names and titles are compared
+/// case-insensitively. Only papers in the network are indexed, so metadata of other
+/// papers is ignored.
+pub struct CitationNetworkIndex<'a> {
+    network: &'a LabeledCitationNetwork,
+    metadata: &'a MetadataStore,
+    /// The publication year and id of every paper with a valid date
+    years: OnceCell<Vec<(u32, usize)>>,
+    /// The lowercase name and paper id of every authorship
+    authors: OnceCell<Vec<(String, usize)>>,
+    /// The lowercase title and id of every paper with a title
+    titles: OnceCell<Vec<(String, usize)>>,
+}
+
+impl<'a> CitationNetworkIndex<'a> {
+    /// Creates an index of a network, without building any of its indices yet
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The papers to index, with their labels
+    /// * `metadata` - The years, authors and titles of the papers
+    pub fn new(network: &'a LabeledCitationNetwork, metadata: &'a MetadataStore) -> Self {
+        CitationNetworkIndex {
+            network,
+            metadata,
+            years: OnceCell::new(),
+            authors: OnceCell::new(),
+            titles: OnceCell::new(),
+        }
+    }
+    /// Returns the sorted `(key, vertex)` pairs of every paper of the network
+    fn sorted_index<K, F, I>(&self, keys: F) -> Vec<(K, usize)>
+    where
+        K: Ord,
+        F: Fn(usize) -> I,
+        I: IntoIterator<Item = K>,
+    {
+        let mut index: Vec<(K, usize)> = self
+            .network
+            .network()
+            .nodes()
+            .flat_map(|&vertex| keys(vertex).into_iter().map(move |key| (key, vertex)))
+            .collect();
+        index.sort_unstable();
+        index.dedup();
+        index
+    }
+    /// Returns the papers published in a year, in ascending order of id
+    ///
+    /// Papers without a valid date are never found.
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The publication year
+    pub fn find_by_year(&self, year: u32) -> Vec<usize> {
+        let years = self.years.get_or_init(|| {
+            self.sorted_index(|vertex| self.metadata.date(vertex).map(|date| date.year()))
+        });
+        let start = years.partition_point(|(y, _)| *y < year);
+        let end = years.partition_point(|(y, _)| *y <= year);
+        years[start..end]
+            .iter()
+            .map(|&(_, vertex)| vertex)
+            .collect()
+    }
+    /// Returns the papers with an author, in ascending order of id
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The full name of the author, in any case
+    pub fn find_by_author(&self, name: &str) -> Vec<usize> {
+        let authors = self.authors.get_or_init(|| {
+            self.sorted_index(|vertex| {
+                self.metadata
+                    .authors(vertex)
+                    .iter()
+                    .map(|author| author.to_lowercase())
+            })
+        });
+        let name = name.to_lowercase();
+        let start = authors.partition_point(|(author, _)| *author < name);
+        let end = authors.partition_point(|(author, _)| *author <= name);
+        authors[start..end]
+            .iter()
+            .map(|&(_, vertex)| vertex)
+            .collect()
+    }
+    /// Returns the papers whose title starts with a prefix, in ascending order of id
+    ///
+    /// The matching titles are adjacent in the sorted index, so the search costs
+    /// O(log n) plus the number of matches.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The start of the title, in any case
+    pub fn find_by_title_prefix(&self, prefix: &str) -> Vec<usize> {
+        let titles = self.titles.get_or_init(|| {
+            self.sorted_index(|vertex| {
+                let title = self.metadata.title(vertex);
+                title.or(self.network.label(vertex)).map(str::to_lowercase)
+            })
+        });
+        let prefix = prefix.to_lowercase();
+        let start = titles.partition_point(|(title, _)| *title < prefix);
+        let mut found: Vec<usize> = titles[start..]
+            .iter()
+            .take_while(|(title, _)| title.starts_with(&prefix))
+            .map(|&(_, vertex)| vertex)
+            .collect();
+        found.sort_unstable();
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Four labeled papers, of which only paper 4 has no title in the metadata
+    const PAJEK: &str = "*Vertices 4\n\
+                         1 \"S1\"\n\
+                         2 \"S2\"\n\
+                         3 \"S3\"\n\
+                         4 \"Dualities in field theory\"\n\
+                         *Arcs\n\
+                         2 1\n\
+                         3 1\n\
+                         3 2\n\
+                         4 3\n";
+
+    fn build_metadata() -> MetadataStore {
+        let mut metadata = MetadataStore::new();
+        for (vertex, title, date, authors) in [
+            (1, "Strings and branes", "1995-02-01", &["E. Witten"][..]),
+            (
+                2,
+                "String theory dynamics",
+                "1995-06-01",
+                &["E. Witten", "A. Strominger"],
+            ),
+            (
+                3,
+                "Black hole entropy",
+                "1996-01-10",
+                &["A. Strominger", "C. Vafa"],
+            ),
+            (5, "Unrelated", "1995-01-01", &["E. Witten"]),
+        ] {
+            metadata.set_title(vertex, title);
+            metadata.set_date(vertex, date);
+            metadata.set_authors(vertex, authors);
+        }
+        // The date of paper 4 is invalid
+        metadata.set_date(4, "1996-13-40");
+        metadata.set_authors(4, &["C. Vafa"]);
+        metadata
+    }
+
+    /// Paper 5 has metadata but is not in the network
+    fn build_network() -> LabeledCitationNetwork {
+        LabeledCitationNetwork::load_from_pajek(PAJEK.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_find_by_year() {
+        let (network, metadata) = (build_network(), build_metadata());
+        let index = CitationNetworkIndex::new(&network, &metadata);
+        assert_eq!(index.find_by_year(1995), [1, 2]);
+        assert_eq!(index.find_by_year(1996), [3]);
+        assert!(index.find_by_year(1994).is_empty());
+        assert!(index.find_by_year(2000).is_empty());
+    }
+
+    #[test]
+    fn test_find_by_author() {
+        let (network, metadata) = (build_network(), build_metadata());
+        let index = CitationNetworkIndex::new(&network, &metadata);
+        assert_eq!(index.find_by_author("E. Witten"), [1, 2]);
+        assert_eq!(index.find_by_author("a. strominger"), [2, 3]);
+        assert_eq!(index.find_by_author("C. VAFA"), [3, 4]);
+        assert!(index.find_by_author("C.").is_empty());
+        assert!(index.find_by_author("").is_empty());
+    }
+
+    #[test]
+    fn test_find_by_title_prefix() {
+        let (network, metadata) = (build_network(), build_metadata());
+        let index = CitationNetworkIndex::new(&network, &metadata);
+        assert_eq!(index.find_by_title_prefix("String"), [1, 2]);
+        assert_eq!(index.find_by_title_prefix("strings "), [1]);
+        // Paper 4 has no title in the metadata and is found by its label
+        assert_eq!(index.find_by_title_prefix("DUALITIES"), [4]);
+        // The labels of the other papers are not searched
+        assert!(index.find_by_title_prefix("S1").is_empty());
+        assert_eq!(index.find_by_title_prefix(""), [1, 2, 3, 4]);
+        assert!(index.find_by_title_prefix("Unrelated").is_empty());
+        assert!(index.find_by_title_prefix("Zeta").is_empty());
+    }
+
+    #[test]
+    fn test_indices_are_built_lazily() {
+        let (network, metadata) = (build_network(), build_metadata());
+        let index = CitationNetworkIndex::new(&network, &metadata);
+        index.find_by_year(1995);
+        assert!(index.years.get().is_some());
+        assert!(index.authors.get().is_none());
+        assert!(index.titles.get().is_none());
+        index.find_by_author("C. Vafa");
+        index.find_by_title_prefix("B");
+        assert_eq!(index.titles.get().unwrap().len(), 4);
+        assert_eq!(index.authors.get().unwrap().len(), 6);
+    }
+}
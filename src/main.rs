@@ -1,22 +1,145 @@
-use crate::centrality::degree_centrality::calculate_degree_centrality;
-use crate::centrality::pagerank_centrality::calculate_pagerank_centrality;
+use citation_network_analysis::analysis::profile::node_profile;
+use citation_network_analysis::analysis::second_order::distinct_citers_within;
+use citation_network_analysis::centrality::core_rank::calculate_core_rank;
+use citation_network_analysis::centrality::degree_centrality::calculate_degree_centrality;
+use citation_network_analysis::centrality::katz_status::calculate_katz_status;
+use citation_network_analysis::centrality::measure::{find_measure, registry};
+use citation_network_analysis::centrality::pagerank_centrality::calculate_pagerank_centrality;
+use citation_network_analysis::centrality::report::CombinedReport;
+use citation_network_analysis::metadata::MetadataStore;
+use citation_network_analysis::network::CitationNetwork;
+use citation_network_analysis::repl::Session;
 use std::fs::File;
+use std::io;
+use std::process;
 
-use crate::network::CitationNetwork;
-
-mod network;
+/// The command line options of the program
+#[derive(Default)]
+struct Options {
+    /// Print all rankings side by side
+    report: bool,
+    /// Print only the ranking of the named centrality measure
+    metric: Option<String>,
+    /// Print everything known about a single paper
+    profile: Option<usize>,
+    /// The file with the dates and titles of the papers
+    metadata: Option<String>,
+    /// Print details about the loaded data
+    verbose: bool,
+    /// The network file to explore interactively
+    interactive: Option<String>,
+}
 
-mod centrality {
-    pub(crate) mod centrality;
-    pub(crate) mod degree_centrality;
-    pub(crate) mod pagerank_centrality;
+impl Options {
+    /// Parses the command line arguments, excluding the program name
+    fn parse(args: impl Iterator<Item = String>) -> Result<Options, String> {
+        let mut options = Options::default();
+        let mut args = args;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--report" => options.report = true,
+                "--metric" => {
+                    let name = args.next().ok_or("--metric requires a measure name")?;
+                    options.metric = Some(name);
+                }
+                "--profile" => {
+                    let vertex = args.next().ok_or("--profile requires a vertex id")?;
+                    let vertex = vertex
+                        .parse()
+                        .map_err(|_| format!("invalid vertex id: {}", vertex))?;
+                    options.profile = Some(vertex);
+                }
+                "--metadata" => {
+                    let path = args.next().ok_or("--metadata requires a file name")?;
+                    options.metadata = Some(path);
+                }
+                "--verbose" => options.verbose = true,
+                "--interactive" => {
+                    let path = args.next().ok_or("--interactive requires a file name")?;
+                    options.interactive = Some(path);
+                }
+                _ => return Err(format!("unknown argument: {}", arg)),
+            }
+        }
+        Ok(options)
+    }
 }
 
 fn main() {
+    let options = Options::parse(std::env::args().skip(1)).unwrap_or_else(|message| {
+        eprintln!("error: {}", message);
+        process::exit(2);
+    });
+    let measure = options.metric.as_ref().map(|name| {
+        find_measure(name).unwrap_or_else(|| {
+            let names: Vec<_> = registry().iter().map(|m| m.name().to_string()).collect();
+            eprintln!(
+                "error: unknown measure {} (available: {})",
+                name,
+                names.join(", ")
+            );
+            process::exit(2);
+        })
+    });
+    if let Some(path) = &options.interactive {
+        let file = File::open(path).unwrap_or_else(|error| {
+            eprintln!("error: {}: {}", path, error);
+            process::exit(1);
+        });
+        let mut session = Session::new(CitationNetwork::load_from_file(file));
+        session
+            .run(io::stdin().lock(), io::stdout())
+            .unwrap_or_else(|error| {
+                eprintln!("error: {}", error);
+                process::exit(1);
+            });
+        return;
+    }
     let file = File::open("data/cit-HepTh.txt").unwrap();
     let network = CitationNetwork::load_from_file(file);
+    if options.verbose {
+        eprintln!("loaded network: {:?}", network);
+    }
+    if let Some(path) = &options.metadata {
+        let metadata = File::open(path)
+            .map_err(|error| error.into())
+            .and_then(MetadataStore::load_from_reader)
+            .unwrap_or_else(|error| {
+                eprintln!("error: {}: {}", path, error);
+                process::exit(1);
+            });
+        if options.verbose {
+            eprint!("{}", metadata.validate_against(&network));
+        }
+    }
+    if let Some(measure) = measure {
+        println!("{} Centrality Scores: ", measure.name());
+        for (vertex, score) in measure.compute(&network).into_iter().take(5) {
+            println!("vertex {}: {} {}", vertex, measure.name(), score);
+        }
+        return;
+    }
+    if let Some(vertex) = options.profile {
+        let profile = node_profile(&network, vertex).unwrap_or_else(|error| {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        });
+        let pagerank_ranks = calculate_pagerank_centrality(&network);
+        print!("{}", profile.with_pagerank(&pagerank_ranks));
+        return;
+    }
     let degree_ranks = calculate_degree_centrality(&network);
-    println!("Degree Centrality Scores: \n{}", degree_ranks.top(5));
     let pagerank_ranks = calculate_pagerank_centrality(&network);
+    if options.report {
+        let report = CombinedReport::new(5)
+            .add("in-degree", &degree_ranks)
+            .add("PageRank", &pagerank_ranks)
+            .add("Katz", &calculate_katz_status(&network))
+            .add("citers within 2", &distinct_citers_within(&network, 2))
+            .add("CoreRank", &calculate_core_rank(&network));
+        println!("{}", report);
+        return;
+    }
+    println!("Degree Centrality Scores: \n{}", degree_ranks.top(5));
     println!("PageRank Centrality Scores: \n{}", pagerank_ranks.top(5));
 }
@@ -0,0 +1,462 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::{BufRead, BufReader, Read};
+
+use crate::network::{CitationNetwork, LoadError};
+
+/// The largest number of ids listed for each problem in a [`ValidationReport`]
+pub const VALIDATION_SAMPLE_SIZE: usize = 10;
+
+/// The descriptive information about a single paper
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PaperMetadata {
+    pub title: Option<String>,
+    /// The publication date as written in the source, normally `YYYY-MM-DD`
+    pub date: Option<String>,
+    /// The names of the authors, in byline order
+    pub authors: Vec<String>,
+}
+
+/// The metadata of the papers in a network, keyed by vertex id
+#[derive(Default)]
+pub struct MetadataStore {
+    entries: HashMap<usize, PaperMetadata>,
+}
+
+impl MetadataStore {
+    /// Creates an empty store
+    pub fn new() -> MetadataStore {
+        MetadataStore::default()
+    }
+    /// Sets the title of a paper
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex` - The id of the paper
+    /// * `title` - The title of the paper
+    pub fn set_title(&mut self, vertex: usize, title: &str) {
+        self.entries.entry(vertex).or_default().title = Some(title.to_string());
+    }
+    /// Sets the publication date of a paper
+    ///
+    /// The date is stored as given, so that [`MetadataStore::validate_against`] can
+    /// report dates that are not of the form `YYYY-MM-DD`.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex` - The id of the paper
+    /// * `date` - The publication date of the paper
+    pub fn set_date(&mut self, vertex: usize, date: &str) {
+        self.entries.entry(vertex).or_default().date = Some(date.to_string());
+    }
+    /// Sets the authors of a paper
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex` - The id of the paper
+    /// * `authors` - The names of the authors, in byline order
+    pub fn set_authors(&mut self, vertex: usize, authors: &[&str]) {
+        self.entries.entry(vertex).or_default().authors =
+            authors.iter().map(|author| author.to_string()).collect();
+    }
+    /// Merges the metadata of a duplicate record of a paper into another record
+    ///
+    /// The title, date and authors of `keep` are kept when known and otherwise taken from
+    /// `merge`, whose metadata is removed. This matches
+    /// [`CitationNetwork::contract_nodes`] on the network.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - The id of the paper that remains
+    /// * `merge` - The id of the paper merged into it
+    pub fn merge(&mut self, keep: usize, merge: usize) {
+        if keep == merge {
+            return;
+        }
+        let Some(merged) = self.entries.remove(&merge) else {
+            return;
+        };
+        let kept = self.entries.entry(keep).or_default();
+        kept.title = kept.title.take().or(merged.title);
+        kept.date = kept.date.take().or(merged.date);
+        if kept.authors.is_empty() {
+            kept.authors = merged.authors;
+        }
+    }
+    /// Returns the metadata of a paper, if there is any
+    pub fn get(&self, vertex: usize) -> Option<&PaperMetadata> {
+        self.entries.get(&vertex)
+    }
+    /// Returns the title of a paper, if it is known
+    pub fn title(&self, vertex: usize) -> Option<&str> {
+        self.get(vertex)?.title.as_deref()
+    }
+    /// Returns the authors of a paper, which are empty if they are unknown
+    pub fn authors(&self, vertex: usize) -> &[String] {
+        self.get(vertex).map_or(&[], |metadata| &metadata.authors)
+    }
+    /// Returns the publication date of a paper, if it is known and valid
+    pub fn date(&self, vertex: usize) -> Option<Date> {
+        Date::parse(self.get(vertex)?.date.as_deref()?)
+    }
+    /// Returns the number of papers with metadata
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// Returns whether the store is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// Loads the dates and titles of papers
+    ///
+    /// Each line holds a paper id, its date and optionally its title, separated by
+    /// tabs, as in the SNAP `cit-HepTh-dates.txt` file with an extra title column.
+    /// Empty lines and lines starting with `#` are skipped, and an empty date is
+    /// treated as unknown. Dates are not checked here; see
+    /// [`MetadataStore::validate_against`].
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to load from
+    pub fn load_from_reader<R: Read>(reader: R) -> Result<MetadataStore, LoadError> {
+        let mut store = MetadataStore::new();
+        for (number, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(3, '\t');
+            let id = fields.next().unwrap().trim();
+            let vertex = id
+                .parse()
+                .map_err(|_| LoadError::parse(number + 1, format!("invalid paper id: {}", id)))?;
+            let entry = store.entries.entry(vertex).or_default();
+            match fields.next().map(str::trim) {
+                Some("") | None => {}
+                Some(date) => entry.date = Some(date.to_string()),
+            }
+            if let Some(title) = fields.next() {
+                entry.title = Some(title.trim().to_string());
+            }
+        }
+        Ok(store)
+    }
+    /// Compares the store with the network it describes
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network the metadata belongs to
+    pub fn validate_against(&self, network: &CitationNetwork) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        for vertex in network.nodes_sorted() {
+            if !self.entries.contains_key(&vertex) {
+                report.missing_metadata.record(vertex);
+            }
+        }
+        let mut vertices: Vec<usize> = self.entries.keys().copied().collect();
+        vertices.sort_unstable();
+        for vertex in vertices {
+            if !network.contains_node(vertex) {
+                report.unknown_nodes.record(vertex);
+            }
+            if let Some(date) = &self.entries[&vertex].date {
+                if !is_valid_date(date) {
+                    report.invalid_dates.record(vertex);
+                }
+            }
+        }
+        report
+    }
+}
+
+/// The average number of days in a year of the Gregorian calendar
+const DAYS_PER_YEAR: f64 = 365.2425;
+
+/// A calendar date
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// Creates a date, or returns `None` if it is not a real calendar date
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The year
+    /// * `month` - The month, from 1 to 12
+    /// * `day` - The day of the month, from 1
+    pub fn new(year: u32, month: u32, day: u32) -> Option<Date> {
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+        (1..=days)
+            .contains(&day)
+            .then_some(Date { year, month, day })
+    }
+    /// Parses a date of the form `YYYY-MM-DD`, or returns `None` if it is malformed
+    /// or not a real calendar date
+    pub fn parse(date: &str) -> Option<Date> {
+        let parts: Vec<&str> = date.split('-').collect();
+        let [year, month, day] = parts[..] else {
+            return None;
+        };
+        if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+            return None;
+        }
+        Date::new(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)
+    }
+    /// Returns the year of the date
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+    /// Returns the number of days since 1970-01-01, negative for earlier dates
+    pub fn days_since_epoch(&self) -> i64 {
+        // Howard Hinnant's days_from_civil, with years starting in March
+        let year = self.year as i64 - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = (self.month as i64 + 9) % 12;
+        let day_of_year = (153 * month + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+    /// Returns the number of years from this date to another, negative if the other
+    /// date is earlier
+    ///
+    /// Years are measured as the average Gregorian year of 365.2425 days.
+    pub fn years_until(&self, other: Date) -> f64 {
+        (other.days_since_epoch() - self.days_since_epoch()) as f64 / DAYS_PER_YEAR
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Returns whether a date is a real calendar date of the form `YYYY-MM-DD`
+fn is_valid_date(date: &str) -> bool {
+    Date::parse(date).is_some()
+}
+
+/// The number of papers with a problem, with the smallest of their ids
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IdSample {
+    /// The number of papers with the problem
+    pub count: usize,
+    /// The smallest ids, at most [`VALIDATION_SAMPLE_SIZE`] of them, sorted
+    pub sample: Vec<usize>,
+}
+
+impl IdSample {
+    /// Counts a paper, which must come after those already counted
+    fn record(&mut self, vertex: usize) {
+        self.count += 1;
+        if self.sample.len() < VALIDATION_SAMPLE_SIZE {
+            self.sample.push(vertex);
+        }
+    }
+}
+
+impl Display for IdSample {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.count)?;
+        if !self.sample.is_empty() {
+            let ids: Vec<String> = self.sample.iter().map(usize::to_string).collect();
+            let more = if self.count > self.sample.len() {
+                ", ..."
+            } else {
+                ""
+            };
+            write!(f, " ({}{})", ids.join(", "), more)?;
+        }
+        Ok(())
+    }
+}
+
+/// The mismatches between a [`MetadataStore`] and a network
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValidationReport {
+    /// Nodes of the network without any metadata
+    pub missing_metadata: IdSample,
+    /// Metadata entries for papers that are not in the network
+    pub unknown_nodes: IdSample,
+    /// Entries whose date is not a valid `YYYY-MM-DD` date
+    pub invalid_dates: IdSample,
+}
+
+impl ValidationReport {
+    /// Returns whether no problem was found
+    pub fn is_clean(&self) -> bool {
+        self.missing_metadata.count == 0
+            && self.unknown_nodes.count == 0
+            && self.invalid_dates.count == 0
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "nodes without metadata: {}", self.missing_metadata)?;
+        writeln!(
+            f,
+            "metadata for nodes not in the network: {}",
+            self.unknown_nodes
+        )?;
+        writeln!(f, "entries with unparseable dates: {}", self.invalid_dates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_store() {
+        let mut store = MetadataStore::new();
+        store.set_title(9207016, "Noncompact Symmetries in String Theory");
+        assert_eq!(store.len(), 1);
+        assert_eq!(
+            store.title(9207016),
+            Some("Noncompact Symmetries in String Theory")
+        );
+        assert_eq!(store.title(1), None);
+    }
+
+    fn build_network() -> CitationNetwork {
+        let mut network = CitationNetwork::new();
+        for vertex in 0..15 {
+            network.add_edge(vertex, vertex + 100);
+        }
+        network
+    }
+
+    #[test]
+    fn test_load_from_reader() {
+        let input = "# FromNodeId\tDate\n\
+                     9203201\t1992-03-31\n\
+                     \n\
+                     9207016\t1992-07-02\tNoncompact Symmetries in String Theory\n\
+                     9301001\t\tUndated\n";
+        let store = MetadataStore::load_from_reader(input.as_bytes()).unwrap();
+        assert_eq!(store.len(), 3);
+        assert_eq!(
+            store.get(9203201).unwrap().date.as_deref(),
+            Some("1992-03-31")
+        );
+        assert_eq!(store.title(9203201), None);
+        assert_eq!(
+            store.title(9207016),
+            Some("Noncompact Symmetries in String Theory")
+        );
+        assert_eq!(store.get(9301001).unwrap().date, None);
+        let error = MetadataStore::load_from_reader("1\t1992-01-01\nabc\t\n".as_bytes());
+        assert_eq!(
+            error.err().unwrap().to_string(),
+            "line 2: invalid paper id: abc"
+        );
+    }
+
+    #[test]
+    fn test_validate_against() {
+        let network = build_network();
+        let mut store = MetadataStore::new();
+        for vertex in 100..115 {
+            store.set_date(vertex, "1995-01-31");
+        }
+        store.set_date(3, "1992-02-30");
+        store.set_date(5, "19920301");
+        store.set_title(7, "No date");
+        store.set_date(500, "1999-12-31");
+        store.set_date(600, "2000-02-29");
+        let report = store.validate_against(&network);
+        assert_eq!(report.missing_metadata.count, 12);
+        assert_eq!(
+            report.missing_metadata.sample,
+            vec![0, 1, 2, 4, 6, 8, 9, 10, 11, 12]
+        );
+        assert_eq!(report.unknown_nodes.count, 2);
+        assert_eq!(report.unknown_nodes.sample, vec![500, 600]);
+        assert_eq!(report.invalid_dates.count, 2);
+        assert_eq!(report.invalid_dates.sample, vec![3, 5]);
+        assert!(!report.is_clean());
+        assert_eq!(
+            report.to_string(),
+            "nodes without metadata: 12 (0, 1, 2, 4, 6, 8, 9, 10, 11, 12, ...)\n\
+             metadata for nodes not in the network: 2 (500, 600)\n\
+             entries with unparseable dates: 2 (3, 5)\n"
+        );
+    }
+
+    #[test]
+    fn test_validate_matching_metadata() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(1, 2);
+        let mut store = MetadataStore::new();
+        store.set_date(1, "1992-03-31");
+        store.set_title(2, "Title only");
+        let report = store.validate_against(&network);
+        assert!(report.is_clean());
+        assert_eq!(
+            report.to_string(),
+            "nodes without metadata: 0\n\
+             metadata for nodes not in the network: 0\n\
+             entries with unparseable dates: 0\n"
+        );
+    }
+
+    #[test]
+    fn test_date() {
+        let date = Date::parse("1992-07-02").unwrap();
+        assert_eq!(date.year(), 1992);
+        assert_eq!(date.to_string(), "1992-07-02");
+        assert_eq!(Date::new(1970, 1, 1).unwrap().days_since_epoch(), 0);
+        assert_eq!(Date::new(2000, 3, 1).unwrap().days_since_epoch(), 11_017);
+        assert_eq!(Date::new(1969, 12, 31).unwrap().days_since_epoch(), -1);
+        let later = Date::new(2002, 7, 2).unwrap();
+        assert!((date.years_until(later) - 10.0).abs() < 0.01);
+        assert!((later.years_until(date) + 10.0).abs() < 0.01);
+        assert!(date < later);
+        assert_eq!(Date::new(1900, 2, 29), None);
+        assert_eq!(Date::parse("2000-02-29"), Date::new(2000, 2, 29));
+        assert_eq!(Date::parse("1992-7-02"), None);
+    }
+
+    #[test]
+    fn test_parsed_date() {
+        let mut store = MetadataStore::new();
+        store.set_date(1, "1992-03-31");
+        store.set_date(2, "1992-02-30");
+        store.set_title(3, "Undated");
+        assert_eq!(store.date(1), Date::new(1992, 3, 31));
+        assert_eq!(store.date(2), None);
+        assert_eq!(store.date(3), None);
+        assert_eq!(store.date(4), None);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut store = MetadataStore::new();
+        store.set_title(1, "Published title");
+        store.set_title(2, "Preprint title");
+        store.set_date(2, "1999-05-01");
+        store.set_authors(2, &["A. Author", "B. Author"]);
+        store.merge(1, 2);
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.title(1), Some("Published title"));
+        assert_eq!(store.get(1).unwrap().date.as_deref(), Some("1999-05-01"));
+        assert_eq!(store.authors(1), ["A. Author", "B. Author"]);
+        assert!(store.authors(2).is_empty());
+        store.merge(3, 4);
+        store.merge(1, 1);
+        assert_eq!(store.len(), 1);
+    }
+}
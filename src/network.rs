@@ -1,10 +1,169 @@
 use std::collections::HashMap;
-use std::fmt::{Debug, Formatter};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, Read};
+
+use crate::network::builder::{BuildStats, CitationNetworkBuilder};
+use crate::network::stream::{DeclaredCounts, EdgeStream};
+
+mod assortativity;
+pub mod builder;
+mod clustering;
+mod coloring;
+mod connectivity;
+mod dag;
+pub mod diff;
+pub mod diffusion;
+mod distance;
+mod edge_list;
+pub mod girvan_newman;
+mod gml;
+mod graphml;
+pub mod hubs;
+pub mod index;
+mod kcore;
+pub mod louvain;
+pub mod matrix;
+mod matrix_market;
+mod ndjson;
+#[cfg(feature = "serde_json")]
+mod networkx;
+pub mod pajek;
+mod parallel;
+mod reciprocity;
+mod rewiring;
+pub mod robustness;
+mod sampling;
+mod similarity;
+pub mod snapshot;
+mod spectral;
+pub mod stream;
+pub mod temporal;
+mod transform;
+pub mod weighted;
+
+/// The direction in which a traversal follows citations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// From a citing paper to the papers it cites
+    Outgoing,
+    /// From a cited paper to the papers citing it
+    Incoming,
+    /// Both ways, as if citations were undirected
+    Undirected,
+}
+
+impl Direction {
+    /// Returns the direction that undoes this one
+    pub fn reverse(self) -> Direction {
+        match self {
+            Direction::Outgoing => Direction::Incoming,
+            Direction::Incoming => Direction::Outgoing,
+            Direction::Undirected => Direction::Undirected,
+        }
+    }
+}
+
+/// The error returned when a paper id is not in the network
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownVertex(pub usize);
+
+impl Display for UnknownVertex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "vertex {} is not in the network", self.0)
+    }
+}
+
+impl Error for UnknownVertex {}
+
+/// The error returned when an algorithm for acyclic networks finds a cycle
+///
+/// It holds a paper on the cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleError(pub usize);
+
+impl Display for CycleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the network has a cycle through vertex {}", self.0)
+    }
+}
+
+impl Error for CycleError {}
+
+/// The error returned when groups of papers cannot be contracted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractError {
+    /// A paper of the groups is not in the network
+    UnknownVertex(usize),
+    /// A paper appears more than once in the groups
+    RepeatedVertex(usize),
+}
+
+impl Display for ContractError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContractError::UnknownVertex(vertex) => write!(f, "{}", UnknownVertex(*vertex)),
+            ContractError::RepeatedVertex(vertex) => {
+                write!(f, "vertex {} appears in the groups more than once", vertex)
+            }
+        }
+    }
+}
+
+impl Error for ContractError {}
+
+/// The error returned when a network cannot be loaded
+#[derive(Debug)]
+pub enum LoadError {
+    /// Reading the input failed
+    Io(io::Error),
+    /// The input is malformed
+    Parse {
+        /// The 1-based line number of the malformed input
+        line: usize,
+        /// What is wrong with the input
+        message: String,
+    },
+}
+
+impl LoadError {
+    /// Creates an error for malformed input
+    pub fn parse(line: usize, message: impl Into<String>) -> LoadError {
+        LoadError::Parse {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Io(error) => write!(f, "failed to read the network: {}", error),
+            LoadError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::Io(error) => Some(error),
+            LoadError::Parse { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for LoadError {
+    fn from(error: io::Error) -> Self {
+        LoadError::Io(error)
+    }
+}
 
 /// A network of citations
-pub(crate) struct CitationNetwork {
+#[derive(Clone)]
+pub struct CitationNetwork {
     /// The in-edges of each node
     in_edges: HashMap<usize, Vec<usize>>,
     /// The out-edges of each node
@@ -13,7 +172,7 @@ pub(crate) struct CitationNetwork {
 
 impl CitationNetwork {
     /// Creates a new empty network
-    pub(crate) fn new() -> CitationNetwork {
+    pub fn new() -> CitationNetwork {
         CitationNetwork {
             in_edges: HashMap::new(),
             out_edges: HashMap::new(),
@@ -28,35 +187,231 @@ impl CitationNetwork {
     /// * `from` - The id of the source paper
     /// * `to` - The id of the cited paper
     ///
-    pub(crate) fn add_edge(&mut self, from: usize, to: usize) {
-        self.in_edges.entry(to).or_insert_with(Vec::new).push(from);
-        self.in_edges.entry(from).or_insert_with(Vec::new);
-        self.out_edges.entry(from).or_insert_with(Vec::new).push(to);
-        self.out_edges.entry(to).or_insert_with(Vec::new);
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        self.in_edges.entry(to).or_default().push(from);
+        self.in_edges.entry(from).or_default();
+        self.out_edges.entry(from).or_default().push(to);
+        self.out_edges.entry(to).or_default();
+    }
+    /// Adds a node without any edges to the network
+    ///
+    /// Adding a node that is already in the network has no effect.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex` - The id of the paper
+    ///
+    pub fn add_node(&mut self, vertex: usize) {
+        self.in_edges.entry(vertex).or_default();
+        self.out_edges.entry(vertex).or_default();
+    }
+    /// Removes a node and all of its edges from the network
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex` - The id of the paper
+    ///
+    /// # Returns
+    ///
+    /// * `removed` - Whether the node was in the network
+    pub fn remove_node(&mut self, vertex: usize) -> bool {
+        let (Some(citers), Some(references)) = (
+            self.in_edges.remove(&vertex),
+            self.out_edges.remove(&vertex),
+        ) else {
+            return false;
+        };
+        for citer in citers {
+            if let Some(edges) = self.out_edges.get_mut(&citer) {
+                edges.retain(|&to| to != vertex);
+            }
+        }
+        for reference in references {
+            if let Some(edges) = self.in_edges.get_mut(&reference) {
+                edges.retain(|&from| from != vertex);
+            }
+        }
+        true
+    }
+    /// Removes every citation from one paper to another
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The id of the source paper
+    /// * `to` - The id of the cited paper
+    ///
+    /// # Returns
+    ///
+    /// * `removed` - Whether the network contained the edge
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> bool {
+        let Some(references) = self.out_edges.get_mut(&from) else {
+            return false;
+        };
+        let count = references.len();
+        references.retain(|&reference| reference != to);
+        if references.len() == count {
+            return false;
+        }
+        if let Some(citers) = self.in_edges.get_mut(&to) {
+            citers.retain(|&citer| citer != from);
+        }
+        true
+    }
+    /// Removes every node without any edges from the network
+    ///
+    /// # Returns
+    ///
+    /// * `removed` - The number of nodes removed
+    pub fn remove_isolates(&mut self) -> usize {
+        let isolates: Vec<usize> = self
+            .nodes()
+            .copied()
+            .filter(|&v| self.in_edges[&v].is_empty() && self.out_edges[&v].is_empty())
+            .collect();
+        for &vertex in &isolates {
+            self.remove_node(vertex);
+        }
+        isolates.len()
     }
     /// Returns the number of nodes in the network
-    pub(crate) fn size(&self) -> usize {
-        return self.out_edges.len();
+    pub fn size(&self) -> usize {
+        self.out_edges.len()
     }
     /// Returns the number of edges in the network
-    pub(crate) fn num_edges(&self) -> usize {
-        return self.out_edges.values().map(|x| x.len()).sum();
+    pub fn num_edges(&self) -> usize {
+        self.out_edges.values().map(|x| x.len()).sum()
+    }
+    /// Returns whether a node is in the network
+    pub fn contains_node(&self, vertex: usize) -> bool {
+        self.out_edges.contains_key(&vertex)
+    }
+    /// Returns whether a node cites another at least once
+    ///
+    /// Nodes not in the network have no citations, so this is false for them.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The id of the citing paper
+    /// * `to` - The id of the cited paper
+    pub fn contains_edge(&self, from: usize, to: usize) -> bool {
+        self.out_edges_from(from).any(|&reference| reference == to)
+    }
+    /// Returns whether a node is in the network
+    ///
+    /// This is [`CitationNetwork::contains_node`] under the name it shares with
+    /// [`CitationNetwork::edge_exists`].
+    pub fn node_exists(&self, vertex: usize) -> bool {
+        self.contains_node(vertex)
+    }
+    /// Returns whether a node cites another at least once
+    ///
+    /// This is [`CitationNetwork::contains_edge`] under the name it shares with
+    /// [`CitationNetwork::node_exists`].
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The id of the citing paper
+    /// * `to` - The id of the cited paper
+    pub fn edge_exists(&self, from: usize, to: usize) -> bool {
+        self.contains_edge(from, to)
     }
-    /// Returns the nodes in the network
-    pub(crate) fn nodes(&self) -> impl Iterator<Item = &usize> {
+    /// Returns the nodes in the network in an arbitrary order
+    pub fn nodes(&self) -> impl Iterator<Item = &usize> {
         self.out_edges.keys()
     }
+    /// Returns the nodes in the network sorted in ascending order of id
+    ///
+    /// [`CitationNetwork::nodes`] visits the nodes in an arbitrary order that may
+    /// change between runs; use this wherever the order affects the output.
+    pub fn nodes_sorted(&self) -> Vec<usize> {
+        let mut vertices: Vec<usize> = self.nodes().copied().collect();
+        vertices.sort_unstable();
+        vertices
+    }
     /// Returns an iterator over the in-edges in the network
-    pub(crate) fn in_edges(&self) -> impl Iterator<Item = (&usize, &Vec<usize>)> {
+    #[deprecated(note = "use `edges_iter` or `in_degree` instead")]
+    pub fn in_edges(&self) -> impl Iterator<Item = (&usize, &Vec<usize>)> {
         self.in_edges.iter()
     }
+    /// Returns an iterator over the edges in the network as (citing, cited) pairs
+    ///
+    /// The edges are ordered by citing paper id and then by cited paper id, so the
+    /// order does not depend on how the network was built. Repeated citations are
+    /// yielded once per occurrence.
+    pub fn edges_iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.nodes_sorted().into_iter().flat_map(move |from| {
+            let mut references = self.out_edges[&from].clone();
+            references.sort_unstable();
+            references.into_iter().map(move |to| (from, to))
+        })
+    }
+    /// Returns the number of times a node is cited, or 0 if it is not in the network
+    pub fn in_degree(&self, vertex: usize) -> usize {
+        self.in_edges.get(&vertex).map_or(0, Vec::len)
+    }
+    /// Returns the number of times a node is cited, or `None` if it is not in the network
+    ///
+    /// Unlike [`CitationNetwork::in_degree`], this tells a missing node apart from
+    /// one that is never cited.
+    pub fn try_in_degree(&self, vertex: usize) -> Option<usize> {
+        self.in_edges.get(&vertex).map(Vec::len)
+    }
+    /// Returns the number of citations a node makes, or 0 if it is not in the network
+    pub fn out_degree(&self, vertex: usize) -> usize {
+        self.out_edges.get(&vertex).map_or(0, Vec::len)
+    }
     /// Returns an iterator over the in-edges to a node in the network
-    pub(crate) fn in_edges_to(&self, vertex: usize) -> impl Iterator<Item = &usize> {
-        self.in_edges.get(&vertex).unwrap().iter()
+    ///
+    /// The iterator is empty if the node is not in the network.
+    pub fn in_edges_to(&self, vertex: usize) -> impl Iterator<Item = &usize> {
+        self.in_edges.get(&vertex).into_iter().flatten()
     }
-    /// Returns an iterator over the out-edges in the network
-    pub(crate) fn out_edges_from(&self, vertex: usize) -> impl Iterator<Item = &usize> {
-        self.out_edges.get(&vertex).unwrap().iter()
+    /// Returns an iterator over the out-edges from a node in the network
+    ///
+    /// The iterator is empty if the node is not in the network.
+    pub fn out_edges_from(&self, vertex: usize) -> impl Iterator<Item = &usize> {
+        self.out_edges.get(&vertex).into_iter().flatten()
+    }
+    /// Returns an iterator over the papers a node cites, or `None` if it is not in
+    /// the network
+    ///
+    /// Unlike [`CitationNetwork::out_edges_from`], this tells a missing node apart
+    /// from one that cites nothing. Repeated citations are yielded once per
+    /// occurrence.
+    pub fn edges(&self, vertex: usize) -> Option<impl Iterator<Item = &usize>> {
+        self.out_edges
+            .get(&vertex)
+            .map(|references| references.iter())
+    }
+    /// Returns the neighbors of a node when citations are treated as undirected
+    ///
+    /// The neighbors are the papers the node cites together with the papers citing it,
+    /// sorted by id and without duplicates or the node itself. A node not in the
+    /// network has no neighbors.
+    pub fn neighbors(&self, vertex: usize) -> Vec<usize> {
+        let mut neighbors: Vec<usize> = self
+            .in_edges_to(vertex)
+            .chain(self.out_edges_from(vertex))
+            .copied()
+            .filter(|&neighbor| neighbor != vertex)
+            .collect();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        neighbors
+    }
+    /// Returns the nodes adjacent to a node in the given direction
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex` - The id of the paper
+    /// * `direction` - Which edges of the node to follow
+    ///
+    pub fn neighbors_in(&self, vertex: usize, direction: Direction) -> Vec<usize> {
+        match direction {
+            Direction::Outgoing => self.out_edges_from(vertex).copied().collect(),
+            Direction::Incoming => self.in_edges_to(vertex).copied().collect(),
+            Direction::Undirected => self.neighbors(vertex),
+        }
     }
     /// Loads a network from a file
     ///
@@ -64,18 +419,93 @@ impl CitationNetwork {
     ///
     /// * `file` - The file to load from
     ///
-    pub(crate) fn load_from_file(file: File) -> CitationNetwork {
-        let reader = BufReader::new(file);
-        let mut graph = CitationNetwork::new();
-        for line in reader.lines().skip(4) {
-            let line = line.unwrap();
-            let entries: Vec<usize> = line
-                .split_whitespace()
-                .map(|x| x.parse::<usize>().unwrap())
-                .collect();
-            graph.add_edge(entries[0], entries[1]);
-        }
-        graph
+    pub fn load_from_file(file: File) -> CitationNetwork {
+        CitationNetwork::load_from_reader(file)
+    }
+    /// Loads a network from an edge list in the SNAP format
+    ///
+    /// Every line holds the ids of a citing and a cited paper separated by
+    /// whitespace. Empty lines and comment lines starting with `#` or `%`, such as
+    /// the SNAP header, are skipped wherever they are, so files with any number of
+    /// header lines or none load every edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to load from
+    ///
+    /// # Panics
+    ///
+    /// Panics if reading fails or a line is malformed; use
+    /// [`CitationNetwork::stream_from`] to handle these errors.
+    pub fn load_from_reader<R: Read>(reader: R) -> CitationNetwork {
+        CitationNetwork::load_from_reader_with(reader, CitationNetworkBuilder::new()).0
+    }
+    /// Loads a network from an edge list in the SNAP format, with the counts its
+    /// header declares
+    ///
+    /// See [`CitationNetwork::load_from_reader`]. Compare the counts with the network
+    /// using [`DeclaredCounts::verify_counts`] to detect a truncated or corrupted
+    /// file.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to load from
+    ///
+    /// # Returns
+    ///
+    /// * `(network, declared)` - The network and the counts of the
+    ///   `# Nodes: N Edges: M` comment, or `None` if the file has no such comment
+    pub fn load_from_reader_declared<R: Read>(
+        reader: R,
+    ) -> (CitationNetwork, Option<DeclaredCounts>) {
+        let (network, _, declared) = load_edges(reader, CitationNetworkBuilder::new());
+        (network, declared)
+    }
+    /// Loads a network from an edge list in the SNAP format through a builder
+    ///
+    /// See [`CitationNetwork::load_from_reader`]; the options of the builder decide
+    /// which edges are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to load from
+    /// * `builder` - The builder to add the edges to
+    pub fn load_from_reader_with<R: Read>(
+        reader: R,
+        builder: CitationNetworkBuilder,
+    ) -> (CitationNetwork, BuildStats) {
+        let (network, stats, _) = load_edges(reader, builder);
+        (network, stats)
+    }
+}
+
+/// Adds the edges of an edge list to a builder
+///
+/// # Returns
+///
+/// * `(network, stats, declared)` - The built network, what happened to its edges
+///   and the counts declared by the header
+///
+/// # Panics
+///
+/// Panics if reading fails or a line is malformed.
+fn load_edges<R: Read>(
+    reader: R,
+    mut builder: CitationNetworkBuilder,
+) -> (CitationNetwork, BuildStats, Option<DeclaredCounts>) {
+    let mut edges = EdgeStream::new(reader);
+    for edge in edges.by_ref() {
+        let (from, to) = edge.unwrap_or_else(|error| panic!("{}", error));
+        builder.add_edge(from, to);
+    }
+    let declared = edges.declared_counts();
+    let (network, stats) = builder.build();
+    (network, stats, declared)
+}
+
+impl Default for CitationNetwork {
+    fn default() -> Self {
+        CitationNetwork::new()
     }
 }
 
@@ -100,7 +530,7 @@ mod tests {
         graph.add_edge(2, 3);
         assert_eq!(graph.size(), 4);
         assert!(graph.in_edges.contains_key(&0));
-        assert_eq!(graph.in_edges[&0], vec![]);
+        assert_eq!(graph.in_edges[&0], Vec::<usize>::new());
         assert!(graph.in_edges.contains_key(&1));
         assert_eq!(graph.in_edges[&1], vec![0]);
         assert!(graph.in_edges.contains_key(&2));
@@ -116,11 +546,217 @@ mod tests {
         assert_eq!(graph.num_edges(), 6);
     }
 
+    #[test]
+    fn test_edges_iter() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(2, 3);
+        graph.add_edge(0, 2);
+        graph.add_edge(1, 3);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(0, 3);
+        let edges: Vec<_> = graph.edges_iter().collect();
+        assert_eq!(edges, vec![(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+        assert_eq!(edges.len(), graph.num_edges());
+    }
+
+    #[test]
+    fn test_degrees() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(1, 2);
+        assert_eq!(graph.in_degree(2), 2);
+        assert_eq!(graph.out_degree(0), 2);
+        assert_eq!(graph.in_degree(0), 0);
+        assert_eq!(graph.in_degree(42), 0);
+        assert_eq!(graph.out_degree(42), 0);
+    }
+
+    #[test]
+    fn test_missing_vertex_accessors() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        assert!(!graph.contains_node(42));
+        assert_eq!(graph.in_edges_to(42).count(), 0);
+        assert_eq!(graph.out_edges_from(42).count(), 0);
+        assert!(graph.neighbors(42).is_empty());
+        for direction in [
+            Direction::Outgoing,
+            Direction::Incoming,
+            Direction::Undirected,
+        ] {
+            assert!(graph.neighbors_in(42, direction).is_empty());
+        }
+        assert_eq!(graph.in_degree(42), 0);
+        assert_eq!(graph.out_degree(42), 0);
+        assert!(graph.bfs_distances(42).is_empty());
+        assert_eq!(graph.eccentricity(42), None);
+        assert_eq!(graph.jaccard_similarity(0, 42), 0.0);
+        assert!(graph.top_similar_neighbors(42, 3).is_empty());
+        assert_eq!(graph.local_clustering_coefficient(42), 0.0);
+        assert_eq!(graph.complement_degree(42), 0);
+        assert!(!graph.is_articulation_point(42));
+        assert!(!graph.contains_edge(42, 0));
+        assert!(!graph.contains_edge(0, 42));
+        assert!(!graph.node_exists(42));
+        assert!(!graph.edge_exists(42, 0));
+        assert!(!graph.edge_exists(0, 42));
+        assert_eq!(graph.try_in_degree(42), None);
+        assert!(graph.edges(42).is_none());
+        assert_eq!(graph.size(), 2);
+    }
+
+    #[test]
+    fn test_contains_edge() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 1);
+        graph.add_edge(2, 2);
+        assert!(graph.contains_edge(0, 1));
+        assert!(!graph.contains_edge(1, 0));
+        assert!(graph.contains_edge(2, 2));
+        assert!(!graph.contains_edge(0, 2));
+        graph.remove_edge(0, 1);
+        assert!(!graph.contains_edge(0, 1));
+    }
+
+    #[test]
+    fn test_exists_and_option_accessors() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 1);
+        graph.add_node(2);
+        assert!(graph.node_exists(0) && graph.node_exists(2));
+        assert!(graph.edge_exists(0, 1));
+        assert!(!graph.edge_exists(1, 0));
+        assert!(!graph.edge_exists(0, 2));
+        assert_eq!(graph.try_in_degree(1), Some(2));
+        // A node without citations is told apart from a missing one
+        assert_eq!(graph.try_in_degree(2), Some(0));
+        assert_eq!(graph.edges(0).unwrap().collect::<Vec<_>>(), vec![&1, &1]);
+        assert_eq!(graph.edges(2).unwrap().count(), 0);
+        graph.remove_node(2);
+        assert!(!graph.node_exists(2));
+        assert_eq!(graph.try_in_degree(2), None);
+    }
+
+    #[test]
+    fn test_remove_edge() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        assert!(graph.remove_edge(0, 1));
+        assert!(!graph.remove_edge(0, 1));
+        assert!(!graph.remove_edge(2, 1));
+        assert!(!graph.remove_edge(7, 1));
+        assert_eq!(graph.size(), 3);
+        assert_eq!(graph.edges_iter().collect::<Vec<_>>(), vec![(1, 2)]);
+        assert_eq!(graph.in_degree(1), 0);
+    }
+
+    #[test]
+    fn test_remove_node() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 1);
+        graph.add_edge(0, 2);
+        assert!(graph.remove_node(1));
+        assert!(!graph.remove_node(1));
+        assert_eq!(graph.size(), 2);
+        assert_eq!(graph.num_edges(), 1);
+        assert_eq!(graph.in_edges[&2], vec![0]);
+        assert_eq!(graph.out_edges[&2], Vec::<usize>::new());
+    }
+
     #[test]
     fn test_load_network_from_file() {
         let file = File::open("data/cit-HepTh.txt").unwrap();
         let network = CitationNetwork::load_from_file(file);
         assert_eq!(network.size(), 27770);
         assert_eq!(network.num_edges(), 352807);
+        let file = File::open("data/cit-HepTh.txt").unwrap();
+        let (network, declared) = CitationNetwork::load_from_reader_declared(file);
+        assert_eq!(declared.unwrap().verify_counts(&network), Ok(()));
+    }
+
+    #[test]
+    fn test_load_without_header() {
+        let (network, declared) =
+            CitationNetwork::load_from_reader_declared("1 2\n2 3\n3 4\n4 5\n5 1\n".as_bytes());
+        assert_eq!(network.num_edges(), 5);
+        assert!(network.contains_edge(1, 2));
+        assert_eq!(declared, None);
+    }
+
+    #[test]
+    fn test_load_with_long_header() {
+        let input = "# Directed graph (each unordered pair of nodes is saved once)\n\
+                     # Citation network\n\
+                     % exported with an extra comment\n\
+                     # Nodes: 4 Edges: 3\n\
+                     #\n\
+                     # FromNodeId\tToNodeId\n\
+                     1\t2\n\
+                     \n\
+                     2\t3\n\
+                     # a comment between edges\n\
+                     3\t4\n";
+        let (network, declared) = CitationNetwork::load_from_reader_declared(input.as_bytes());
+        assert_eq!(network.num_edges(), 3);
+        let declared = declared.unwrap();
+        assert_eq!(declared, DeclaredCounts { nodes: 4, edges: 3 });
+        assert!(declared.verify_counts(&network).is_ok());
+        assert_eq!(
+            CitationNetwork::load_from_reader(input.as_bytes()).num_edges(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_declared_counts_disagree() {
+        let input = "# Nodes: 5 Edges: 6\n1 2\n2 3\n3 4\n";
+        let (network, declared) = CitationNetwork::load_from_reader_declared(input.as_bytes());
+        let mismatch = declared.unwrap().verify_counts(&network).unwrap_err();
+        assert_eq!(mismatch.actual, DeclaredCounts { nodes: 4, edges: 3 });
+        assert_eq!(
+            mismatch.to_string(),
+            "the header declares 5 nodes and 6 edges but the network has 4 nodes and 3 edges"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: invalid paper id: x")]
+    fn test_load_malformed_line() {
+        CitationNetwork::load_from_reader("1 2\n2 x\n".as_bytes());
+    }
+
+    #[test]
+    fn test_load_through_builder() {
+        let input =
+            "# Directed graph\n# Citation network\n# Nodes: 3 Edges: 5\n# FromNodeId\tToNodeId\n\
+                     1\t2\n1\t2\n2\t3\n3\t3\n3\t1\n";
+        let network = CitationNetwork::load_from_reader(input.as_bytes());
+        let collected: CitationNetwork = [(1, 2), (1, 2), (2, 3), (3, 3), (3, 1)]
+            .into_iter()
+            .collect();
+        assert!(network.is_isomorphic_as_labeled(&collected));
+        assert_eq!(network.num_edges(), 5);
+        let builder = CitationNetworkBuilder::new()
+            .dedup(true)
+            .drop_self_loops(true);
+        let (cleaned, stats) = CitationNetwork::load_from_reader_with(input.as_bytes(), builder);
+        assert_eq!(cleaned.size(), 3);
+        assert_eq!(cleaned.num_edges(), 3);
+        assert_eq!(
+            stats,
+            BuildStats {
+                edges_added: 3,
+                duplicates_skipped: 1,
+                loops_dropped: 1
+            }
+        );
     }
 }
@@ -0,0 +1,51 @@
+use crate::network::CitationNetwork;
+
+impl CitationNetwork {
+    /// Returns the degree assortativity of the network
+    ///
+    /// This is the Pearson correlation, over all citations, between the out-degree of
+    /// the citing paper and the in-degree of the cited paper. It is positive when
+    /// papers with many references tend to cite highly cited papers. It is 0 if there
+    /// are no citations or either degree is the same for every citation, where the
+    /// correlation is undefined.
+    pub fn degree_assortativity(&self) -> f64 {
+        let pairs: Vec<(f64, f64)> = self
+            .edges_iter()
+            .map(|(from, to)| (self.out_degree(from) as f64, self.in_degree(to) as f64))
+            .collect();
+        let n = pairs.len() as f64;
+        let mean_x = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_y = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+        let mut covariance = 0.0;
+        let mut variance_x = 0.0;
+        let mut variance_y = 0.0;
+        for &(x, y) in &pairs {
+            covariance += (x - mean_x) * (y - mean_y);
+            variance_x += (x - mean_x).powi(2);
+            variance_y += (y - mean_y).powi(2);
+        }
+        if variance_x > 0.0 && variance_y > 0.0 {
+            covariance / (variance_x * variance_y).sqrt()
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_degree_assortativity() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(3, 1);
+        // The degree pairs are (2, 2), (2, 1) and (1, 2)
+        assert!((graph.degree_assortativity() + 0.5).abs() < 1e-12);
+        graph.remove_edge(3, 1);
+        assert_eq!(graph.degree_assortativity(), 0.0);
+        assert_eq!(CitationNetwork::new().degree_assortativity(), 0.0);
+    }
+}
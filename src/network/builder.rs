@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+
+use crate::network::CitationNetwork;
+
+/// Counts of what happened to the edges given to a [`CitationNetworkBuilder`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BuildStats {
+    /// The edges added to the network
+    pub edges_added: usize,
+    /// The edges skipped because they were already in the network
+    pub duplicates_skipped: usize,
+    /// The self-citations dropped
+    pub loops_dropped: usize,
+}
+
+/// Builds a network edge by edge, optionally cleaning the edges up on the way
+///
+/// By default every edge is kept, as with [`CitationNetwork::add_edge`]. The loaders
+/// build their networks through a builder, so they handle these options the same
+/// way.
+#[derive(Default)]
+pub struct CitationNetworkBuilder {
+    dedup: bool,
+    drop_self_loops: bool,
+    network: CitationNetwork,
+    /// The edges added so far, tracked only when deduplicating
+    seen: HashSet<(usize, usize)>,
+    stats: BuildStats,
+}
+
+impl CitationNetworkBuilder {
+    /// Creates a builder that keeps every edge
+    pub fn new() -> CitationNetworkBuilder {
+        CitationNetworkBuilder::default()
+    }
+    /// Sets whether an edge that is already in the network is skipped
+    pub fn dedup(mut self, dedup: bool) -> CitationNetworkBuilder {
+        self.dedup = dedup;
+        self
+    }
+    /// Sets whether self-citations are dropped
+    pub fn drop_self_loops(mut self, drop_self_loops: bool) -> CitationNetworkBuilder {
+        self.drop_self_loops = drop_self_loops;
+        self
+    }
+    /// Reserves space for the expected number of nodes
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - The number of nodes the network is expected to have
+    pub fn capacity(mut self, nodes: usize) -> CitationNetworkBuilder {
+        self.network.in_edges.reserve(nodes);
+        self.network.out_edges.reserve(nodes);
+        self
+    }
+    /// Adds an edge to the network, unless the options say to leave it out
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The id of the source paper
+    /// * `to` - The id of the cited paper
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        if self.drop_self_loops && from == to {
+            // The paper is still in the network, like the endpoints of skipped duplicates
+            self.network.add_node(from);
+            self.stats.loops_dropped += 1;
+        } else if self.dedup && !self.seen.insert((from, to)) {
+            self.stats.duplicates_skipped += 1;
+        } else {
+            self.network.add_edge(from, to);
+            self.stats.edges_added += 1;
+        }
+    }
+    /// Adds a node without any edges to the network
+    pub fn add_node(&mut self, vertex: usize) {
+        self.network.add_node(vertex);
+    }
+    /// Returns the network and what happened to its edges
+    pub fn build(self) -> (CitationNetwork, BuildStats) {
+        (self.network, self.stats)
+    }
+}
+
+impl Extend<(usize, usize)> for CitationNetworkBuilder {
+    fn extend<I: IntoIterator<Item = (usize, usize)>>(&mut self, edges: I) {
+        for (from, to) in edges {
+            self.add_edge(from, to);
+        }
+    }
+}
+
+impl FromIterator<(usize, usize)> for CitationNetwork {
+    /// Builds a network from its edges, keeping every edge
+    fn from_iter<I: IntoIterator<Item = (usize, usize)>>(edges: I) -> CitationNetwork {
+        let mut network = CitationNetwork::new();
+        network.extend(edges);
+        network
+    }
+}
+
+impl Extend<(usize, usize)> for CitationNetwork {
+    fn extend<I: IntoIterator<Item = (usize, usize)>>(&mut self, edges: I) {
+        for (from, to) in edges {
+            self.add_edge(from, to);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EDGES: [(usize, usize); 6] = [(0, 1), (1, 2), (0, 1), (2, 2), (2, 0), (2, 2)];
+
+    fn build(dedup: bool, drop_self_loops: bool) -> (CitationNetwork, BuildStats) {
+        let mut builder = CitationNetworkBuilder::new()
+            .dedup(dedup)
+            .drop_self_loops(drop_self_loops)
+            .capacity(3);
+        builder.extend(EDGES);
+        builder.add_node(7);
+        builder.build()
+    }
+
+    #[test]
+    fn test_collect_into_network() {
+        let network: CitationNetwork = EDGES.into_iter().collect();
+        assert_eq!(network.size(), 3);
+        assert_eq!(network.num_edges(), 6);
+        let mut extended: CitationNetwork = EDGES[..2].iter().copied().collect();
+        extended.extend(EDGES[2..].iter().copied());
+        assert!(extended.is_isomorphic_as_labeled(&network));
+        assert_eq!(extended.num_edges(), 6);
+    }
+
+    #[test]
+    fn test_build_stats() {
+        let counts = |(network, stats): (CitationNetwork, BuildStats)| {
+            assert_eq!(network.size(), 4);
+            assert_eq!(network.num_edges(), stats.edges_added);
+            (
+                stats.edges_added,
+                stats.duplicates_skipped,
+                stats.loops_dropped,
+            )
+        };
+        assert_eq!(counts(build(false, false)), (6, 0, 0));
+        assert_eq!(counts(build(true, false)), (4, 2, 0));
+        assert_eq!(counts(build(false, true)), (4, 0, 2));
+        assert_eq!(counts(build(true, true)), (3, 1, 2));
+    }
+}
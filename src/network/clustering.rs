@@ -0,0 +1,146 @@
+use std::collections::HashSet;
+
+use crate::network::CitationNetwork;
+
+impl CitationNetwork {
+    /// Returns the local clustering coefficient of a node
+    ///
+    /// Citations are treated as undirected. The coefficient is the fraction of pairs
+    /// of the node's neighbors that are themselves linked, and is 0 for nodes with
+    /// fewer than two neighbors or not in the network.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex` - The id of the paper
+    pub fn local_clustering_coefficient(&self, vertex: usize) -> f64 {
+        if !self.contains_node(vertex) {
+            return 0.0;
+        }
+        let neighbors = self.neighbors(vertex);
+        let k = neighbors.len();
+        if k < 2 {
+            return 0.0;
+        }
+        2.0 * self.local_triangle_count(vertex) as f64 / (k * (k - 1)) as f64
+    }
+    /// Returns the number of triangles a node is part of
+    ///
+    /// Citations are treated as undirected, so a triangle is a pair of linked
+    /// neighbors of the node, whichever way the three citations go. Nodes not in the
+    /// network are in no triangle.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex` - The id of the paper
+    pub fn local_triangle_count(&self, vertex: usize) -> usize {
+        let neighbors = self.neighbors(vertex);
+        let neighbor_set: HashSet<usize> = neighbors.iter().copied().collect();
+        let links: usize = neighbors
+            .iter()
+            .map(|&neighbor| {
+                self.neighbors(neighbor)
+                    .into_iter()
+                    .filter(|other| neighbor_set.contains(other))
+                    .count()
+            })
+            .sum();
+        // Every link between two neighbors was counted from both ends
+        links / 2
+    }
+    /// Returns the number of triangles in the network
+    ///
+    /// Citations are treated as undirected, and each triangle is counted once. This
+    /// is the node-iterator algorithm: every triangle is found from its node with the
+    /// smallest id, by checking which pairs of its larger neighbors are linked.
+    pub fn triangle_count(&self) -> usize {
+        let mut triangles = 0;
+        for vertex in self.nodes_sorted() {
+            let larger: Vec<usize> = self
+                .neighbors(vertex)
+                .into_iter()
+                .filter(|&neighbor| neighbor > vertex)
+                .collect();
+            for (i, &a) in larger.iter().enumerate() {
+                let linked: HashSet<usize> = self.neighbors(a).into_iter().collect();
+                triangles += larger[i + 1..]
+                    .iter()
+                    .filter(|b| linked.contains(b))
+                    .count();
+            }
+        }
+        triangles
+    }
+    /// Returns the mean of the local clustering coefficients of all nodes
+    ///
+    /// The coefficient is 0 for an empty network.
+    pub fn average_clustering_coefficient(&self) -> f64 {
+        if self.size() == 0 {
+            return 0.0;
+        }
+        // Summed in id order so that the result does not depend on hashing
+        let total: f64 = self
+            .nodes_sorted()
+            .into_iter()
+            .map(|vertex| self.local_clustering_coefficient(vertex))
+            .sum();
+        total / self.size() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_graphs::{complete_dag, path};
+
+    #[test]
+    fn test_local_clustering_coefficient() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(0, 3);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 1);
+        graph.add_edge(4, 0);
+        // Of the six pairs of neighbors of 0, only 1 and 2 are linked
+        assert!((graph.local_clustering_coefficient(0) - 1.0 / 6.0).abs() < 1e-12);
+        assert_eq!(graph.local_clustering_coefficient(1), 1.0);
+        assert_eq!(graph.local_clustering_coefficient(3), 0.0);
+        assert_eq!(graph.local_clustering_coefficient(42), 0.0);
+        // 1 and 2 have coefficient 1 and the others 1/6 or 0
+        let average = (1.0 / 6.0 + 2.0) / 5.0;
+        assert!((graph.average_clustering_coefficient() - average).abs() < 1e-12);
+        assert_eq!(CitationNetwork::new().average_clustering_coefficient(), 0.0);
+    }
+
+    #[test]
+    fn test_triangle_count() {
+        assert_eq!(complete_dag(3).triangle_count(), 1);
+        assert_eq!(complete_dag(4).triangle_count(), 4);
+        assert_eq!(complete_dag(6).triangle_count(), 20);
+        assert_eq!(path(6).triangle_count(), 0);
+        assert_eq!(CitationNetwork::new().triangle_count(), 0);
+        // Mutual and repeated citations do not make extra triangles
+        let mut graph = complete_dag(3);
+        graph.add_edge(1, 0);
+        graph.add_edge(0, 2);
+        graph.add_edge(2, 2);
+        assert_eq!(graph.triangle_count(), 1);
+    }
+
+    #[test]
+    fn test_local_triangle_count() {
+        let k4 = complete_dag(4);
+        for vertex in 0..4 {
+            assert_eq!(k4.local_triangle_count(vertex), 3);
+        }
+        assert_eq!(k4.local_triangle_count(42), 0);
+        let graph = crate::generators::erdos_renyi(60, 0.1, 2);
+        let total: usize = graph
+            .nodes_sorted()
+            .into_iter()
+            .map(|vertex| graph.local_triangle_count(vertex))
+            .sum();
+        assert!(graph.triangle_count() > 0);
+        assert_eq!(total, 3 * graph.triangle_count());
+    }
+}
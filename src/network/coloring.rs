@@ -0,0 +1,181 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::network::CitationNetwork;
+
+impl CitationNetwork {
+    /// Returns the sides of the network if it is bipartite
+    ///
+    /// Citations are treated as undirected, and each connected component is
+    /// 2-colored by a breadth-first search from its smallest node, which is placed
+    /// on the left side. A paper citing itself makes the network non-bipartite.
+    ///
+    /// # Returns
+    ///
+    /// * `Some((left, right))` - The two sides, each sorted by id, if the network is bipartite
+    /// * `None` - If some cycle has odd length
+    pub fn is_bipartite(&self) -> Option<(Vec<usize>, Vec<usize>)> {
+        let mut sides: HashMap<usize, bool> = HashMap::new();
+        let roots = self.nodes_sorted();
+        for root in roots {
+            if sides.contains_key(&root) {
+                continue;
+            }
+            sides.insert(root, false);
+            let mut queue = VecDeque::from([root]);
+            while let Some(vertex) = queue.pop_front() {
+                if self.contains_edge(vertex, vertex) {
+                    return None;
+                }
+                let side = sides[&vertex];
+                for neighbor in self.neighbors(vertex) {
+                    match sides.get(&neighbor) {
+                        Some(&other) if other == side => return None,
+                        Some(_) => {}
+                        None => {
+                            sides.insert(neighbor, !side);
+                            queue.push_back(neighbor);
+                        }
+                    }
+                }
+            }
+        }
+        let (mut left, mut right): (Vec<usize>, Vec<usize>) =
+            sides.keys().partition(|vertex| !sides[vertex]);
+        left.sort_unstable();
+        right.sort_unstable();
+        Some((left, right))
+    }
+    /// Colors the nodes so that no two neighbors share a color
+    ///
+    /// Citations are treated as undirected and self-citations are ignored. This is
+    /// the greedy sequential algorithm: nodes are visited in order of decreasing
+    /// number of neighbors, with ties broken by id, and each gets the smallest color
+    /// not used by its neighbors colored before it. A node with `d` neighbors never
+    /// gets a color above `d`, but the coloring is not always optimal.
+    ///
+    /// # Returns
+    ///
+    /// * `coloring` - The color of every node, from `0..k` where `k` is the number of colors
+    pub fn greedy_coloring(&self) -> HashMap<usize, usize> {
+        let mut order: Vec<(usize, Vec<usize>)> = self
+            .nodes_sorted()
+            .into_iter()
+            .map(|vertex| (vertex, self.neighbors(vertex)))
+            .collect();
+        // The sort is stable, so nodes with as many neighbors stay in order of id
+        order.sort_by_key(|(_, neighbors)| Reverse(neighbors.len()));
+        let mut coloring = HashMap::with_capacity(order.len());
+        for (vertex, neighbors) in order {
+            let used: HashSet<usize> = neighbors
+                .iter()
+                .filter_map(|neighbor| coloring.get(neighbor).copied())
+                .collect();
+            let color = (0..).find(|color| !used.contains(color)).unwrap();
+            coloring.insert(vertex, color);
+        }
+        coloring
+    }
+    /// Returns the number of colors used by [`CitationNetwork::greedy_coloring`]
+    ///
+    /// This is an upper bound on the chromatic number of the network, the smallest
+    /// number of colors needed. It is 0 for an empty network.
+    pub fn chromatic_number_upper_bound(&self) -> usize {
+        self.greedy_coloring()
+            .values()
+            .max()
+            .map_or(0, |&color| color + 1)
+    }
+    /// Returns whether every node has a color that none of its neighbors share
+    ///
+    /// Citations are treated as undirected and self-citations are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `coloring` - The color of every node
+    pub fn is_properly_colored(&self, coloring: &HashMap<usize, usize>) -> bool {
+        self.nodes().all(|vertex| coloring.contains_key(vertex))
+            && self
+                .edges_iter()
+                .all(|(from, to)| from == to || coloring[&from] != coloring[&to])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bipartite_network() {
+        // Authors 0 and 1 writing papers 10, 11 and 12, plus a separate pair
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 10);
+        graph.add_edge(0, 11);
+        graph.add_edge(1, 11);
+        graph.add_edge(1, 12);
+        graph.add_edge(20, 21);
+        graph.add_node(30);
+        let (left, right) = graph.is_bipartite().unwrap();
+        assert_eq!(left, vec![0, 1, 20, 30]);
+        assert_eq!(right, vec![10, 11, 12, 21]);
+    }
+
+    #[test]
+    fn test_non_bipartite_network() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(0, 2);
+        graph.add_edge(5, 6);
+        assert!(graph.is_bipartite().is_none());
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 1);
+        assert!(graph.is_bipartite().is_none());
+    }
+
+    #[test]
+    fn test_even_cycle_is_bipartite() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 0);
+        assert_eq!(graph.is_bipartite(), Some((vec![0, 2], vec![1, 3])));
+    }
+
+    #[test]
+    fn test_greedy_coloring_of_bipartite_network() {
+        // Every one of 0 and 1 cites every one of 2, 3 and 4
+        let mut graph = CitationNetwork::new();
+        for from in 0..2 {
+            for to in 2..5 {
+                graph.add_edge(from, to);
+            }
+        }
+        let coloring = graph.greedy_coloring();
+        assert!(graph.is_properly_colored(&coloring));
+        assert_eq!(graph.chromatic_number_upper_bound(), 2);
+        assert_eq!(coloring[&0], coloring[&1]);
+    }
+
+    #[test]
+    fn test_greedy_coloring_of_triangle() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 0);
+        graph.add_edge(2, 2);
+        graph.add_node(3);
+        let coloring = graph.greedy_coloring();
+        assert!(graph.is_properly_colored(&coloring));
+        assert_eq!(graph.chromatic_number_upper_bound(), 3);
+        assert_eq!(coloring[&3], 0);
+        let mut improper = coloring.clone();
+        improper.insert(1, coloring[&0]);
+        assert!(!graph.is_properly_colored(&improper));
+        improper.remove(&3);
+        assert!(!graph.is_properly_colored(&improper));
+        assert_eq!(CitationNetwork::new().chromatic_number_upper_bound(), 0);
+    }
+}
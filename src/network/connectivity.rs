@@ -0,0 +1,312 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::network::CitationNetwork;
+
+/// A vertex on the depth-first search stack together with its unexplored neighbors
+struct Frame {
+    vertex: usize,
+    parent: Option<usize>,
+    neighbors: Vec<usize>,
+    next: usize,
+}
+
+impl Frame {
+    fn new(network: &CitationNetwork, vertex: usize, parent: Option<usize>) -> Frame {
+        Frame {
+            vertex,
+            parent,
+            neighbors: network.neighbors(vertex),
+            next: 0,
+        }
+    }
+}
+
+/// The articulation points and bridges found by a low-link depth-first search
+struct LowLink {
+    articulation_points: Vec<usize>,
+    bridges: Vec<(usize, usize)>,
+}
+
+impl CitationNetwork {
+    /// Runs Tarjan's low-link depth-first search over the undirected network
+    ///
+    /// The search is iterative so that long chains of citations cannot overflow the
+    /// stack, and roots are visited in ascending order of their ids.
+    fn low_link_search(&self) -> LowLink {
+        let mut discovery: HashMap<usize, usize> = HashMap::new();
+        let mut low: HashMap<usize, usize> = HashMap::new();
+        let mut points: HashSet<usize> = HashSet::new();
+        let mut bridges: Vec<(usize, usize)> = Vec::new();
+        let roots = self.nodes_sorted();
+        for root in roots {
+            if discovery.contains_key(&root) {
+                continue;
+            }
+            discovery.insert(root, discovery.len());
+            low.insert(root, discovery[&root]);
+            let mut root_children = 0;
+            let mut stack = vec![Frame::new(self, root, None)];
+            while let Some(frame) = stack.last_mut() {
+                let vertex = frame.vertex;
+                if frame.next < frame.neighbors.len() {
+                    let next = frame.neighbors[frame.next];
+                    frame.next += 1;
+                    if Some(next) == frame.parent {
+                        continue;
+                    }
+                    if let Some(&time) = discovery.get(&next) {
+                        low.insert(vertex, low[&vertex].min(time));
+                    } else {
+                        discovery.insert(next, discovery.len());
+                        low.insert(next, discovery[&next]);
+                        stack.push(Frame::new(self, next, Some(vertex)));
+                    }
+                    continue;
+                }
+                let parent = frame.parent;
+                stack.pop();
+                if let Some(parent) = parent {
+                    low.insert(parent, low[&parent].min(low[&vertex]));
+                    if low[&vertex] > discovery[&parent] {
+                        bridges.push((parent.min(vertex), parent.max(vertex)));
+                    }
+                    if parent == root {
+                        root_children += 1;
+                    } else if low[&vertex] >= discovery[&parent] {
+                        points.insert(parent);
+                    }
+                }
+            }
+            if root_children > 1 {
+                points.insert(root);
+            }
+        }
+        let mut articulation_points: Vec<usize> = points.into_iter().collect();
+        articulation_points.sort_unstable();
+        bridges.sort_unstable();
+        LowLink {
+            articulation_points,
+            bridges,
+        }
+    }
+    /// Returns the articulation points of the network
+    ///
+    /// An articulation point is a node whose removal disconnects the network when
+    /// citations are treated as undirected. In a citation network these are papers
+    /// bridging otherwise separate bodies of literature. The points are sorted by id.
+    pub fn articulation_points(&self) -> Vec<usize> {
+        self.low_link_search().articulation_points
+    }
+    /// Returns the bridges of the network
+    ///
+    /// A bridge is an edge whose removal disconnects the network when citations are
+    /// treated as undirected; removing one would split the literature in two. Each
+    /// bridge is given as `(min(u, v), max(u, v))`, and the bridges are sorted. Two
+    /// papers citing each other are joined by a single undirected edge, so a mutual
+    /// citation can still be a bridge.
+    pub fn bridges(&self) -> Vec<(usize, usize)> {
+        self.low_link_search().bridges
+    }
+    /// Returns the connected components of the network
+    ///
+    /// Citations are treated as undirected. Each component is sorted by id, and the
+    /// components are ordered by their smallest node.
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut visited: HashSet<usize> = HashSet::new();
+        let mut components = Vec::new();
+        let roots = self.nodes_sorted();
+        for root in roots {
+            if !visited.insert(root) {
+                continue;
+            }
+            let mut component = vec![root];
+            let mut queue = VecDeque::from([root]);
+            while let Some(vertex) = queue.pop_front() {
+                for neighbor in self.neighbors(vertex) {
+                    if visited.insert(neighbor) {
+                        component.push(neighbor);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+    /// Returns the strongly connected components of the network
+    ///
+    /// Each component is sorted by id, and every component comes after all the
+    /// components it cites, so the sinks of the condensation come first.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let index = self.node_index();
+        let n = index.len();
+        let adjacency: Vec<Vec<usize>> = index
+            .vertices()
+            .iter()
+            .map(|&vertex| {
+                self.out_edges_from(vertex)
+                    .map(|&to| index.index_of(to).unwrap())
+                    .collect()
+            })
+            .collect();
+        // Iterative Tarjan: `order` is the discovery time, `low` the earliest
+        // discovery time reachable through the search tree and one back edge
+        let mut order = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut time = 0;
+        for root in 0..n {
+            if order[root] != usize::MAX {
+                continue;
+            }
+            let mut calls = vec![(root, 0)];
+            order[root] = time;
+            low[root] = time;
+            time += 1;
+            stack.push(root);
+            on_stack[root] = true;
+            while let Some((vertex, next)) = calls.last_mut() {
+                let vertex = *vertex;
+                if let Some(&neighbor) = adjacency[vertex].get(*next) {
+                    *next += 1;
+                    if order[neighbor] == usize::MAX {
+                        order[neighbor] = time;
+                        low[neighbor] = time;
+                        time += 1;
+                        stack.push(neighbor);
+                        on_stack[neighbor] = true;
+                        calls.push((neighbor, 0));
+                    } else if on_stack[neighbor] {
+                        low[vertex] = low[vertex].min(order[neighbor]);
+                    }
+                    continue;
+                }
+                calls.pop();
+                if let Some(&(parent, _)) = calls.last() {
+                    low[parent] = low[parent].min(low[vertex]);
+                }
+                if low[vertex] == order[vertex] {
+                    let mut component = Vec::new();
+                    loop {
+                        let member = stack.pop().unwrap();
+                        on_stack[member] = false;
+                        component.push(index.vertex_at(member));
+                        if member == vertex {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+    /// Returns whether a node is an articulation point of the network
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex` - The id of the paper
+    pub fn is_articulation_point(&self, vertex: usize) -> bool {
+        self.contains_node(vertex) && self.articulation_points().contains(&vertex)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_articulation_points_on_path() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(3, 2);
+        assert_eq!(graph.articulation_points(), vec![1, 2]);
+        assert!(graph.is_articulation_point(1));
+        assert!(!graph.is_articulation_point(0));
+        assert!(!graph.is_articulation_point(7));
+    }
+
+    #[test]
+    fn test_articulation_points_of_two_triangles() {
+        // Two triangles sharing node 2, plus a pendant node 5 hanging off node 4
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 0);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 4);
+        graph.add_edge(4, 2);
+        graph.add_edge(5, 4);
+        graph.add_edge(6, 7);
+        assert_eq!(graph.articulation_points(), vec![2, 4]);
+    }
+
+    #[test]
+    fn test_no_articulation_points_in_cycle() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 0);
+        graph.add_edge(0, 3);
+        assert!(graph.articulation_points().is_empty());
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(3, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(5, 4);
+        graph.add_node(0);
+        assert_eq!(
+            graph.connected_components(),
+            vec![vec![0], vec![1, 2, 3], vec![4, 5]]
+        );
+    }
+
+    #[test]
+    fn test_bridges() {
+        // A triangle joined to a pair of papers by a single citation
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 0);
+        graph.add_edge(3, 2);
+        graph.add_edge(4, 3);
+        graph.add_edge(3, 4);
+        assert_eq!(graph.bridges(), vec![(2, 3), (3, 4)]);
+    }
+
+    #[test]
+    fn test_no_bridges_in_cycle() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 0);
+        assert!(graph.bridges().is_empty());
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 0);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 4);
+        graph.add_edge(4, 3);
+        graph.add_edge(5, 4);
+        graph.add_edge(6, 6);
+        assert_eq!(
+            graph.strongly_connected_components(),
+            vec![vec![3, 4], vec![0, 1, 2], vec![5], vec![6]]
+        );
+    }
+}
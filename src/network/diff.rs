@@ -0,0 +1,237 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+use crate::network::CitationNetwork;
+
+/// The differences between two versions of a citation network
+///
+/// All lists are sorted, and edges are compared as a set, so a citation repeated
+/// in one version is counted once.
+#[derive(Debug, Default, PartialEq)]
+pub struct NetworkDiff {
+    /// The nodes only in the other network
+    pub added_nodes: Vec<usize>,
+    /// The nodes only in this network
+    pub removed_nodes: Vec<usize>,
+    /// The edges only in the other network
+    pub added_edges: Vec<(usize, usize)>,
+    /// The edges only in this network
+    pub removed_edges: Vec<(usize, usize)>,
+}
+
+impl NetworkDiff {
+    /// Returns whether the two networks are identical
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+    /// Returns the diff that undoes this one
+    pub fn inverse(&self) -> NetworkDiff {
+        NetworkDiff {
+            added_nodes: self.removed_nodes.clone(),
+            removed_nodes: self.added_nodes.clone(),
+            added_edges: self.removed_edges.clone(),
+            removed_edges: self.added_edges.clone(),
+        }
+    }
+}
+
+impl Display for NetworkDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "nodes added: {}", self.added_nodes.len())?;
+        writeln!(f, "nodes removed: {}", self.removed_nodes.len())?;
+        writeln!(f, "edges added: {}", self.added_edges.len())?;
+        writeln!(f, "edges removed: {}", self.removed_edges.len())
+    }
+}
+
+impl CitationNetwork {
+    /// Returns the set of distinct edges in the network
+    fn edge_set(&self) -> HashSet<(usize, usize)> {
+        self.edges_iter().collect()
+    }
+    /// Compares the network with another version of it
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The newer version of the network
+    ///
+    /// # Returns
+    ///
+    /// * `diff` - What has to be added to and removed from this network to obtain `other`
+    pub fn diff(&self, other: &CitationNetwork) -> NetworkDiff {
+        fn sorted<T: Ord + Copy>(items: impl Iterator<Item = T>) -> Vec<T> {
+            let mut items: Vec<T> = items.collect();
+            items.sort_unstable();
+            items
+        }
+        let edges = self.edge_set();
+        let other_edges = other.edge_set();
+        NetworkDiff {
+            added_nodes: sorted(other.nodes().copied().filter(|&v| !self.contains_node(v))),
+            removed_nodes: sorted(self.nodes().copied().filter(|&v| !other.contains_node(v))),
+            added_edges: sorted(other_edges.difference(&edges).copied()),
+            removed_edges: sorted(edges.difference(&other_edges).copied()),
+        }
+    }
+    /// Applies the changes described by a diff to the network
+    ///
+    /// Nodes and edges are added before edges and nodes are removed, and an added
+    /// edge that is already in the network is not repeated. Applying
+    /// `self.diff(&other)` turns this network into one that is isomorphic as labeled
+    /// to `other`.
+    ///
+    /// # Arguments
+    ///
+    /// * `diff` - The changes to apply
+    pub fn apply_diff(&mut self, diff: &NetworkDiff) {
+        for &vertex in &diff.added_nodes {
+            self.add_node(vertex);
+        }
+        for &(from, to) in &diff.added_edges {
+            if !self.contains_edge(from, to) {
+                self.add_edge(from, to);
+            }
+        }
+        for &(from, to) in &diff.removed_edges {
+            self.remove_edge(from, to);
+        }
+        for &vertex in &diff.removed_nodes {
+            self.remove_node(vertex);
+        }
+    }
+    /// Undoes the changes described by a diff previously applied to the network
+    ///
+    /// When the diff was computed from the original network with
+    /// [`CitationNetwork::diff`], the result is isomorphic as labeled to it, but not
+    /// always identical: the diff compares edges as a set, so a removed citation that
+    /// the original repeated is restored only once. An added edge that was already
+    /// present before is removed as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `diff` - The changes to undo
+    pub fn revert_diff(&mut self, diff: &NetworkDiff) {
+        self.apply_diff(&diff.inverse());
+    }
+    /// Returns whether two networks have the same labeled nodes and edges
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The network to compare with
+    pub fn is_isomorphic_as_labeled(&self, other: &CitationNetwork) -> bool {
+        self.size() == other.size()
+            && self.nodes().all(|&vertex| other.contains_node(vertex))
+            && self.edge_set() == other.edge_set()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_network() -> CitationNetwork {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(1, 2);
+        graph
+    }
+
+    #[test]
+    fn test_diff_with_itself() {
+        let graph = build_network();
+        let diff = graph.diff(&build_network());
+        assert!(diff.is_empty());
+        assert!(graph.is_isomorphic_as_labeled(&build_network()));
+    }
+
+    #[test]
+    fn test_diff_with_superset() {
+        let graph = build_network();
+        let mut superset = build_network();
+        superset.add_edge(3, 0);
+        superset.add_edge(2, 4);
+        let diff = graph.diff(&superset);
+        assert_eq!(diff.added_nodes, vec![3, 4]);
+        assert!(diff.removed_nodes.is_empty());
+        assert_eq!(diff.added_edges, vec![(2, 4), (3, 0)]);
+        assert!(diff.removed_edges.is_empty());
+        assert_eq!(
+            diff.to_string(),
+            "nodes added: 2\nnodes removed: 0\nedges added: 2\nedges removed: 0\n"
+        );
+        let reverse = superset.diff(&graph);
+        assert_eq!(reverse.removed_nodes, vec![3, 4]);
+        assert_eq!(reverse.removed_edges, vec![(2, 4), (3, 0)]);
+        assert!(!graph.is_isomorphic_as_labeled(&superset));
+    }
+
+    #[test]
+    fn test_diff_with_rewired_edge() {
+        let graph = build_network();
+        let mut rewired = CitationNetwork::new();
+        rewired.add_edge(0, 1);
+        rewired.add_edge(0, 2);
+        rewired.add_edge(2, 1);
+        let diff = graph.diff(&rewired);
+        assert!(diff.added_nodes.is_empty());
+        assert!(diff.removed_nodes.is_empty());
+        assert_eq!(diff.added_edges, vec![(2, 1)]);
+        assert_eq!(diff.removed_edges, vec![(1, 2)]);
+        assert!(!graph.is_isomorphic_as_labeled(&rewired));
+    }
+
+    #[test]
+    fn test_apply_and_revert_diff() {
+        let mut graph = build_network();
+        let mut newer = CitationNetwork::new();
+        newer.add_edge(0, 1);
+        newer.add_edge(1, 3);
+        newer.add_edge(3, 4);
+        newer.add_node(5);
+        let diff = graph.diff(&newer);
+        assert_eq!(diff.added_nodes, vec![3, 4, 5]);
+        assert_eq!(diff.removed_nodes, vec![2]);
+        assert_eq!(diff.added_edges, vec![(1, 3), (3, 4)]);
+        assert_eq!(diff.removed_edges, vec![(0, 2), (1, 2)]);
+        graph.apply_diff(&diff);
+        assert!(graph.is_isomorphic_as_labeled(&newer));
+        assert!(graph.diff(&newer).is_empty());
+        graph.revert_diff(&diff);
+        assert!(graph.is_isomorphic_as_labeled(&build_network()));
+        assert_eq!(diff.inverse().inverse(), diff);
+    }
+
+    #[test]
+    fn test_apply_diff_does_not_repeat_edges() {
+        let mut graph = build_network();
+        let diff = NetworkDiff {
+            added_edges: vec![(0, 1), (2, 0)],
+            ..NetworkDiff::default()
+        };
+        graph.apply_diff(&diff);
+        assert_eq!(graph.num_edges(), 4);
+        graph.revert_diff(&diff);
+        // Reverting removes every added edge, even one that was already present
+        assert_eq!(graph.num_edges(), 2);
+    }
+
+    #[test]
+    fn test_revert_diff_loses_repeated_citations() {
+        let mut graph = build_network();
+        graph.add_edge(0, 2);
+        let mut newer = CitationNetwork::new();
+        newer.add_edge(0, 1);
+        newer.add_edge(1, 2);
+        let diff = graph.diff(&newer);
+        assert_eq!(diff.removed_edges, vec![(0, 2)]);
+        graph.apply_diff(&diff);
+        graph.revert_diff(&diff);
+        assert!(graph.is_isomorphic_as_labeled(&build_network()));
+        // Both citations from 0 to 2 were removed, but only one is restored
+        assert_eq!(graph.num_edges(), 3);
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use crate::network::{CitationNetwork, UnknownVertex};
+
+/// How a paper combines the activations of the papers citing it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffusionRule {
+    /// The mean activation of the citing papers, which never exceeds the largest
+    /// seed
+    Average,
+    /// The total activation of the citing papers, which can grow without bound
+    Sum,
+}
+
+impl CitationNetwork {
+    /// Simulates activation spreading from seed papers, with each paper adopting the
+    /// average activation of the papers citing it
+    ///
+    /// This is [`CitationNetwork::diffuse_with`] with [`DiffusionRule::Average`].
+    ///
+    /// # Arguments
+    ///
+    /// * `seeds` - The initial activation of some papers; all others start at 0
+    /// * `steps` - The number of steps to simulate
+    ///
+    /// # Panics
+    ///
+    /// Panics if a seed is not in the network.
+    pub fn diffuse(&self, seeds: &HashMap<usize, f64>, steps: usize) -> HashMap<usize, f64> {
+        self.diffuse_with(seeds, steps, DiffusionRule::Average)
+    }
+    /// Simulates activation spreading from seed papers
+    ///
+    /// At every step all papers update at once: a paper takes the combined activation
+    /// of its in-neighbors, the papers citing it, as they were after the previous
+    /// step. A repeated citation counts once per occurrence. A paper nobody cites has
+    /// nothing to adopt and keeps its activation, so uncited seeds keep spreading.
+    ///
+    /// # Arguments
+    ///
+    /// * `seeds` - The initial activation of some papers; all others start at 0
+    /// * `steps` - The number of steps to simulate
+    /// * `rule` - How the activations of the citing papers are combined
+    ///
+    /// # Returns
+    ///
+    /// The activation of every paper after the last step.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a seed is not in the network.
+    pub fn diffuse_with(
+        &self,
+        seeds: &HashMap<usize, f64>,
+        steps: usize,
+        rule: DiffusionRule,
+    ) -> HashMap<usize, f64> {
+        let mut unknown: Vec<usize> = seeds
+            .keys()
+            .copied()
+            .filter(|&vertex| !self.contains_node(vertex))
+            .collect();
+        unknown.sort_unstable();
+        if let Some(&vertex) = unknown.first() {
+            panic!("{}", UnknownVertex(vertex));
+        }
+        let mut activation: HashMap<usize, f64> = self
+            .nodes()
+            .map(|&vertex| (vertex, seeds.get(&vertex).copied().unwrap_or(0.0)))
+            .collect();
+        for _ in 0..steps {
+            let next = activation
+                .iter()
+                .map(|(&vertex, &current)| {
+                    let citers: Vec<f64> = self
+                        .in_edges_to(vertex)
+                        .map(|citer| activation[citer])
+                        .collect();
+                    if citers.is_empty() {
+                        return (vertex, current);
+                    }
+                    let total: f64 = citers.iter().sum();
+                    let combined = match rule {
+                        DiffusionRule::Average => total / citers.len() as f64,
+                        DiffusionRule::Sum => total,
+                    };
+                    (vertex, combined)
+                })
+                .collect();
+            activation = next;
+        }
+        activation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_graphs::path;
+
+    #[test]
+    fn test_diffuse_along_chain() {
+        let network = path(5);
+        let seeds = HashMap::from([(0, 1.0)]);
+        let activation = network.diffuse(&seeds, 2);
+        assert_eq!(activation.len(), 5);
+        // The seed is cited by nobody and keeps its activation, which moves one
+        // citation further at every step
+        assert_eq!(activation[&0], 1.0);
+        assert_eq!(activation[&1], 1.0);
+        assert_eq!(activation[&2], 1.0);
+        assert_eq!(activation[&3], 0.0);
+        assert_eq!(activation[&4], 0.0);
+        assert_eq!(network.diffuse(&seeds, 0), {
+            let mut initial: HashMap<usize, f64> = (0..5).map(|v| (v, 0.0)).collect();
+            initial.insert(0, 1.0);
+            initial
+        });
+    }
+
+    #[test]
+    fn test_activation_halves_along_chain_with_silent_citers() {
+        // Every paper of the chain after the seed is also cited by an inactive paper
+        let mut network = path(5);
+        for i in 1..5 {
+            network.add_edge(100 + i, i);
+        }
+        let seeds = HashMap::from([(0, 1.0)]);
+        let activation = network.diffuse(&seeds, 10);
+        for i in 0..5 {
+            assert_eq!(activation[&i], 0.5f64.powi(i as i32));
+        }
+        assert!((1..5).all(|i| activation[&(100 + i)] == 0.0));
+        // After two steps the activation has not reached the third paper yet
+        let activation = network.diffuse(&seeds, 2);
+        assert_eq!(activation[&2], 0.25);
+        assert_eq!(activation[&3], 0.0);
+        // Summing passes the activation on undiminished
+        let activation = network.diffuse_with(&seeds, 10, DiffusionRule::Sum);
+        assert!((0..5).all(|i| activation[&i] == 1.0));
+    }
+
+    #[test]
+    fn test_sum_adds_up_citers() {
+        let network: CitationNetwork = [(0, 2), (1, 2), (2, 3), (2, 3)].into_iter().collect();
+        let seeds = HashMap::from([(0, 1.0), (1, 2.0)]);
+        let activation = network.diffuse_with(&seeds, 2, DiffusionRule::Sum);
+        assert_eq!(activation[&2], 3.0);
+        // The repeated citation counts twice
+        assert_eq!(activation[&3], 6.0);
+        assert_eq!(network.diffuse(&seeds, 2)[&3], 1.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "vertex 9 is not in the network")]
+    fn test_diffuse_from_unknown_seed() {
+        path(3).diffuse(&HashMap::from([(9, 1.0)]), 1);
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+use crate::network::{CitationNetwork, Direction};
+
+impl CitationNetwork {
+    /// Returns the shortest-path distances from a node to every node it can reach
+    ///
+    /// Distances follow the direction of citations, so a node reaches the papers it
+    /// cites, the papers those papers cite, and so on. The source itself is included
+    /// at distance 0. An empty map is returned if the source is not in the network.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The id of the paper to start from
+    ///
+    pub fn bfs_distances(&self, source: usize) -> HashMap<usize, usize> {
+        self.bfs_distances_in(source, Direction::Outgoing)
+    }
+    /// Returns the shortest-path distances from a node, following edges in a direction
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The id of the paper to start from
+    /// * `direction` - Which edges to follow
+    ///
+    pub fn bfs_distances_in(&self, source: usize, direction: Direction) -> HashMap<usize, usize> {
+        let mut distances = HashMap::new();
+        if !self.out_edges.contains_key(&source) {
+            return distances;
+        }
+        let mut queue = VecDeque::new();
+        distances.insert(source, 0);
+        queue.push_back(source);
+        while let Some(vertex) = queue.pop_front() {
+            let distance = distances[&vertex];
+            for next in self.neighbors_in(vertex, direction) {
+                if let Entry::Vacant(entry) = distances.entry(next) {
+                    entry.insert(distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        distances
+    }
+    /// Returns the shortest-path distance from the nearest of several seeds to every
+    /// node reachable from any of them
+    ///
+    /// Distances follow the direction of citations, as in
+    /// [`CitationNetwork::bfs_distances`]. Seeds not in the network are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `seeds` - The ids of the papers to start from
+    pub fn multi_source_bfs(&self, seeds: &[usize]) -> HashMap<usize, usize> {
+        self.multi_source_bfs_with_voronoi(seeds)
+            .into_iter()
+            .map(|(vertex, (distance, _))| (vertex, distance))
+            .collect()
+    }
+    /// Returns the distance to and the id of the nearest seed of every node reachable
+    /// from any seed
+    ///
+    /// The nodes sharing a nearest seed form its Voronoi cell. A node equally close
+    /// to several seeds is assigned to the one appearing first in `seeds`. Seeds not
+    /// in the network are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `seeds` - The ids of the papers to start from
+    ///
+    /// # Returns
+    ///
+    /// * `cells` - The distance and nearest seed of each reachable node
+    pub fn multi_source_bfs_with_voronoi(&self, seeds: &[usize]) -> HashMap<usize, (usize, usize)> {
+        let mut cells = HashMap::new();
+        let mut queue = VecDeque::new();
+        for &seed in seeds {
+            if self.contains_node(seed) {
+                if let Entry::Vacant(entry) = cells.entry(seed) {
+                    entry.insert((0, seed));
+                    queue.push_back(seed);
+                }
+            }
+        }
+        // Each level of the queue is ordered by the position of the nearest seed, so
+        // a node is first reached through the earliest of its nearest seeds
+        while let Some(vertex) = queue.pop_front() {
+            let (distance, seed) = cells[&vertex];
+            for &next in self.out_edges_from(vertex) {
+                if let Entry::Vacant(entry) = cells.entry(next) {
+                    entry.insert((distance + 1, seed));
+                    queue.push_back(next);
+                }
+            }
+        }
+        cells
+    }
+    /// Returns a shortest chain of citations from one paper to another
+    ///
+    /// The chain follows the direction of citations, as in
+    /// [`CitationNetwork::bfs_distances`]. Of several shortest chains, the one whose
+    /// papers have the smallest ids, compared from the start, is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The id of the paper to start from
+    /// * `to` - The id of the paper to reach
+    ///
+    /// # Returns
+    ///
+    /// The papers of the chain from `from` to `to`, both included, or `None` if `to`
+    /// cannot be reached or either paper is not in the network.
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        if !self.contains_node(from) || !self.contains_node(to) {
+            return None;
+        }
+        let mut parents: HashMap<usize, usize> = HashMap::from([(from, from)]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(vertex) = queue.pop_front() {
+            if vertex == to {
+                break;
+            }
+            let mut references: Vec<usize> = self.out_edges_from(vertex).copied().collect();
+            references.sort_unstable();
+            for next in references {
+                if let Entry::Vacant(entry) = parents.entry(next) {
+                    entry.insert(vertex);
+                    queue.push_back(next);
+                }
+            }
+        }
+        parents.get(&to)?;
+        let mut path = vec![to];
+        while *path.last().unwrap() != from {
+            path.push(parents[path.last().unwrap()]);
+        }
+        path.reverse();
+        Some(path)
+    }
+    /// Returns the eccentricity of a node
+    ///
+    /// The eccentricity is the largest shortest-path distance from the node to any
+    /// node reachable from it. A node that cites nothing has eccentricity 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex` - The id of the paper
+    ///
+    /// # Returns
+    ///
+    /// * `None` if the vertex is not in the network
+    pub fn eccentricity(&self, vertex: usize) -> Option<usize> {
+        if !self.out_edges.contains_key(&vertex) {
+            return None;
+        }
+        self.bfs_distances(vertex).into_values().max()
+    }
+    /// Returns the eccentricity of every node in the network
+    pub fn eccentricities(&self) -> HashMap<usize, Option<usize>> {
+        self.nodes()
+            .map(|&vertex| (vertex, self.eccentricity(vertex)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eccentricity_on_path() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        assert_eq!(graph.eccentricity(0), Some(3));
+        assert_eq!(graph.eccentricity(1), Some(2));
+        assert_eq!(graph.eccentricity(2), Some(1));
+        assert_eq!(graph.eccentricity(3), Some(0));
+        assert_eq!(graph.eccentricity(4), None);
+        let eccentricities = graph.eccentricities();
+        assert_eq!(eccentricities.len(), 4);
+        assert_eq!(eccentricities[&0], Some(3));
+        assert_eq!(eccentricities[&3], Some(0));
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(1, 3);
+        graph.add_edge(2, 3);
+        graph.add_edge(4, 0);
+        let distances = graph.bfs_distances(0);
+        assert_eq!(distances.len(), 4);
+        assert_eq!(distances[&0], 0);
+        assert_eq!(distances[&1], 1);
+        assert_eq!(distances[&3], 2);
+        assert!(!distances.contains_key(&4));
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 2);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 3);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 4);
+        graph.add_node(5);
+        // Both 1 and 2 lead to 3 in two steps, and 1 is the smaller
+        assert_eq!(graph.shortest_path(0, 4), Some(vec![0, 1, 3, 4]));
+        assert_eq!(graph.shortest_path(2, 4), Some(vec![2, 3, 4]));
+        assert_eq!(graph.shortest_path(3, 3), Some(vec![3]));
+        assert_eq!(graph.shortest_path(4, 0), None);
+        assert_eq!(graph.shortest_path(0, 5), None);
+        assert_eq!(graph.shortest_path(0, 42), None);
+    }
+
+    #[test]
+    fn test_multi_source_bfs() {
+        // Two chains 0 -> 1 -> 2 -> 3 and 10 -> 11 -> 3, plus 5 unreachable
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(10, 11);
+        graph.add_edge(11, 3);
+        graph.add_node(5);
+        let distances = graph.multi_source_bfs(&[0, 10]);
+        assert_eq!(distances.len(), 6);
+        assert_eq!(distances[&0], 0);
+        assert_eq!(distances[&2], 2);
+        assert_eq!(distances[&3], 2);
+        assert!(!distances.contains_key(&5));
+        assert_eq!(graph.multi_source_bfs(&[0]), graph.bfs_distances(0));
+        assert!(graph.multi_source_bfs(&[42]).is_empty());
+    }
+
+    #[test]
+    fn test_multi_source_bfs_with_voronoi() {
+        // 3 is two steps from both seeds, and 4 is one step beyond it
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 3);
+        graph.add_edge(10, 11);
+        graph.add_edge(11, 3);
+        graph.add_edge(3, 4);
+        let cells = graph.multi_source_bfs_with_voronoi(&[10, 0]);
+        assert_eq!(cells[&1], (1, 0));
+        assert_eq!(cells[&11], (1, 10));
+        assert_eq!(cells[&3], (2, 10));
+        assert_eq!(cells[&4], (3, 10));
+        let cells = graph.multi_source_bfs_with_voronoi(&[0, 10, 0]);
+        assert_eq!(cells[&3], (2, 0));
+        assert_eq!(cells[&4], (3, 0));
+        assert_eq!(cells[&10], (0, 10));
+    }
+}
@@ -0,0 +1,69 @@
+use std::io::{self, Write};
+
+use crate::network::CitationNetwork;
+
+impl CitationNetwork {
+    /// Writes the network as an edge list in the SNAP format
+    ///
+    /// The output starts with a four-line header and can be read back with
+    /// [`CitationNetwork::load_from_reader`]. Edges are written in the order of
+    /// [`CitationNetwork::edges_iter`], sorted in ascending order of citing and then
+    /// cited id, so the same network always produces the same bytes regardless of
+    /// how it was built. Nodes without any edges cannot be represented and are lost.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the edge list
+    pub fn write_edge_list<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "# Directed graph")?;
+        writeln!(writer, "# Citation network")?;
+        writeln!(
+            writer,
+            "# Nodes: {} Edges: {}",
+            self.size(),
+            self.num_edges()
+        )?;
+        writeln!(writer, "# FromNodeId\tToNodeId")?;
+        for (from, to) in self.edges_iter() {
+            writeln!(writer, "{}\t{}", from, to)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(network: &CitationNetwork) -> Vec<u8> {
+        let mut output = Vec::new();
+        network.write_edge_list(&mut output).unwrap();
+        output
+    }
+
+    #[test]
+    fn test_write_edge_list_is_deterministic() {
+        let edges = [(5, 1), (0, 1), (3, 0), (0, 2), (5, 0), (2, 1)];
+        let mut forward = CitationNetwork::new();
+        for &(from, to) in &edges {
+            forward.add_edge(from, to);
+        }
+        let mut backward = CitationNetwork::new();
+        for &(from, to) in edges.iter().rev() {
+            backward.add_edge(from, to);
+        }
+        assert_eq!(write(&forward), write(&backward));
+        let output = String::from_utf8(write(&forward)).unwrap();
+        assert!(output.ends_with("0\t1\n0\t2\n2\t1\n3\t0\n5\t0\n5\t1\n"));
+    }
+
+    #[test]
+    fn test_write_edge_list_round_trip() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(9207016, 9201015);
+        network.add_edge(9201015, 9207016);
+        network.add_edge(7, 9207016);
+        let loaded = CitationNetwork::load_from_reader(write(&network).as_slice());
+        assert!(loaded.is_isomorphic_as_labeled(&network));
+    }
+}
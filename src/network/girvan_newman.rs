@@ -0,0 +1,336 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::centrality::edge_betweenness::edge_betweenness;
+use crate::network::CitationNetwork;
+
+/// The difference below which two edge betweenness values are considered equal
+const EPSILON: f64 = 1e-9;
+
+/// A level of the Girvan-Newman split history, reached when removing edges split a
+/// community
+#[derive(Clone, Debug, PartialEq)]
+pub struct GnLevel {
+    /// The communities of the level, each sorted by id and ordered by their smallest
+    /// paper
+    pub communities: Vec<Vec<usize>>,
+    /// The modularity of the communities in the whole network
+    pub modularity: f64,
+    /// The edges removed since the previous level, in the order they were removed,
+    /// each given by the ids of its papers in ascending order
+    pub removed_edges: Vec<(usize, usize)>,
+}
+
+impl GnLevel {
+    /// Returns the community of every paper, numbered in the order of the
+    /// communities
+    pub fn partition(&self) -> HashMap<usize, usize> {
+        self.communities
+            .iter()
+            .enumerate()
+            .flat_map(|(id, community)| community.iter().map(move |&vertex| (vertex, id)))
+            .collect()
+    }
+}
+
+/// The result of the Girvan-Newman algorithm
+///
+/// The levels run from the connected components of the network to the communities
+/// the algorithm stopped at, with one more community at each level unless a
+/// removal split off several at once.
+#[derive(Clone, Debug)]
+pub struct GnResult {
+    levels: Vec<GnLevel>,
+    best: usize,
+}
+
+impl GnResult {
+    /// Returns every level of the split history, starting with the connected
+    /// components of the network
+    pub fn levels(&self) -> &[GnLevel] {
+        &self.levels
+    }
+    /// Returns the level with the highest modularity, the first of several with the
+    /// same modularity
+    pub fn best_level(&self) -> &GnLevel {
+        &self.levels[self.best]
+    }
+    /// Returns the community of every paper at the level with the highest modularity
+    pub fn best_partition(&self) -> HashMap<usize, usize> {
+        self.best_level().partition()
+    }
+    /// Returns the communities of the last level, where the algorithm stopped
+    pub fn communities(&self) -> &[Vec<usize>] {
+        &self.levels.last().unwrap().communities
+    }
+}
+
+/// The parameters of the Girvan-Newman algorithm
+pub struct GirvanNewmanConfig {
+    batch_size: usize,
+}
+
+impl Default for GirvanNewmanConfig {
+    fn default() -> Self {
+        GirvanNewmanConfig { batch_size: 1 }
+    }
+}
+
+impl GirvanNewmanConfig {
+    /// Creates a configuration recomputing the betweenness after every removal
+    pub fn new() -> GirvanNewmanConfig {
+        GirvanNewmanConfig::default()
+    }
+    /// Sets the number of edges removed between two computations of the betweenness
+    ///
+    /// The edges with the highest betweenness are removed together, which divides
+    /// the cost by about the batch size, but the later edges of a batch are chosen by
+    /// a betweenness that earlier removals have made stale.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_size` - The number of edges
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is 0.
+    pub fn batch_size(mut self, batch_size: usize) -> GirvanNewmanConfig {
+        assert!(
+            batch_size > 0,
+            "at least one edge must be removed per batch"
+        );
+        self.batch_size = batch_size;
+        self
+    }
+    /// Splits a network into communities with the Girvan-Newman algorithm
+    ///
+    /// Citations are treated as undirected, with repeated and mutual citations
+    /// joining two papers by a single edge and self-citations ignored. The edge with
+    /// the highest betweenness, which is the one most shortest paths between
+    /// communities have to cross, is removed, and the betweenness of the remaining
+    /// edges is recomputed, until the network falls apart into `target_communities`
+    /// connected components or no edges remain. Of several edges with the same
+    /// betweenness, the one with the smallest ids is removed first. Every split adds
+    /// a level to the history, with the modularity of its communities as computed by
+    /// [`CitationNetwork::modularity`] at resolution 1.
+    ///
+    /// Every computation of the betweenness takes O(VE), so the whole algorithm
+    /// costs O(VE^2) in the worst case and is intended for networks of up to a few
+    /// thousand papers. Larger networks are better split by
+    /// [`CitationNetwork::louvain`].
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network to split
+    /// * `target_communities` - The number of communities to stop at
+    pub fn run(&self, network: &CitationNetwork, target_communities: usize) -> GnResult {
+        let index = network.node_index();
+        let mut adjacency: Vec<Vec<usize>> = index
+            .vertices()
+            .iter()
+            .map(|&vertex| {
+                network
+                    .neighbors(vertex)
+                    .into_iter()
+                    .map(|neighbor| index.index_of(neighbor).unwrap())
+                    .collect()
+            })
+            .collect();
+        let level = |components: &[Vec<usize>], removed_edges: Vec<(usize, usize)>| {
+            let mut communities: Vec<Vec<usize>> = components
+                .iter()
+                .map(|component| {
+                    let mut community: Vec<usize> =
+                        component.iter().map(|&i| index.vertex_at(i)).collect();
+                    community.sort_unstable();
+                    community
+                })
+                .collect();
+            communities.sort_unstable();
+            let mut level = GnLevel {
+                communities,
+                modularity: 0.0,
+                removed_edges,
+            };
+            level.modularity = network.modularity(&level.partition(), 1.0);
+            level
+        };
+        let mut components = connected_components(&adjacency);
+        let mut levels = vec![level(&components, Vec::new())];
+        let mut removed = Vec::new();
+        'split: while components.len() < target_communities {
+            let mut edges = edge_betweenness(&adjacency);
+            if edges.is_empty() {
+                break;
+            }
+            for _ in 0..self.batch_size {
+                let mut highest: Option<(usize, f64)> = None;
+                for (i, &(_, value)) in edges.iter().enumerate() {
+                    if highest.is_none_or(|(_, best)| value > best + EPSILON) {
+                        highest = Some((i, value));
+                    }
+                }
+                let Some((i, _)) = highest else {
+                    break;
+                };
+                let ((u, v), _) = edges.remove(i);
+                adjacency[u].retain(|&neighbor| neighbor != v);
+                adjacency[v].retain(|&neighbor| neighbor != u);
+                let (a, b) = (index.vertex_at(u), index.vertex_at(v));
+                removed.push((a.min(b), a.max(b)));
+                let split = connected_components(&adjacency);
+                if split.len() > components.len() {
+                    components = split;
+                    levels.push(level(&components, std::mem::take(&mut removed)));
+                    if components.len() >= target_communities {
+                        break 'split;
+                    }
+                }
+            }
+        }
+        let mut best = 0;
+        for (i, level) in levels.iter().enumerate() {
+            if level.modularity > levels[best].modularity {
+                best = i;
+            }
+        }
+        GnResult { levels, best }
+    }
+}
+
+/// Returns the connected components of an undirected graph given as adjacency lists
+fn connected_components(adjacency: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut visited = vec![false; adjacency.len()];
+    let mut components = Vec::new();
+    for root in 0..adjacency.len() {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let mut component = vec![root];
+        let mut queue = VecDeque::from([root]);
+        while let Some(vertex) = queue.pop_front() {
+            for &neighbor in &adjacency[vertex] {
+                if !visited[neighbor] {
+                    visited[neighbor] = true;
+                    component.push(neighbor);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        components.push(component);
+    }
+    components
+}
+
+impl CitationNetwork {
+    /// Splits the network into communities with the Girvan-Newman algorithm
+    ///
+    /// Citations are treated as undirected, with repeated and mutual citations
+    /// joining two papers by a single edge and self-citations ignored. The edge with
+    /// the highest betweenness, which is the one most shortest paths between
+    /// communities have to cross, is removed, and the betweenness of the remaining
+    /// edges is recomputed, until the network falls apart into `num_communities`
+    /// connected components. Of several edges with the same betweenness, the one with
+    /// the smallest ids is removed first.
+    ///
+    /// Every removal recomputes the betweenness of all edges in O(VE), so the whole
+    /// algorithm costs O(VE^2) in the worst case and is only practical for networks
+    /// of a few thousand papers. These are the communities of the last level of
+    /// [`GirvanNewmanConfig::run`], which also keeps the split history and its
+    /// modularity.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_communities` - The number of communities to split the network into
+    ///
+    /// # Returns
+    ///
+    /// The communities, each sorted by id and ordered by their smallest paper. If the
+    /// network already has at least `num_communities` connected components, these
+    /// are returned unchanged, and if it has fewer papers, every paper is a community
+    /// of its own.
+    pub fn girvan_newman(&self, num_communities: usize) -> Vec<Vec<usize>> {
+        GirvanNewmanConfig::new()
+            .run(self, num_communities)
+            .communities()
+            .to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_graphs::{path, two_cliques_bridge};
+
+    #[test]
+    fn test_girvan_newman_splits_at_bridge() {
+        let network = two_cliques_bridge(5);
+        let communities = network.girvan_newman(2);
+        assert_eq!(communities, vec![vec![0, 1, 2, 3, 4], vec![5, 6, 7, 8, 9]]);
+        assert_eq!(network.girvan_newman(1), vec![(0..10).collect::<Vec<_>>()]);
+    }
+
+    #[test]
+    fn test_girvan_newman_on_path() {
+        // The middle edge of a path carries the most shortest paths
+        let network = path(6);
+        assert_eq!(network.girvan_newman(2), vec![vec![0, 1, 2], vec![3, 4, 5]]);
+        assert_eq!(network.girvan_newman(6).len(), 6);
+        assert_eq!(network.girvan_newman(10).len(), 6);
+    }
+
+    #[test]
+    fn test_girvan_newman_keeps_existing_components() {
+        let mut network = two_cliques_bridge(3);
+        network.add_edge(20, 21);
+        network.add_node(30);
+        assert_eq!(
+            network.girvan_newman(2),
+            vec![vec![0, 1, 2, 3, 4, 5], vec![20, 21], vec![30]]
+        );
+        assert_eq!(network.girvan_newman(4).len(), 4);
+        assert!(CitationNetwork::new().girvan_newman(3).is_empty());
+    }
+
+    #[test]
+    fn test_girvan_newman_history() {
+        let network = two_cliques_bridge(4);
+        let result = GirvanNewmanConfig::new().run(&network, 20);
+        let levels = result.levels();
+        // Splitting the cliques apart lowers the modularity again
+        assert_eq!(result.best_level().communities.len(), 2);
+        assert_eq!(result.communities().len(), 8);
+        for pair in levels.windows(2) {
+            assert!(pair[0].communities.len() < pair[1].communities.len());
+            assert!(!pair[1].removed_edges.is_empty());
+        }
+        let removed: usize = levels.iter().map(|level| level.removed_edges.len()).sum();
+        assert_eq!(removed, network.num_edges());
+        assert!(levels.last().unwrap().modularity < 0.0);
+        assert_eq!(
+            network.girvan_newman(3),
+            GirvanNewmanConfig::new().run(&network, 3).communities()
+        );
+        let empty = GirvanNewmanConfig::new().run(&CitationNetwork::new(), 2);
+        assert!(empty.communities().is_empty());
+        assert!(empty.best_partition().is_empty());
+    }
+
+    #[test]
+    fn test_girvan_newman_in_batches() {
+        let network = two_cliques_bridge(5);
+        let result = GirvanNewmanConfig::new().batch_size(3).run(&network, 2);
+        assert_eq!(result.levels().len(), 2);
+        assert_eq!(result.best_level().removed_edges, vec![(4, 5)]);
+        // The second edge of a batch is chosen by the betweenness of the whole path
+        let result = GirvanNewmanConfig::new().batch_size(2).run(&path(7), 3);
+        assert_eq!(result.levels()[1].removed_edges, vec![(2, 3)]);
+        assert_eq!(result.levels()[2].removed_edges, vec![(3, 4)]);
+        assert_eq!(
+            result.communities(),
+            [vec![0, 1, 2], vec![3], vec![4, 5, 6]]
+        );
+        let result = GirvanNewmanConfig::new().run(&path(7), 3);
+        assert_eq!(result.levels()[2].removed_edges, vec![(4, 5)]);
+    }
+}
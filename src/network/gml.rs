@@ -0,0 +1,299 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+use crate::network::builder::{BuildStats, CitationNetworkBuilder};
+use crate::network::{CitationNetwork, LoadError};
+
+/// A key, value or bracket in a GML file together with its line number
+struct Token {
+    text: String,
+    line: usize,
+}
+
+/// Splits GML into tokens, keeping quoted strings whole and dropping comments
+fn tokenize<R: Read>(reader: R) -> Result<Vec<Token>, LoadError> {
+    let mut tokens = Vec::new();
+    for (index, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        let number = index + 1;
+        let mut chars = line.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+            } else if c == '#' {
+                break;
+            } else if c == '"' {
+                chars.next();
+                let text: String = chars.by_ref().take_while(|&c| c != '"').collect();
+                tokens.push(Token {
+                    text: format!("\"{}\"", text),
+                    line: number,
+                });
+            } else if c == '[' || c == ']' {
+                chars.next();
+                tokens.push(Token {
+                    text: c.to_string(),
+                    line: number,
+                });
+            } else {
+                let mut text = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '[' || c == ']' {
+                        break;
+                    }
+                    text.push(c);
+                    chars.next();
+                }
+                tokens.push(Token { text, line: number });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parses the value of an `id`, `source` or `target` key
+fn parse_id(key: &str, value: &Token) -> Result<usize, LoadError> {
+    value.text.parse().map_err(|_| {
+        LoadError::parse(
+            value.line,
+            format!(
+                "invalid {} {}: expected a non-negative integer",
+                key, value.text
+            ),
+        )
+    })
+}
+
+impl CitationNetwork {
+    /// Writes the network as a directed graph in the GML format
+    ///
+    /// Node ids are the vertex ids. Nodes are written in ascending order of id and
+    /// edges in the order of [`CitationNetwork::edges_iter`], so the output is
+    /// deterministic.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the GML
+    pub fn to_gml<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "graph [")?;
+        writeln!(writer, "  directed 1")?;
+        for vertex in self.nodes_sorted() {
+            writeln!(writer, "  node [")?;
+            writeln!(writer, "    id {}", vertex)?;
+            writeln!(writer, "  ]")?;
+        }
+        for (from, to) in self.edges_iter() {
+            writeln!(writer, "  edge [")?;
+            writeln!(writer, "    source {}", from)?;
+            writeln!(writer, "    target {}", to)?;
+            writeln!(writer, "  ]")?;
+        }
+        writeln!(writer, "]")
+    }
+    /// Loads a network from a graph in the GML format
+    ///
+    /// Only the node `id` and the edge `source` and `target` keys are read, and
+    /// vertex ids are the GML node ids. Every other key, such as `label`, `directed`
+    /// or `graphics`, is ignored, as are comments starting with `#` and keys outside
+    /// the graph such as `Creator`. Edges are always loaded as citations from source
+    /// to target.
+    ///
+    /// A network has no place for names, so the node labels are lost: a paper
+    /// labelled with its arXiv id in the file becomes the vertex of its GML id.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to load from
+    pub fn load_from_gml<R: Read>(reader: R) -> Result<CitationNetwork, LoadError> {
+        Ok(CitationNetwork::load_from_gml_with(reader, CitationNetworkBuilder::new())?.0)
+    }
+    /// Loads a network from a graph in the GML format through a builder
+    ///
+    /// See [`CitationNetwork::load_from_gml`]; the options of the builder decide
+    /// which edges are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to load from
+    /// * `builder` - The builder to add the nodes and edges to
+    pub fn load_from_gml_with<R: Read>(
+        reader: R,
+        mut builder: CitationNetworkBuilder,
+    ) -> Result<(CitationNetwork, BuildStats), LoadError> {
+        let tokens = tokenize(reader)?;
+        // The keys of the open lists, with the line each one was opened on
+        let mut open: Vec<(&str, usize)> = Vec::new();
+        let (mut id, mut source, mut target) = (None, None, None);
+        let mut tokens = tokens.iter();
+        while let Some(key) = tokens.next() {
+            if key.text == "]" {
+                let Some((list, line)) = open.pop() else {
+                    return Err(LoadError::parse(key.line, "unmatched ]"));
+                };
+                match (list, open.len()) {
+                    ("node", 1) => {
+                        let vertex = id
+                            .take()
+                            .ok_or_else(|| LoadError::parse(line, "node without an id"))?;
+                        builder.add_node(vertex);
+                    }
+                    ("edge", 1) => match (source.take(), target.take()) {
+                        (Some(from), Some(to)) => builder.add_edge(from, to),
+                        _ => {
+                            return Err(LoadError::parse(line, "edge without a source and target"))
+                        }
+                    },
+                    _ => {}
+                }
+                continue;
+            }
+            let Some(value) = tokens.next() else {
+                return Err(LoadError::parse(
+                    key.line,
+                    format!("{} without a value", key.text),
+                ));
+            };
+            if value.text == "[" {
+                if open.is_empty() && key.text != "graph" {
+                    return Err(LoadError::parse(
+                        key.line,
+                        format!("expected graph, found {}", key.text),
+                    ));
+                }
+                if key.text == "node" || key.text == "edge" {
+                    id = None;
+                    source = None;
+                    target = None;
+                }
+                open.push((&key.text, key.line));
+                continue;
+            }
+            match (open.last(), open.len(), key.text.as_str()) {
+                (Some(("node", _)), 2, "id") => id = Some(parse_id("id", value)?),
+                (Some(("edge", _)), 2, "source") => source = Some(parse_id("source", value)?),
+                (Some(("edge", _)), 2, "target") => target = Some(parse_id("target", value)?),
+                _ => {}
+            }
+        }
+        if let Some((list, line)) = open.last() {
+            return Err(LoadError::parse(*line, format!("{} is not closed", list)));
+        }
+        Ok(builder.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A directed multigraph as written by networkx's `write_gml`
+    const NETWORKX_GML: &str = r#"graph [
+  directed 1
+  multigraph 1
+  node [
+    id 0
+    label "9207016"
+  ]
+  node [
+    id 1
+    label "9201015"
+    graphics [
+      x 1.5
+      y -2.0
+    ]
+  ]
+  node [
+    id 2
+    label "isolated paper"
+  ]
+  edge [
+    source 0
+    target 1
+    key 0
+  ]
+  edge [
+    source 1
+    target 0
+    key 0
+    weight 0.5
+  ]
+]
+"#;
+
+    #[test]
+    fn test_gml_round_trip() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(3, 1);
+        network.add_edge(1, 2);
+        network.add_edge(2, 3);
+        network.add_node(9);
+        let mut output = Vec::new();
+        network.to_gml(&mut output).unwrap();
+        let text = String::from_utf8(output).unwrap();
+        assert!(text.starts_with("graph [\n  directed 1\n  node [\n    id 1\n  ]\n"));
+        let loaded = CitationNetwork::load_from_gml(text.as_bytes()).unwrap();
+        assert!(loaded.is_isomorphic_as_labeled(&network));
+        // The same graph on one line
+        let inline = "Creator \"yEd\" graph [ directed 1 node [ id 0 ] node [ id 1 ] \
+                      edge [ source 0 target 1 ] ]";
+        let loaded = CitationNetwork::load_from_gml(inline.as_bytes()).unwrap();
+        assert_eq!(loaded.edges_iter().collect::<Vec<_>>(), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn test_load_networkx_gml() {
+        let network = CitationNetwork::load_from_gml(NETWORKX_GML.as_bytes()).unwrap();
+        assert_eq!(network.size(), 3);
+        assert_eq!(
+            network.edges_iter().collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+    }
+
+    #[test]
+    fn test_load_gml_through_builder() {
+        let text = "graph [ edge [ source 1 target 2 ] edge [ source 1 target 2 ] \
+                    edge [ source 2 target 2 ] node [ id 3 ] ]";
+        let builder = CitationNetworkBuilder::new()
+            .dedup(true)
+            .drop_self_loops(true);
+        let (network, stats) =
+            CitationNetwork::load_from_gml_with(text.as_bytes(), builder).unwrap();
+        assert_eq!(network.nodes_sorted(), vec![1, 2, 3]);
+        assert_eq!(network.edges_iter().collect::<Vec<_>>(), vec![(1, 2)]);
+        assert_eq!(
+            stats,
+            BuildStats {
+                edges_added: 1,
+                duplicates_skipped: 1,
+                loops_dropped: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_load_malformed_gml() {
+        let error = |text: &str| {
+            CitationNetwork::load_from_gml(text.as_bytes())
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error("graph [\n  node [\n    id x\n  ]\n]"),
+            "line 3: invalid id x: expected a non-negative integer"
+        );
+        assert_eq!(
+            error("graph [\n  node [\n    label \"a\"\n  ]\n]"),
+            "line 2: node without an id"
+        );
+        assert_eq!(
+            error("graph [\n  edge [\n    source 1\n  ]\n]"),
+            "line 2: edge without a source and target"
+        );
+        assert_eq!(
+            error("graph [\n  node [\n    id 1\n  ]\n"),
+            "line 1: graph is not closed"
+        );
+        assert_eq!(error("graph [\n]\n]"), "line 3: unmatched ]");
+        assert_eq!(error("node [ id 1 ]"), "line 1: expected graph, found node");
+    }
+}
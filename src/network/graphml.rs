@@ -0,0 +1,167 @@
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+use crate::metadata::MetadataStore;
+use crate::network::CitationNetwork;
+
+/// Escapes the characters of a string that are special in XML text and attributes
+fn escape_xml(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '<', '>', '"', '\'']) {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len() + 8);
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+impl CitationNetwork {
+    /// Writes the network as a directed graph in the GraphML format
+    ///
+    /// Node ids are the vertex ids. Nodes are written in order of id and edges in the
+    /// order of [`CitationNetwork::edges_iter`], so the output is deterministic.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the GraphML
+    pub fn to_graphml<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.to_graphml_with(writer, None)
+    }
+    /// Writes the network in the GraphML format with the titles of the papers
+    ///
+    /// When `metadata` is given, a `title` key is declared for nodes and each paper
+    /// with a known title gets a `<data key="title">` element.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the GraphML
+    /// * `metadata` - The titles of the vertices, if available
+    pub fn to_graphml_with<W: Write>(
+        &self,
+        mut writer: W,
+        metadata: Option<&MetadataStore>,
+    ) -> io::Result<()> {
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            writer,
+            r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+        )?;
+        if metadata.is_some() {
+            writeln!(
+                writer,
+                r#"  <key id="title" for="node" attr.name="title" attr.type="string"/>"#
+            )?;
+        }
+        writeln!(writer, r#"  <graph id="G" edgedefault="directed">"#)?;
+        let vertices = self.nodes_sorted();
+        for vertex in vertices {
+            match metadata.and_then(|metadata| metadata.title(vertex)) {
+                Some(title) => {
+                    writeln!(writer, r#"    <node id="{}">"#, vertex)?;
+                    writeln!(
+                        writer,
+                        r#"      <data key="title">{}</data>"#,
+                        escape_xml(title)
+                    )?;
+                    writeln!(writer, "    </node>")?;
+                }
+                None => writeln!(writer, r#"    <node id="{}"/>"#, vertex)?,
+            }
+        }
+        for (from, to) in self.edges_iter() {
+            writeln!(writer, r#"    <edge source="{}" target="{}"/>"#, from, to)?;
+        }
+        writeln!(writer, "  </graph>")?;
+        writeln!(writer, "</graphml>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    /// Parses GraphML, checking that it is well-formed XML
+    ///
+    /// # Returns
+    ///
+    /// * `(nodes, edges, titles)` - The node ids, the edges and the title data
+    fn parse(xml: &str) -> (Vec<String>, Vec<(String, String)>, Vec<String>) {
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().check_end_names = true;
+        let (mut nodes, mut edges, mut titles) = (Vec::new(), Vec::new(), Vec::new());
+        let mut in_data = false;
+        loop {
+            match reader.read_event().unwrap() {
+                Event::Start(element) | Event::Empty(element) => {
+                    let attribute = |name: &str| {
+                        let value = element.try_get_attribute(name).unwrap().unwrap();
+                        value.unescape_value().unwrap().into_owned()
+                    };
+                    match element.name().as_ref() {
+                        b"node" => nodes.push(attribute("id")),
+                        b"edge" => edges.push((attribute("source"), attribute("target"))),
+                        b"data" => in_data = true,
+                        _ => {}
+                    }
+                }
+                Event::Text(text) if in_data => {
+                    titles.push(text.unescape().unwrap().into_owned());
+                    in_data = false;
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        (nodes, edges, titles)
+    }
+
+    fn build_network() -> CitationNetwork {
+        let mut network = CitationNetwork::new();
+        network.add_edge(2, 0);
+        network.add_edge(0, 1);
+        network.add_edge(2, 1);
+        network
+    }
+
+    #[test]
+    fn test_to_graphml() {
+        let mut output = Vec::new();
+        build_network().to_graphml(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(!output.contains("<key"));
+        let (nodes, edges, titles) = parse(&output);
+        assert_eq!(nodes, vec!["0", "1", "2"]);
+        let edges: Vec<(&str, &str)> = edges
+            .iter()
+            .map(|(from, to)| (from.as_str(), to.as_str()))
+            .collect();
+        assert_eq!(edges, vec![("0", "1"), ("2", "0"), ("2", "1")]);
+        assert!(titles.is_empty());
+    }
+
+    #[test]
+    fn test_to_graphml_with_titles() {
+        let mut metadata = MetadataStore::new();
+        metadata.set_title(1, "Strings & <branes>");
+        let mut output = Vec::new();
+        build_network()
+            .to_graphml_with(&mut output, Some(&metadata))
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains(r#"<key id="title" for="node""#));
+        assert!(output.contains("Strings &amp; &lt;branes&gt;"));
+        let (nodes, _, titles) = parse(&output);
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(titles, vec!["Strings & <branes>"]);
+    }
+}
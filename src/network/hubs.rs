@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+
+use crate::network::{CitationNetwork, Direction};
+
+/// A paper citing many others, together with the papers it cites
+///
+/// In a citation network these are typically survey papers.
+#[derive(Debug, PartialEq)]
+pub struct HubSpoke {
+    /// The id of the citing paper
+    pub hub: usize,
+    /// The ids of the papers cited by the hub, sorted and without duplicates
+    pub spokes: Vec<usize>,
+}
+
+impl CitationNetwork {
+    /// Returns every paper citing at least a given number of papers
+    ///
+    /// The out-degree counts repeated citations, while the spokes do not. The hubs
+    /// are sorted by id.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_out_degree` - The smallest number of citations a hub makes
+    pub fn find_hubs_and_spokes(&self, min_out_degree: usize) -> Vec<HubSpoke> {
+        self.nodes_sorted()
+            .into_iter()
+            .filter(|&vertex| self.out_degree(vertex) >= min_out_degree)
+            .map(|hub| {
+                let mut spokes: Vec<usize> = self.out_edges_from(hub).copied().collect();
+                spokes.sort_unstable();
+                spokes.dedup();
+                HubSpoke { hub, spokes }
+            })
+            .collect()
+    }
+    /// Returns whether the neighborhood of a paper is a star centered on it
+    ///
+    /// The neighborhood holds the papers within `radius` steps of the center with
+    /// citations treated as undirected. It is a star if every citation between its
+    /// papers involves the center, ignoring self-citations and the direction of
+    /// citations. A paper without neighbors, or not in the network, is not a star.
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - The id of the paper at the center
+    /// * `radius` - The number of steps from the center to include
+    pub fn is_star_subgraph(&self, center: usize, radius: usize) -> bool {
+        let neighborhood: HashSet<usize> = self
+            .bfs_distances_in(center, Direction::Undirected)
+            .into_iter()
+            .filter(|&(_, distance)| distance <= radius)
+            .map(|(vertex, _)| vertex)
+            .collect();
+        if neighborhood.len() < 2 {
+            return false;
+        }
+        neighborhood
+            .iter()
+            .filter(|&&vertex| vertex != center)
+            .all(|&vertex| {
+                self.out_edges_from(vertex)
+                    .all(|&to| to == vertex || to == center || !neighborhood.contains(&to))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A survey 0 citing 1, 2, 3 and 4 twice, with 5 citing 4
+    fn build_network() -> CitationNetwork {
+        let mut network = CitationNetwork::new();
+        for spoke in [3, 1, 2, 4, 4] {
+            network.add_edge(0, spoke);
+        }
+        network.add_edge(5, 4);
+        network
+    }
+
+    #[test]
+    fn test_find_hubs_and_spokes() {
+        let network = build_network();
+        assert_eq!(
+            network.find_hubs_and_spokes(5),
+            vec![HubSpoke {
+                hub: 0,
+                spokes: vec![1, 2, 3, 4]
+            }]
+        );
+        let hubs = network.find_hubs_and_spokes(1);
+        assert_eq!(hubs.len(), 2);
+        assert_eq!(hubs[1].hub, 5);
+        assert!(network.find_hubs_and_spokes(6).is_empty());
+    }
+
+    #[test]
+    fn test_is_star_subgraph() {
+        let mut network = build_network();
+        assert!(network.is_star_subgraph(0, 1));
+        // 5 is two steps away and cites a spoke
+        assert!(!network.is_star_subgraph(0, 2));
+        assert!(network.is_star_subgraph(5, 1));
+        assert!(!network.is_star_subgraph(42, 1));
+        // Spokes citing the center or themselves keep it a star
+        network.add_edge(1, 0);
+        network.add_edge(2, 2);
+        assert!(network.is_star_subgraph(0, 1));
+        network.add_edge(1, 2);
+        assert!(!network.is_star_subgraph(0, 1));
+        network.add_node(9);
+        assert!(!network.is_star_subgraph(9, 1));
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use crate::network::CitationNetwork;
+
+/// A mapping between the paper ids of a network and a compact range of indices
+///
+/// The nodes are numbered `0..n` in ascending order of their ids, so the mapping of
+/// a network does not depend on the order its edges were added in.
+pub struct NodeIndex {
+    /// The paper id at each index
+    vertices: Vec<usize>,
+    /// The index of each paper id
+    indices: HashMap<usize, usize>,
+}
+
+impl NodeIndex {
+    /// Creates a mapping for a collection of paper ids
+    ///
+    /// # Arguments
+    ///
+    /// * `vertices` - The paper ids, which are sorted and deduplicated
+    ///
+    pub fn new(mut vertices: Vec<usize>) -> NodeIndex {
+        vertices.sort_unstable();
+        vertices.dedup();
+        let indices = vertices
+            .iter()
+            .enumerate()
+            .map(|(index, &vertex)| (vertex, index))
+            .collect();
+        NodeIndex { vertices, indices }
+    }
+    /// Returns the number of nodes in the mapping
+    pub fn len(&self) -> usize {
+        self.vertices.len()
+    }
+    /// Returns whether the mapping is empty
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+    /// Returns the compact index of a paper, or `None` if it is not in the mapping
+    pub fn index_of(&self, vertex: usize) -> Option<usize> {
+        self.indices.get(&vertex).copied()
+    }
+    /// Returns the paper id at a compact index
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn vertex_at(&self, index: usize) -> usize {
+        self.vertices[index]
+    }
+    /// Returns the paper ids in index order
+    pub fn vertices(&self) -> &[usize] {
+        &self.vertices
+    }
+}
+
+impl CitationNetwork {
+    /// Returns the mapping between the paper ids of the network and `0..n`
+    pub fn node_index(&self) -> NodeIndex {
+        NodeIndex::new(self.nodes().copied().collect())
+    }
+    /// Returns a copy of the network with its nodes renumbered `0..n`
+    ///
+    /// Nodes are numbered in ascending order of id, as in
+    /// [`CitationNetwork::node_index`], which also maps the original ids to the new
+    /// ones. Every edge is kept, including repeated citations and self-citations, in
+    /// the same order.
+    ///
+    /// # Returns
+    ///
+    /// * `(compacted, original_ids)` - The renumbered network, and the original id of
+    ///   each new id; `NodeIndex::new(original_ids)` gives the inverse mapping
+    pub fn compact(&self) -> (CitationNetwork, Vec<usize>) {
+        let index = self.node_index();
+        let mut compacted = CitationNetwork::new();
+        for (new_id, &vertex) in index.vertices().iter().enumerate() {
+            compacted.add_node(new_id);
+            for &to in self.out_edges_from(vertex) {
+                compacted.add_edge(new_id, index.index_of(to).unwrap());
+            }
+        }
+        (compacted, index.vertices().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_index() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(9207016, 9201015);
+        graph.add_edge(9201015, 9108001);
+        let index = graph.node_index();
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.vertices(), &[9108001, 9201015, 9207016]);
+        assert_eq!(index.index_of(9207016), Some(2));
+        assert_eq!(index.index_of(1), None);
+        assert_eq!(index.vertex_at(0), 9108001);
+    }
+
+    #[test]
+    fn test_compact() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(9207016, 9201015);
+        graph.add_edge(9201015, 9108001);
+        graph.add_edge(9207016, 9201015);
+        graph.add_edge(9108001, 9108001);
+        graph.add_node(9912001);
+        let (compacted, original_ids) = graph.compact();
+        assert_eq!(original_ids, vec![9108001, 9201015, 9207016, 9912001]);
+        assert_eq!(compacted.nodes_sorted(), vec![0, 1, 2, 3]);
+        assert_eq!(compacted.num_edges(), graph.num_edges());
+        let restored: Vec<(usize, usize)> = compacted
+            .edges_iter()
+            .map(|(from, to)| (original_ids[from], original_ids[to]))
+            .collect();
+        let mut expected: Vec<(usize, usize)> = graph.edges_iter().collect();
+        let mut actual = restored;
+        expected.sort_unstable();
+        actual.sort_unstable();
+        assert_eq!(actual, expected);
+        let inverse = NodeIndex::new(original_ids);
+        assert_eq!(inverse.index_of(9207016), Some(2));
+        assert_eq!(compacted.out_degree(2), 2);
+    }
+}
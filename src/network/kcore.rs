@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use crate::network::CitationNetwork;
+
+impl CitationNetwork {
+    /// Returns the coreness of every node of the network
+    ///
+    /// Citations are treated as undirected. The k-core of the network is the largest
+    /// subnetwork in which every node has at least `k` neighbors, and the coreness of
+    /// a node is the largest `k` whose k-core contains it. This is the bucket
+    /// algorithm of Batagelj and Zaversnik, which repeatedly removes a node of least
+    /// remaining degree in O(V + E). Repeated and mutual citations join two papers
+    /// once, and self-citations are ignored.
+    pub fn k_core_decomposition(&self) -> HashMap<usize, usize> {
+        let index = self.node_index();
+        let n = index.len();
+        let neighbors: Vec<Vec<usize>> = index
+            .vertices()
+            .iter()
+            .map(|&vertex| {
+                self.neighbors(vertex)
+                    .into_iter()
+                    .map(|neighbor| index.index_of(neighbor).unwrap())
+                    .collect()
+            })
+            .collect();
+        let mut degree: Vec<usize> = neighbors.iter().map(Vec::len).collect();
+        let max_degree = degree.iter().copied().max().unwrap_or(0);
+        // The nodes sorted by degree, with `bin[d]` the start of the nodes of degree
+        // `d` and `position` the place of each node
+        let mut bin = vec![0; max_degree + 1];
+        for &d in &degree {
+            bin[d] += 1;
+        }
+        let mut start = 0;
+        for count in bin.iter_mut() {
+            let size = *count;
+            *count = start;
+            start += size;
+        }
+        let mut order = vec![0; n];
+        let mut position = vec![0; n];
+        for (node, &d) in degree.iter().enumerate() {
+            position[node] = bin[d];
+            order[bin[d]] = node;
+            bin[d] += 1;
+        }
+        for d in (1..=max_degree).rev() {
+            bin[d] = bin[d - 1];
+        }
+        if let Some(first) = bin.first_mut() {
+            *first = 0;
+        }
+        // Removing a node lowers the degree of each neighbor with a larger degree by
+        // moving the neighbor to the front of its bin and shrinking the bin
+        for i in 0..n {
+            let node = order[i];
+            for &neighbor in &neighbors[node] {
+                if degree[neighbor] > degree[node] {
+                    let d = degree[neighbor];
+                    let front = order[bin[d]];
+                    if front != neighbor {
+                        order.swap(position[neighbor], bin[d]);
+                        position[front] = position[neighbor];
+                        position[neighbor] = bin[d];
+                    }
+                    bin[d] += 1;
+                    degree[neighbor] -= 1;
+                }
+            }
+        }
+        index.vertices().iter().copied().zip(degree).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_graphs::{complete_dag, path, star, zachary_like};
+
+    /// Returns the coreness of every node by removing nodes of degree below `k` for
+    /// increasing `k`
+    fn naive_coreness(network: &CitationNetwork) -> HashMap<usize, usize> {
+        let mut coreness = HashMap::new();
+        let mut remaining = network.clone();
+        let mut k = 0;
+        while remaining.size() > 0 {
+            loop {
+                let low: Vec<usize> = remaining
+                    .nodes_sorted()
+                    .into_iter()
+                    .filter(|&vertex| remaining.neighbors(vertex).len() <= k)
+                    .collect();
+                if low.is_empty() {
+                    break;
+                }
+                for vertex in low {
+                    coreness.insert(vertex, k);
+                    remaining.remove_node(vertex);
+                }
+            }
+            k += 1;
+        }
+        coreness
+    }
+
+    #[test]
+    fn test_k_core_decomposition() {
+        // A complete network of 4 papers with a tail of two papers and one isolated
+        // paper
+        let mut network = complete_dag(4);
+        network.add_edge(4, 3);
+        network.add_edge(5, 4);
+        network.add_edge(5, 4);
+        network.add_edge(6, 6);
+        let coreness = network.k_core_decomposition();
+        let expected: HashMap<usize, usize> =
+            [(0, 3), (1, 3), (2, 3), (3, 3), (4, 1), (5, 1), (6, 0)].into();
+        assert_eq!(coreness, expected);
+        assert!(CitationNetwork::new().k_core_decomposition().is_empty());
+    }
+
+    #[test]
+    fn test_k_core_decomposition_of_examples() {
+        assert!(path(5).k_core_decomposition().values().all(|&k| k == 1));
+        assert!(star(4).k_core_decomposition().values().all(|&k| k == 1));
+        let network = zachary_like();
+        let coreness = network.k_core_decomposition();
+        assert_eq!(coreness, naive_coreness(&network));
+        assert_eq!(coreness.values().max(), Some(&4));
+    }
+}
@@ -0,0 +1,460 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+
+use crate::centrality::centrality::{Centrality, CentralityRank};
+use crate::network::CitationNetwork;
+use crate::random::Random;
+
+/// The cohesion of a single community
+///
+/// The vertex of a community centrality is the id of the community, and its score
+/// is the fraction of all citations in the network that are inside the community.
+#[derive(Clone)]
+pub struct CommunityCentrality {
+    community: usize,
+    internal_fraction: f64,
+}
+
+impl CommunityCentrality {
+    pub fn new(community: usize, internal_fraction: f64) -> CommunityCentrality {
+        CommunityCentrality {
+            community,
+            internal_fraction,
+        }
+    }
+}
+
+impl PartialOrd for CommunityCentrality {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.internal_fraction.partial_cmp(&other.internal_fraction)
+    }
+}
+
+const EPSILON: f64 = 1e-12;
+
+impl PartialEq<Self> for CommunityCentrality {
+    fn eq(&self, other: &Self) -> bool {
+        (self.internal_fraction - other.internal_fraction).abs() <= EPSILON
+    }
+}
+
+impl Display for CommunityCentrality {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "community {}: internal citations {}",
+            self.community, self.internal_fraction
+        )
+    }
+}
+
+impl Centrality<f64> for CommunityCentrality {
+    fn vertex(&self) -> usize {
+        self.community
+    }
+
+    fn score(&self) -> f64 {
+        self.internal_fraction
+    }
+}
+
+/// The communities found by the Louvain algorithm, with the citations inside each
+pub struct LouvainResult {
+    /// The community of every node, numbered from 0
+    pub communities: HashMap<usize, usize>,
+    /// The number of citations between distinct papers of each community
+    internal: BTreeMap<usize, usize>,
+    /// The number of citations between distinct papers
+    num_edges: usize,
+}
+
+impl LouvainResult {
+    /// Counts the citations inside each community of a partition of a network
+    ///
+    /// Citations are counted as in [`CitationNetwork::modularity`]: repeated
+    /// citations count once each and self-citations are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The partitioned network
+    /// * `communities` - The community of every node
+    ///
+    /// # Panics
+    ///
+    /// Panics if a node of the network has no community.
+    pub fn new(network: &CitationNetwork, communities: HashMap<usize, usize>) -> LouvainResult {
+        let community = |vertex: usize| {
+            *communities
+                .get(&vertex)
+                .unwrap_or_else(|| panic!("vertex {} has no community", vertex))
+        };
+        let mut internal: BTreeMap<usize, usize> = BTreeMap::new();
+        for vertex in network.nodes_sorted() {
+            internal.entry(community(vertex)).or_insert(0);
+        }
+        let mut num_edges = 0;
+        for (from, to) in network.edges_iter() {
+            if from == to {
+                continue;
+            }
+            num_edges += 1;
+            if community(from) == community(to) {
+                *internal.get_mut(&community(from)).unwrap() += 1;
+            }
+        }
+        LouvainResult {
+            communities,
+            internal,
+            num_edges,
+        }
+    }
+    /// Returns the number of communities
+    pub fn num_communities(&self) -> usize {
+        self.internal.len()
+    }
+    /// Ranks the communities by the fraction of the citations of the network that
+    /// are inside them
+    ///
+    /// Ties are broken by community id. Every community scores 0 in a network
+    /// without citations between distinct papers.
+    pub fn as_community_centrality_rank(&self) -> CentralityRank<f64, CommunityCentrality> {
+        let mut ranks: Vec<CommunityCentrality> = self
+            .internal
+            .iter()
+            .map(|(&community, &internal)| {
+                let fraction = if self.num_edges == 0 {
+                    0.0
+                } else {
+                    internal as f64 / self.num_edges as f64
+                };
+                CommunityCentrality::new(community, fraction)
+            })
+            .collect();
+        ranks.sort_by(|a, b| {
+            b.partial_cmp(a)
+                .unwrap()
+                .then(a.community.cmp(&b.community))
+        });
+        CentralityRank::new(ranks)
+    }
+}
+
+/// An undirected weighted graph over the nodes `0..n` of one level of the Louvain
+/// algorithm
+struct WeightedGraph {
+    /// The neighbors of each node other than itself, sorted by index, with the total
+    /// weight of the edges to them
+    adjacency: Vec<Vec<(usize, f64)>>,
+    /// The total weight of the edges from each node to itself
+    loops: Vec<f64>,
+}
+
+impl WeightedGraph {
+    /// Builds the graph of a citation network, with one unit of weight per citation
+    /// in either direction and without self-citations
+    fn from_network(network: &CitationNetwork, nodes: &[usize]) -> WeightedGraph {
+        let index: HashMap<usize, usize> = nodes.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+        let mut neighbors = vec![BTreeMap::new(); nodes.len()];
+        for (from, to) in network.edges_iter() {
+            if from != to {
+                let (i, j) = (index[&from], index[&to]);
+                *neighbors[i].entry(j).or_insert(0.0) += 1.0;
+                *neighbors[j].entry(i).or_insert(0.0) += 1.0;
+            }
+        }
+        WeightedGraph {
+            adjacency: neighbors
+                .into_iter()
+                .map(|n| n.into_iter().collect())
+                .collect(),
+            loops: vec![0.0; nodes.len()],
+        }
+    }
+    /// Returns the weighted degree of a node, where a loop counts twice
+    fn degree(&self, node: usize) -> f64 {
+        self.adjacency[node].iter().map(|&(_, w)| w).sum::<f64>() + 2.0 * self.loops[node]
+    }
+    /// Builds the graph with one node for each community, numbered `0..k`
+    fn aggregate(&self, communities: &[usize], k: usize) -> WeightedGraph {
+        let mut neighbors = vec![BTreeMap::new(); k];
+        let mut loops = vec![0.0; k];
+        for (node, &community) in communities.iter().enumerate() {
+            loops[community] += self.loops[node];
+            for &(neighbor, weight) in &self.adjacency[node] {
+                let other = communities[neighbor];
+                if other == community {
+                    // Each internal edge is seen from both of its ends
+                    loops[community] += weight / 2.0;
+                } else {
+                    *neighbors[community].entry(other).or_insert(0.0) += weight;
+                }
+            }
+        }
+        WeightedGraph {
+            adjacency: neighbors
+                .into_iter()
+                .map(|n| n.into_iter().collect())
+                .collect(),
+            loops,
+        }
+    }
+    /// Moves single nodes between communities while that increases the modularity
+    ///
+    /// # Returns
+    ///
+    /// * `communities` - The community of each node, numbered `0..k` in order of first
+    ///   appearance
+    /// * `k` - The number of communities
+    /// * `moved` - Whether any node changed community
+    fn move_nodes(&self, gamma: f64, random: &mut Random) -> (Vec<usize>, usize, bool) {
+        let n = self.adjacency.len();
+        let degrees: Vec<f64> = (0..n).map(|node| self.degree(node)).collect();
+        let two_m: f64 = degrees.iter().sum();
+        let mut communities: Vec<usize> = (0..n).collect();
+        let mut totals = degrees.clone();
+        let mut order: Vec<usize> = (0..n).collect();
+        random.shuffle(&mut order);
+        let mut moved = false;
+        let mut improved = two_m > 0.0;
+        while improved {
+            improved = false;
+            for &node in &order {
+                let current = communities[node];
+                let mut links: BTreeMap<usize, f64> = BTreeMap::from([(current, 0.0)]);
+                for &(neighbor, weight) in &self.adjacency[node] {
+                    *links.entry(communities[neighbor]).or_insert(0.0) += weight;
+                }
+                totals[current] -= degrees[node];
+                // The gain of joining a community, up to terms that do not depend on it
+                let gain = |community: usize, weight: f64| {
+                    weight - gamma * totals[community] * degrees[node] / two_m
+                };
+                let mut best = current;
+                let mut best_gain = gain(current, links[&current]);
+                for (&community, &weight) in &links {
+                    if gain(community, weight) > best_gain {
+                        best = community;
+                        best_gain = gain(community, weight);
+                    }
+                }
+                totals[best] += degrees[node];
+                if best != current {
+                    communities[node] = best;
+                    improved = true;
+                    moved = true;
+                }
+            }
+        }
+        let mut renumbered = HashMap::new();
+        for community in communities.iter_mut() {
+            let next = renumbered.len();
+            *community = *renumbered.entry(*community).or_insert(next);
+        }
+        (communities, renumbered.len(), moved)
+    }
+}
+
+impl CitationNetwork {
+    /// Detects communities with the Louvain algorithm
+    ///
+    /// This is [`CitationNetwork::louvain_with_resolution`] with resolution 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the random number generator
+    pub fn louvain(&self, seed: u64) -> HashMap<usize, usize> {
+        self.louvain_with_resolution(1.0, seed)
+    }
+    /// Detects communities with the Louvain algorithm and counts the citations inside
+    /// them
+    ///
+    /// The communities are those of [`CitationNetwork::louvain`].
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the random number generator
+    pub fn louvain_result(&self, seed: u64) -> LouvainResult {
+        LouvainResult::new(self, self.louvain(seed))
+    }
+    /// Detects communities with the Louvain algorithm at a given resolution
+    ///
+    /// Citations are treated as undirected, with repeated and reciprocal citations
+    /// adding weight and self-citations ignored. The algorithm alternates two phases:
+    /// it first moves single nodes, in a random order, to the neighboring community
+    /// that increases the modularity most, and then merges each community into a
+    /// single node. It stops when no node moves, so it finds a local maximum of the
+    /// modularity in time roughly linear in the number of edges. The same seed always
+    /// gives the same communities.
+    ///
+    /// # Arguments
+    ///
+    /// * `gamma` - The resolution; higher values give more and smaller communities
+    /// * `seed` - The seed of the random number generator
+    ///
+    /// # Returns
+    ///
+    /// * `communities` - The community of every node, numbered from 0 in order of the
+    ///   smallest node in each community
+    ///
+    /// # Panics
+    ///
+    /// Panics if `gamma` is not positive.
+    pub fn louvain_with_resolution(&self, gamma: f64, seed: u64) -> HashMap<usize, usize> {
+        assert!(gamma > 0.0, "resolution must be positive, got {}", gamma);
+        let nodes = self.nodes_sorted();
+        let mut random = Random::new(seed);
+        let mut graph = WeightedGraph::from_network(self, &nodes);
+        // The node of the current level that each original node belongs to
+        let mut membership: Vec<usize> = (0..nodes.len()).collect();
+        loop {
+            let (communities, k, moved) = graph.move_nodes(gamma, &mut random);
+            if !moved {
+                break;
+            }
+            for member in membership.iter_mut() {
+                *member = communities[*member];
+            }
+            graph = graph.aggregate(&communities, k);
+        }
+        // Renumber so that the community of the smallest node comes first
+        let mut renumbered = HashMap::new();
+        nodes
+            .into_iter()
+            .zip(membership)
+            .map(|(vertex, member)| {
+                let next = renumbered.len();
+                (vertex, *renumbered.entry(member).or_insert(next))
+            })
+            .collect()
+    }
+    /// Returns the modularity of a partition of the network into communities
+    ///
+    /// Citations are treated as undirected and weighted as in
+    /// [`CitationNetwork::louvain_with_resolution`]. The modularity is the sum over
+    /// communities of the fraction of edges inside the community minus `gamma` times
+    /// the square of the fraction of edge ends in it. It is 0 for a network without
+    /// edges.
+    ///
+    /// # Arguments
+    ///
+    /// * `communities` - The community of every node
+    /// * `gamma` - The resolution
+    ///
+    /// # Panics
+    ///
+    /// Panics if a node of the network has no community.
+    pub fn modularity(&self, communities: &HashMap<usize, usize>, gamma: f64) -> f64 {
+        let community = |vertex: usize| {
+            *communities
+                .get(&vertex)
+                .unwrap_or_else(|| panic!("vertex {} has no community", vertex))
+        };
+        let mut internal: BTreeMap<usize, f64> = BTreeMap::new();
+        let mut ends: BTreeMap<usize, f64> = BTreeMap::new();
+        let mut m = 0.0;
+        for vertex in self.nodes_sorted() {
+            community(vertex);
+        }
+        for (from, to) in self.edges_iter() {
+            if from == to {
+                continue;
+            }
+            m += 1.0;
+            let (a, b) = (community(from), community(to));
+            *ends.entry(a).or_insert(0.0) += 1.0;
+            *ends.entry(b).or_insert(0.0) += 1.0;
+            if a == b {
+                *internal.entry(a).or_insert(0.0) += 1.0;
+            }
+        }
+        if m == 0.0 {
+            return 0.0;
+        }
+        ends.iter()
+            .map(|(c, &d)| internal.get(c).unwrap_or(&0.0) / m - gamma * (d / (2.0 * m)).powi(2))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_graphs::two_cliques_bridge;
+
+    #[test]
+    fn test_louvain_finds_cliques() {
+        let network = two_cliques_bridge(5);
+        let communities = network.louvain(42);
+        for vertex in 0..10 {
+            assert_eq!(communities[&vertex], vertex / 5);
+        }
+        // 10 of the 21 edges in each clique, with 21 edge ends in each
+        let expected = 2.0 * (10.0 / 21.0 - (21.0_f64 / 42.0).powi(2));
+        assert!((network.modularity(&communities, 1.0) - expected).abs() < 1e-12);
+        for seed in 0..10 {
+            assert_eq!(network.louvain(seed), communities);
+        }
+    }
+
+    #[test]
+    fn test_louvain_resolution() {
+        let network = two_cliques_bridge(5);
+        let merged = network.louvain_with_resolution(0.01, 42);
+        assert!(merged.values().all(|&community| community == 0));
+        let split = network.louvain_with_resolution(10.0, 42);
+        assert!(split.values().max().unwrap() > &1);
+    }
+
+    #[test]
+    fn test_louvain_without_edges() {
+        let mut network = CitationNetwork::new();
+        network.add_node(3);
+        network.add_node(1);
+        network.add_edge(2, 2);
+        let communities = network.louvain(42);
+        assert_eq!(communities, HashMap::from([(1, 0), (2, 1), (3, 2)]));
+        assert_eq!(network.modularity(&communities, 1.0), 0.0);
+        assert!(CitationNetwork::new().louvain(42).is_empty());
+    }
+
+    #[test]
+    fn test_modularity_of_single_community() {
+        let network = two_cliques_bridge(5);
+        let communities: HashMap<usize, usize> = (0..10).map(|vertex| (vertex, 0)).collect();
+        assert!(network.modularity(&communities, 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_community_centrality_rank() {
+        let mut network = two_cliques_bridge(5);
+        // A third community of three papers with two citations between them
+        network.add_edge(10, 11);
+        network.add_edge(11, 12);
+        network.add_edge(10, 4);
+        let result = network.louvain_result(42);
+        assert_eq!(result.num_communities(), 3);
+        assert_eq!(result.communities[&11], 2);
+        let ranks = result.as_community_centrality_rank();
+        assert_eq!(ranks.len(), 3);
+        assert_eq!((ranks[0].vertex(), ranks[1].vertex()), (0, 1));
+        assert!((ranks[0].score() - 10.0 / 24.0).abs() < 1e-12);
+        assert!(ranks[0] == ranks[1]);
+        assert_eq!(ranks[2].vertex(), 2);
+        assert!((ranks[2].score() - 2.0 / 24.0).abs() < 1e-12);
+        assert_eq!(
+            ranks[2].to_string(),
+            format!("community 2: internal citations {}", 2.0 / 24.0)
+        );
+    }
+
+    #[test]
+    fn test_community_centrality_without_edges() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(1, 1);
+        network.add_node(2);
+        let ranks = network.louvain_result(42).as_community_centrality_rank();
+        assert_eq!(ranks.len(), 2);
+        assert!(ranks.iter().all(|c| c.score() == 0.0));
+    }
+}
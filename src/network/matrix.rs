@@ -0,0 +1,332 @@
+use crate::network::index::NodeIndex;
+use crate::network::CitationNetwork;
+
+/// A square matrix of `f64` in compressed sparse row (CSR) format
+///
+/// The entries of row `i` are at positions `row_offsets[i]..row_offsets[i + 1]` of
+/// `columns` and `values`, in ascending order of column, so a product with a vector
+/// reads each row as one contiguous slice.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseMatrix {
+    row_offsets: Vec<usize>,
+    columns: Vec<usize>,
+    values: Vec<f64>,
+}
+
+impl SparseMatrix {
+    /// Returns the number of rows, which is also the number of columns
+    pub fn size(&self) -> usize {
+        self.row_offsets.len() - 1
+    }
+    /// Returns the number of stored entries
+    pub fn num_entries(&self) -> usize {
+        self.values.len()
+    }
+    /// Returns the `(column, value)` entries of a row in ascending order of column
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row is out of bounds.
+    pub fn row(&self, row: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let range = self.row_offsets[row]..self.row_offsets[row + 1];
+        self.columns[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.values[range].iter().copied())
+    }
+    /// Multiplies the matrix by a vector
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` does not have one value per column.
+    pub fn matvec(&self, v: &[f64]) -> Vec<f64> {
+        assert_eq!(v.len(), self.size(), "expected one value per column");
+        self.row_offsets
+            .windows(2)
+            .map(|range| {
+                self.columns[range[0]..range[1]]
+                    .iter()
+                    .zip(&self.values[range[0]..range[1]])
+                    .map(|(&col, &value)| value * v[col])
+                    .sum()
+            })
+            .collect()
+    }
+    /// Returns the sum of the entries of each column
+    pub fn column_sums(&self) -> Vec<f64> {
+        let mut sums = vec![0.0; self.size()];
+        for (&col, &value) in self.columns.iter().zip(&self.values) {
+            sums[col] += value;
+        }
+        sums
+    }
+}
+
+impl CitationNetwork {
+    /// Returns the adjacency matrix of the network in coordinate (COO) format
+    ///
+    /// Rows and columns are the compact indices given by [`CitationNetwork::node_index`],
+    /// and an entry at `(i, j)` means paper `i` cites paper `j`. Entries are sorted by
+    /// row and then by column, and repeated citations are stored once.
+    ///
+    /// # Returns
+    ///
+    /// * `(row_indices, col_indices, values)` - The coordinates and values of the entries
+    pub fn to_sparse_adjacency_matrix(&self) -> (Vec<usize>, Vec<usize>, Vec<u8>) {
+        let index = self.node_index();
+        let mut entries: Vec<(usize, usize)> = Vec::with_capacity(self.num_edges());
+        for (row, &vertex) in index.vertices().iter().enumerate() {
+            let cols = self.adjacency_row(&index, vertex);
+            entries.extend(cols.into_iter().map(|col| (row, col)));
+        }
+        let values = vec![1; entries.len()];
+        let (rows, cols) = entries.into_iter().unzip();
+        (rows, cols, values)
+    }
+    /// Multiplies the adjacency matrix of the network by a vector
+    ///
+    /// Entry `i` of the result is the sum of `v[j]` over the papers `j` that paper `i`
+    /// cites, with repeated citations counted once, as in
+    /// [`CitationNetwork::to_sparse_adjacency_matrix`]. The product is computed from
+    /// the adjacency lists without building the matrix. The index is taken from the
+    /// caller, so that an iterative method building it once pays only O(V + E) per
+    /// product.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The compact indices of the network's nodes, as given by
+    ///   [`CitationNetwork::node_index`]
+    /// * `v` - A value for each node, at its compact index
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` does not have one value per node.
+    pub fn adj_matvec(&self, index: &NodeIndex, v: &[f64]) -> Vec<f64> {
+        assert_eq!(v.len(), index.len(), "expected one value per node");
+        index
+            .vertices()
+            .iter()
+            .map(|&vertex| {
+                self.adjacency_row(index, vertex)
+                    .into_iter()
+                    .map(|col| v[col])
+                    .sum()
+            })
+            .collect()
+    }
+    /// Multiplies the transpose of the adjacency matrix of the network by a vector
+    ///
+    /// Entry `j` of the result is the sum of `v[i]` over the papers `i` that cite
+    /// paper `j`, with repeated citations counted once.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The compact indices of the network's nodes, as given by
+    ///   [`CitationNetwork::node_index`]
+    /// * `v` - A value for each node, at its compact index
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` does not have one value per node.
+    pub fn adj_t_matvec(&self, index: &NodeIndex, v: &[f64]) -> Vec<f64> {
+        assert_eq!(v.len(), index.len(), "expected one value per node");
+        let mut product = vec![0.0; index.len()];
+        for (row, &vertex) in index.vertices().iter().enumerate() {
+            for col in self.adjacency_row(index, vertex) {
+                product[col] += v[row];
+            }
+        }
+        product
+    }
+    /// Returns the sorted, distinct compact indices of the papers a paper cites
+    fn adjacency_row(&self, index: &NodeIndex, vertex: usize) -> Vec<usize> {
+        let mut cols: Vec<usize> = self
+            .out_edges_from(vertex)
+            .map(|&to| index.index_of(to).unwrap())
+            .collect();
+        cols.sort_unstable();
+        cols.dedup();
+        cols
+    }
+    /// Returns the transition matrix of a random walk along the citations of the
+    /// network
+    ///
+    /// The entry at `(j, i)` is the probability that a walker at paper `i` moves to
+    /// paper `j`, which is the share of the citations of `i` that go to `j`, so a
+    /// repeated citation is followed more often. Rows and columns are the compact
+    /// indices given by [`CitationNetwork::node_index`]. Each row holds the citers of a
+    /// paper, and every column sums to 1, except the columns of papers without
+    /// references, which are empty.
+    pub fn build_transition_matrix(&self) -> SparseMatrix {
+        self.build_transition_matrix_with(|from, _| 1.0 / self.out_degree(from) as f64)
+    }
+    /// Returns the transition matrix of a random walk with the given probability of
+    /// following each citation
+    ///
+    /// The entry at `(j, i)` is the sum of `share(i, j)` over the citations from `i`
+    /// to `j`, so a repeated citation adds its share once per occurrence. The columns
+    /// sum to 1 only if the shares of the citations of each paper do.
+    ///
+    /// # Arguments
+    ///
+    /// * `share` - The probability that a walker follows a citation, given the ids of
+    ///   the citing and cited papers
+    pub fn build_transition_matrix_with<F: Fn(usize, usize) -> f64>(
+        &self,
+        share: F,
+    ) -> SparseMatrix {
+        let index = self.node_index();
+        let mut row_offsets = Vec::with_capacity(index.len() + 1);
+        let mut columns = Vec::with_capacity(self.num_edges());
+        let mut values = Vec::with_capacity(self.num_edges());
+        row_offsets.push(0);
+        let mut row: Vec<(usize, f64)> = Vec::new();
+        for &vertex in index.vertices() {
+            row.clear();
+            row.extend(
+                self.in_edges_to(vertex)
+                    .map(|&citer| (index.index_of(citer).unwrap(), share(citer, vertex))),
+            );
+            row.sort_unstable_by_key(|&(col, _)| col);
+            for &(col, value) in &row {
+                if columns.len() > *row_offsets.last().unwrap() && columns.last() == Some(&col) {
+                    *values.last_mut().unwrap() += value;
+                } else {
+                    columns.push(col);
+                    values.push(value);
+                }
+            }
+            row_offsets.push(columns.len());
+        }
+        SparseMatrix {
+            row_offsets,
+            columns,
+            values,
+        }
+    }
+    /// Returns the dense adjacency matrix of the network
+    ///
+    /// Rows and columns are the compact indices given by [`CitationNetwork::node_index`].
+    /// The matrix has `n * n` entries, so this is only practical for small networks.
+    #[cfg(feature = "ndarray")]
+    pub fn to_adjacency_matrix(&self) -> ndarray::Array2<u8> {
+        let index = self.node_index();
+        let mut matrix = ndarray::Array2::zeros((index.len(), index.len()));
+        for (row, &vertex) in index.vertices().iter().enumerate() {
+            for &to in self.out_edges_from(vertex) {
+                matrix[[row, index.index_of(to).unwrap()]] = 1;
+            }
+        }
+        matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_network() -> CitationNetwork {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(30, 10);
+        graph.add_edge(30, 20);
+        graph.add_edge(20, 10);
+        graph.add_edge(40, 30);
+        graph.add_edge(40, 30);
+        graph
+    }
+
+    #[test]
+    fn test_sparse_adjacency_matrix_round_trip() {
+        let graph = build_network();
+        let (rows, cols, values) = graph.to_sparse_adjacency_matrix();
+        assert_eq!(rows, vec![1, 2, 2, 3]);
+        assert_eq!(cols, vec![0, 0, 1, 2]);
+        assert_eq!(values, vec![1, 1, 1, 1]);
+        let index = graph.node_index();
+        let mut restored = CitationNetwork::new();
+        for (&row, &col) in rows.iter().zip(cols.iter()) {
+            restored.add_edge(index.vertex_at(row), index.vertex_at(col));
+        }
+        assert_eq!(restored.size(), graph.size());
+        for &vertex in graph.nodes() {
+            let mut expected: Vec<_> = graph.out_edges_from(vertex).copied().collect();
+            expected.sort_unstable();
+            expected.dedup();
+            let mut actual: Vec<_> = restored.out_edges_from(vertex).copied().collect();
+            actual.sort_unstable();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_adjacency_matrix_vector_products() {
+        let graph = build_network();
+        // Nodes 10, 20, 30 and 40 are at indices 0 to 3
+        let index = graph.node_index();
+        let v = [1.0, 2.0, 4.0, 8.0];
+        assert_eq!(graph.adj_matvec(&index, &v), vec![0.0, 1.0, 3.0, 4.0]);
+        assert_eq!(graph.adj_t_matvec(&index, &v), vec![6.0, 4.0, 8.0, 0.0]);
+        let (rows, cols, _) = graph.to_sparse_adjacency_matrix();
+        let mut expected = vec![0.0; 4];
+        let mut expected_t = vec![0.0; 4];
+        for (&row, &col) in rows.iter().zip(cols.iter()) {
+            expected[row] += v[col];
+            expected_t[col] += v[row];
+        }
+        assert_eq!(graph.adj_matvec(&index, &v), expected);
+        assert_eq!(graph.adj_t_matvec(&index, &v), expected_t);
+    }
+
+    #[test]
+    fn test_transition_matrix() {
+        let graph = build_network();
+        let matrix = graph.build_transition_matrix();
+        assert_eq!(matrix.size(), 4);
+        assert_eq!(matrix.num_entries(), 4);
+        // Paper 10 is cited by 20 and by 30, which splits its citations in two
+        assert_eq!(matrix.row(0).collect::<Vec<_>>(), vec![(1, 1.0), (2, 0.5)]);
+        // Both citations of 40 go to 30 and are merged into one entry
+        assert_eq!(matrix.row(2).collect::<Vec<_>>(), vec![(3, 1.0)]);
+        assert_eq!(matrix.row(3).count(), 0);
+        // Paper 10 cites nothing, so its column is empty
+        assert_eq!(matrix.column_sums(), vec![0.0, 1.0, 1.0, 1.0]);
+        assert_eq!(
+            matrix.matvec(&[1.0, 2.0, 4.0, 8.0]),
+            vec![4.0, 2.0, 8.0, 0.0]
+        );
+        let empty = CitationNetwork::new().build_transition_matrix();
+        assert_eq!(empty.size(), 0);
+        assert!(empty.matvec(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_transition_matrix_with_shares() {
+        let graph = build_network();
+        let matrix = graph.build_transition_matrix_with(|from, to| (from + to) as f64);
+        assert_eq!(
+            matrix.row(0).collect::<Vec<_>>(),
+            vec![(1, 30.0), (2, 40.0)]
+        );
+        assert_eq!(matrix.row(2).collect::<Vec<_>>(), vec![(3, 140.0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected one value per node")]
+    fn test_adj_matvec_rejects_wrong_length() {
+        let graph = build_network();
+        graph.adj_matvec(&graph.node_index(), &[1.0]);
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_adjacency_matrix() {
+        let graph = build_network();
+        let matrix = graph.to_adjacency_matrix();
+        let (rows, cols, _) = graph.to_sparse_adjacency_matrix();
+        assert_eq!(matrix.shape(), &[4, 4]);
+        assert_eq!(matrix.sum() as usize, rows.len());
+        for (&row, &col) in rows.iter().zip(cols.iter()) {
+            assert_eq!(matrix[[row, col]], 1);
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+use crate::network::builder::{BuildStats, CitationNetworkBuilder};
+use crate::network::index::NodeIndex;
+use crate::network::{CitationNetwork, LoadError};
+
+/// The header line written by [`CitationNetwork::to_matrix_market`]
+const HEADER: &str = "%%MatrixMarket matrix coordinate integer general";
+
+/// Parses a 1-based Matrix Market index into a 0-based one
+fn parse_index(text: &str, size: usize, line: usize) -> Result<usize, LoadError> {
+    match text.parse::<usize>() {
+        Ok(index) if (1..=size).contains(&index) => Ok(index - 1),
+        _ => Err(LoadError::parse(
+            line,
+            format!(
+                "invalid index {}: expected an integer in [1, {}]",
+                text, size
+            ),
+        )),
+    }
+}
+
+impl CitationNetwork {
+    /// Writes the adjacency matrix of the network in the Matrix Market format
+    ///
+    /// The matrix is written in coordinate format with 1-based indices, as read by
+    /// `scipy.io.mmread` and Julia's `MatrixMarket` package. Row and column `i` stand
+    /// for the paper at index `i - 1` of the returned mapping, and an entry at
+    /// `(i, j)` means paper `i` cites paper `j`. Repeated citations are written once.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the matrix
+    ///
+    /// # Returns
+    ///
+    /// * `index` - The mapping between paper ids and 0-based matrix indices
+    pub fn to_matrix_market<W: Write>(&self, mut writer: W) -> io::Result<NodeIndex> {
+        let index = self.node_index();
+        let (rows, cols, values) = self.to_sparse_adjacency_matrix();
+        writeln!(writer, "{}", HEADER)?;
+        writeln!(writer, "{} {} {}", index.len(), index.len(), values.len())?;
+        for ((row, col), value) in rows.into_iter().zip(cols).zip(values) {
+            writeln!(writer, "{} {} {}", row + 1, col + 1, value)?;
+        }
+        Ok(index)
+    }
+    /// Loads a network from a square matrix in the Matrix Market coordinate format
+    ///
+    /// Paper ids are the 0-based matrix indices; use the mapping returned by
+    /// [`CitationNetwork::to_matrix_market`] to recover the original ids. Every row
+    /// is a node, and every nonzero entry at `(i, j)` is a citation from `i` to `j`.
+    /// Integer, real and pattern matrices are accepted, and a symmetric matrix gets a
+    /// citation in each direction. Comment lines starting with `%` are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to load from
+    pub fn load_from_matrix_market<R: Read>(reader: R) -> Result<CitationNetwork, LoadError> {
+        Ok(CitationNetwork::load_from_matrix_market_with(reader, CitationNetworkBuilder::new())?.0)
+    }
+    /// Loads a network from a square matrix in the Matrix Market coordinate format
+    /// through a builder
+    ///
+    /// See [`CitationNetwork::load_from_matrix_market`]; the options of the builder
+    /// decide which edges are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to load from
+    /// * `builder` - The builder to add the nodes and edges to
+    pub fn load_from_matrix_market_with<R: Read>(
+        reader: R,
+        mut builder: CitationNetworkBuilder,
+    ) -> Result<(CitationNetwork, BuildStats), LoadError> {
+        let mut lines = BufReader::new(reader)
+            .lines()
+            .enumerate()
+            .map(|(index, line)| line.map(|line| (index + 1, line)));
+        let (_, header) = lines
+            .next()
+            .transpose()?
+            .ok_or_else(|| LoadError::parse(1, "empty input"))?;
+        let fields: Vec<String> = header.split_whitespace().map(str::to_lowercase).collect();
+        let fields: Vec<&str> = fields.iter().map(String::as_str).collect();
+        let (pattern, symmetric) = match fields.as_slice() {
+            ["%%matrixmarket", "matrix", "coordinate", field, symmetry] => {
+                let pattern = match *field {
+                    "integer" | "real" => false,
+                    "pattern" => true,
+                    _ => return Err(LoadError::parse(1, format!("unsupported field {}", field))),
+                };
+                let symmetric = match *symmetry {
+                    "general" => false,
+                    "symmetric" => true,
+                    _ => {
+                        return Err(LoadError::parse(
+                            1,
+                            format!("unsupported symmetry {}", symmetry),
+                        ))
+                    }
+                };
+                (pattern, symmetric)
+            }
+            _ => return Err(LoadError::parse(1, "expected a coordinate matrix header")),
+        };
+        let mut size = None;
+        let mut expected = 0;
+        let mut entries = 0;
+        let mut last_line = 1;
+        for line in lines {
+            let (number, line) = line?;
+            last_line = number;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('%') {
+                continue;
+            }
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let Some(size) = size else {
+                let dimensions: Vec<usize> = tokens.iter().filter_map(|t| t.parse().ok()).collect();
+                match dimensions.as_slice() {
+                    [rows, cols, nonzeros] if tokens.len() == 3 && rows == cols => {
+                        size = Some(*rows);
+                        expected = *nonzeros;
+                        for vertex in 0..*rows {
+                            builder.add_node(vertex);
+                        }
+                    }
+                    _ => {
+                        return Err(LoadError::parse(
+                            number,
+                            "expected the dimensions of a square matrix",
+                        ))
+                    }
+                }
+                continue;
+            };
+            let values = if pattern { 2 } else { 3 };
+            if tokens.len() != values {
+                return Err(LoadError::parse(
+                    number,
+                    format!("expected {} values, found {}", values, tokens.len()),
+                ));
+            }
+            let row = parse_index(tokens[0], size, number)?;
+            let col = parse_index(tokens[1], size, number)?;
+            entries += 1;
+            if !pattern {
+                let value: f64 = tokens[2].parse().map_err(|_| {
+                    LoadError::parse(number, format!("invalid value {}", tokens[2]))
+                })?;
+                if value == 0.0 {
+                    continue;
+                }
+            }
+            builder.add_edge(row, col);
+            if symmetric && row != col {
+                builder.add_edge(col, row);
+            }
+        }
+        if size.is_none() {
+            return Err(LoadError::parse(last_line, "missing the matrix dimensions"));
+        }
+        if entries != expected {
+            return Err(LoadError::parse(
+                last_line,
+                format!("expected {} entries, found {}", expected, entries),
+            ));
+        }
+        Ok(builder.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_matrix_market() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(9207016, 9201015);
+        network.add_edge(9207016, 9201015);
+        network.add_edge(9201015, 7);
+        network.add_node(42);
+        let mut output = Vec::new();
+        let index = network.to_matrix_market(&mut output).unwrap();
+        assert_eq!(index.vertices(), &[7, 42, 9201015, 9207016]);
+        assert_eq!(
+            String::from_utf8(output.clone()).unwrap(),
+            "%%MatrixMarket matrix coordinate integer general\n4 4 2\n3 1 1\n4 3 1\n"
+        );
+        let loaded = CitationNetwork::load_from_matrix_market(output.as_slice()).unwrap();
+        assert_eq!(loaded.size(), 4);
+        let edges: Vec<(usize, usize)> = loaded
+            .edges_iter()
+            .map(|(from, to)| (index.vertex_at(from), index.vertex_at(to)))
+            .collect();
+        assert_eq!(edges, vec![(9201015, 7), (9207016, 9201015)]);
+    }
+
+    #[test]
+    fn test_load_from_matrix_market_variants() {
+        let symmetric = "%%MatrixMarket matrix coordinate pattern symmetric\n\
+                         % written by hand\n\
+                         3 3 2\n\
+                         2 1\n\
+                         3 3\n";
+        let network = CitationNetwork::load_from_matrix_market(symmetric.as_bytes()).unwrap();
+        assert_eq!(
+            network.edges_iter().collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0), (2, 2)]
+        );
+        let real = "%%MatrixMarket matrix coordinate real general\n2 2 2\n1 2 0.5\n2 1 0\n";
+        let network = CitationNetwork::load_from_matrix_market(real.as_bytes()).unwrap();
+        assert_eq!(network.edges_iter().collect::<Vec<_>>(), vec![(0, 1)]);
+        let builder = CitationNetworkBuilder::new().drop_self_loops(true);
+        let (network, stats) =
+            CitationNetwork::load_from_matrix_market_with(symmetric.as_bytes(), builder).unwrap();
+        assert_eq!(network.size(), 3);
+        assert_eq!(
+            network.edges_iter().collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(stats.loops_dropped, 1);
+    }
+
+    #[test]
+    fn test_load_malformed_matrix_market() {
+        let error = |text: &str| {
+            CitationNetwork::load_from_matrix_market(text.as_bytes())
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error("%%MatrixMarket matrix array real general\n2 2\n"),
+            "line 1: expected a coordinate matrix header"
+        );
+        assert_eq!(
+            error("%%MatrixMarket matrix coordinate integer general\n2 3 1\n1 1 1\n"),
+            "line 2: expected the dimensions of a square matrix"
+        );
+        assert_eq!(
+            error("%%MatrixMarket matrix coordinate integer general\n2 2 1\n3 1 1\n"),
+            "line 3: invalid index 3: expected an integer in [1, 2]"
+        );
+        assert_eq!(
+            error("%%MatrixMarket matrix coordinate integer general\n2 2 2\n1 2 1\n"),
+            "line 3: expected 2 entries, found 1"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::io::{self, BufWriter, Write};
+
+use crate::network::CitationNetwork;
+
+impl CitationNetwork {
+    /// Writes the adjacency of the network as newline-delimited JSON
+    ///
+    /// Each paper is written on its own line as
+    /// `{"paper": 9905111, "cited_by": [...], "cites": [...]}`, in order of id, with
+    /// both lists in ascending order and a repeated citation listed once per
+    /// occurrence. The output can be read in Python one paper at a time with
+    /// `[json.loads(line) for line in file]` or `pandas.read_json(file, lines=True)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the lines
+    pub fn to_ndjson<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.to_ndjson_with(writer, None)
+    }
+    /// Writes the adjacency of the network as newline-delimited JSON with a score for
+    /// each paper
+    ///
+    /// When `scores` is given, each paper with a score gets an extra `score` field
+    /// after its lists. A score that is not finite has no JSON number and is written
+    /// as `null`. The ids are written to the output one at a time, so a paper with a
+    /// long list of citations needs no more memory than the list itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the lines
+    /// * `scores` - The centrality score of each paper, if available
+    pub fn to_ndjson_with<W: Write>(
+        &self,
+        writer: W,
+        scores: Option<&HashMap<usize, f64>>,
+    ) -> io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        for vertex in self.nodes_sorted() {
+            write!(writer, "{{\"paper\": {}, \"cited_by\": ", vertex)?;
+            write_ids(&mut writer, self.in_edges_to(vertex))?;
+            write!(writer, ", \"cites\": ")?;
+            write_ids(&mut writer, self.out_edges_from(vertex))?;
+            if let Some(&score) = scores.and_then(|scores| scores.get(&vertex)) {
+                if score.is_finite() {
+                    write!(writer, ", \"score\": {}", score)?;
+                } else {
+                    write!(writer, ", \"score\": null")?;
+                }
+            }
+            writeln!(writer, "}}")?;
+        }
+        writer.flush()
+    }
+}
+
+/// Writes ids as a JSON array in ascending order
+fn write_ids<'a, W: Write>(writer: &mut W, ids: impl Iterator<Item = &'a usize>) -> io::Result<()> {
+    let mut ids: Vec<usize> = ids.copied().collect();
+    ids.sort_unstable();
+    write!(writer, "[")?;
+    for (i, id) in ids.into_iter().enumerate() {
+        if i > 0 {
+            write!(writer, ", ")?;
+        }
+        write!(writer, "{}", id)?;
+    }
+    write!(writer, "]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    /// Paper 9905111 cites 2 and 3 and is cited twice by 11, and paper 4 has no
+    /// citations
+    fn build_network() -> CitationNetwork {
+        let mut network: CitationNetwork = [(9905111, 3), (9905111, 2), (11, 9905111), (3, 2)]
+            .into_iter()
+            .collect();
+        network.add_edge(11, 9905111);
+        network.add_node(4);
+        network
+    }
+
+    fn parse_lines(output: &[u8]) -> Vec<Value> {
+        std::str::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    fn ids(value: &Value) -> Vec<u64> {
+        value
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|id| id.as_u64().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_to_ndjson() {
+        let mut output = Vec::new();
+        build_network().to_ndjson(&mut output).unwrap();
+        assert!(output.ends_with(b"}\n"));
+        let lines = parse_lines(&output);
+        let papers: Vec<u64> = lines
+            .iter()
+            .map(|line| line["paper"].as_u64().unwrap())
+            .collect();
+        assert_eq!(papers, [2, 3, 4, 11, 9905111]);
+        let expected: [(&[u64], &[u64]); 5] = [
+            (&[3, 9905111], &[]),
+            (&[9905111], &[2]),
+            (&[], &[]),
+            (&[], &[9905111, 9905111]),
+            (&[11, 11], &[2, 3]),
+        ];
+        for (line, (cited_by, cites)) in lines.iter().zip(expected) {
+            assert_eq!(line.as_object().unwrap().len(), 3);
+            assert_eq!(ids(&line["cited_by"]), cited_by);
+            assert_eq!(ids(&line["cites"]), cites);
+        }
+        assert_eq!(
+            std::str::from_utf8(&output).unwrap().lines().last(),
+            Some(r#"{"paper": 9905111, "cited_by": [11, 11], "cites": [2, 3]}"#)
+        );
+    }
+
+    #[test]
+    fn test_to_ndjson_with_scores() {
+        let scores = HashMap::from([(2, 0.5), (3, 0.125), (11, f64::NAN), (9905111, 2.0)]);
+        let mut output = Vec::new();
+        build_network()
+            .to_ndjson_with(&mut output, Some(&scores))
+            .unwrap();
+        let lines = parse_lines(&output);
+        assert_eq!(lines[0]["score"].as_f64(), Some(0.5));
+        assert_eq!(lines[1]["score"].as_f64(), Some(0.125));
+        // Paper 4 has no score
+        assert!(lines[2].get("score").is_none());
+        assert!(lines[3]["score"].is_null());
+        assert_eq!(lines[4]["score"].as_f64(), Some(2.0));
+        assert_eq!(ids(&lines[4]["cited_by"]), [11, 11]);
+    }
+
+    #[test]
+    fn test_empty_network_to_ndjson() {
+        let mut output = Vec::new();
+        CitationNetwork::new().to_ndjson(&mut output).unwrap();
+        assert!(output.is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+use std::io::Write;
+
+use serde_json::{json, Map, Value};
+
+use crate::metadata::MetadataStore;
+use crate::network::CitationNetwork;
+
+impl CitationNetwork {
+    /// Writes the network in the node-link JSON format of NetworkX
+    ///
+    /// The output can be loaded in Python with
+    /// `networkx.node_link_graph(json.load(file), edges="links")`. Node ids are the
+    /// vertex ids, nodes are written in order of id and links in the order of
+    /// [`CitationNetwork::edges_iter`]. A network with repeated citations is marked
+    /// as a multigraph, so NetworkX keeps every copy.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the JSON
+    pub fn to_networkx_json<W: Write>(&self, writer: W) -> serde_json::Result<()> {
+        self.to_networkx_json_with(writer, None)
+    }
+    /// Writes the network in the node-link JSON format with the metadata of the
+    /// papers
+    ///
+    /// When `metadata` is given, each paper with a known title or date gets a
+    /// `title` or `date` attribute in its node object.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the JSON
+    /// * `metadata` - The titles and dates of the vertices, if available
+    pub fn to_networkx_json_with<W: Write>(
+        &self,
+        writer: W,
+        metadata: Option<&MetadataStore>,
+    ) -> serde_json::Result<()> {
+        let nodes: Vec<Value> = self
+            .nodes_sorted()
+            .into_iter()
+            .map(|vertex| {
+                let mut node = Map::new();
+                node.insert("id".to_string(), json!(vertex));
+                if let Some(paper) = metadata.and_then(|metadata| metadata.get(vertex)) {
+                    if let Some(title) = &paper.title {
+                        node.insert("title".to_string(), json!(title));
+                    }
+                    if let Some(date) = &paper.date {
+                        node.insert("date".to_string(), json!(date));
+                    }
+                }
+                Value::Object(node)
+            })
+            .collect();
+        let mut seen = HashSet::new();
+        let mut multigraph = false;
+        let links: Vec<Value> = self
+            .edges_iter()
+            .map(|(from, to)| {
+                multigraph |= !seen.insert((from, to));
+                json!({ "source": from, "target": to })
+            })
+            .collect();
+        let graph = json!({
+            "directed": true,
+            "multigraph": multigraph,
+            "graph": {},
+            "nodes": nodes,
+            "links": links,
+        });
+        serde_json::to_writer(writer, &graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_network() -> CitationNetwork {
+        let mut network = CitationNetwork::new();
+        network.add_edge(2, 1);
+        network.add_edge(1, 3);
+        network.add_edge(2, 3);
+        network.add_node(7);
+        network
+    }
+
+    /// Parses the JSON written for a network
+    fn export(network: &CitationNetwork, metadata: Option<&MetadataStore>) -> Value {
+        let mut output = Vec::new();
+        network
+            .to_networkx_json_with(&mut output, metadata)
+            .unwrap();
+        serde_json::from_slice(&output).unwrap()
+    }
+
+    #[test]
+    fn test_networkx_json_round_trip() {
+        let network = build_network();
+        let graph = export(&network, None);
+        assert_eq!(graph["directed"], json!(true));
+        assert_eq!(graph["multigraph"], json!(false));
+        assert_eq!(
+            graph["nodes"],
+            json!([{ "id": 1 }, { "id": 2 }, { "id": 3 }, { "id": 7 }])
+        );
+        let mut loaded = CitationNetwork::new();
+        for node in graph["nodes"].as_array().unwrap() {
+            loaded.add_node(node["id"].as_u64().unwrap() as usize);
+        }
+        for link in graph["links"].as_array().unwrap() {
+            let source = link["source"].as_u64().unwrap() as usize;
+            let target = link["target"].as_u64().unwrap() as usize;
+            loaded.add_edge(source, target);
+        }
+        assert_eq!(loaded.nodes_sorted(), network.nodes_sorted());
+        assert_eq!(
+            loaded.edges_iter().collect::<Vec<_>>(),
+            network.edges_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_networkx_json_with_metadata() {
+        let mut network = build_network();
+        network.add_edge(1, 3);
+        let mut metadata = MetadataStore::new();
+        metadata.set_title(1, "A \"quoted\" title");
+        metadata.set_date(1, "1992-03-31");
+        metadata.set_date(7, "1995-01-01");
+        let graph = export(&network, Some(&metadata));
+        assert_eq!(graph["multigraph"], json!(true));
+        assert_eq!(graph["links"].as_array().unwrap().len(), 4);
+        assert_eq!(
+            graph["nodes"][0],
+            json!({ "id": 1, "title": "A \"quoted\" title", "date": "1992-03-31" })
+        );
+        assert_eq!(graph["nodes"][1], json!({ "id": 2 }));
+        assert_eq!(graph["nodes"][3], json!({ "id": 7, "date": "1995-01-01" }));
+    }
+}
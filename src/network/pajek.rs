@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+
+use crate::network::builder::{BuildStats, CitationNetworkBuilder};
+use crate::network::{CitationNetwork, LoadError};
+
+/// The section of a Pajek file being read
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    /// Before the `*Vertices` line
+    Start,
+    Vertices,
+    Arcs,
+    Edges,
+}
+
+/// A citation network whose papers have labels, as loaded from a Pajek file
+#[derive(Clone, Default)]
+pub struct LabeledCitationNetwork {
+    network: CitationNetwork,
+    labels: HashMap<usize, String>,
+}
+
+impl LabeledCitationNetwork {
+    /// Loads a network together with its vertex labels from the Pajek `.net` format
+    ///
+    /// See [`CitationNetwork::load_from_pajek`].
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to load from
+    pub fn load_from_pajek<R: Read>(reader: R) -> Result<LabeledCitationNetwork, LoadError> {
+        Ok(LabeledCitationNetwork::load_from_pajek_with(reader, CitationNetworkBuilder::new())?.0)
+    }
+    /// Loads a network together with its vertex labels from the Pajek `.net` format
+    /// through a builder
+    ///
+    /// See [`CitationNetwork::load_from_pajek`]; the options of the builder decide
+    /// which edges are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to load from
+    /// * `builder` - The builder to add the nodes and edges to
+    pub fn load_from_pajek_with<R: Read>(
+        reader: R,
+        mut builder: CitationNetworkBuilder,
+    ) -> Result<(LabeledCitationNetwork, BuildStats), LoadError> {
+        let mut labels = HashMap::new();
+        let mut section = Section::Start;
+        let mut size = 0;
+        for (index, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('%') {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('*') {
+                let mut fields = header.split_whitespace();
+                let name = fields.next().unwrap_or("").to_lowercase();
+                section = match (name.as_str(), section) {
+                    ("vertices", Section::Start) => {
+                        let count = fields.next().unwrap_or("");
+                        size = count.parse().map_err(|_| {
+                            LoadError::parse(number, format!("invalid vertex count {}", count))
+                        })?;
+                        for vertex in 1..=size {
+                            builder.add_node(vertex);
+                        }
+                        Section::Vertices
+                    }
+                    (_, Section::Start) => {
+                        return Err(LoadError::parse(number, "expected *Vertices"));
+                    }
+                    ("vertices", _) => {
+                        return Err(LoadError::parse(number, "repeated *Vertices section"));
+                    }
+                    ("arcs", _) => Section::Arcs,
+                    ("edges", _) => Section::Edges,
+                    _ => {
+                        return Err(LoadError::parse(
+                            number,
+                            format!("unsupported section *{}", name),
+                        ));
+                    }
+                };
+                continue;
+            }
+            let (id, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            match section {
+                Section::Start => return Err(LoadError::parse(number, "expected *Vertices")),
+                Section::Vertices => {
+                    let vertex = parse_vertex(id, size, number)?;
+                    if let Some(label) = parse_label(rest.trim_start(), number)? {
+                        labels.insert(vertex, label);
+                    }
+                }
+                Section::Arcs | Section::Edges => {
+                    let Some(target) = rest.split_whitespace().next() else {
+                        return Err(LoadError::parse(number, "expected two vertices"));
+                    };
+                    let from = parse_vertex(id, size, number)?;
+                    let to = parse_vertex(target, size, number)?;
+                    builder.add_edge(from, to);
+                    if section == Section::Edges && from != to {
+                        builder.add_edge(to, from);
+                    }
+                }
+            }
+        }
+        if section == Section::Start {
+            return Err(LoadError::parse(1, "expected *Vertices"));
+        }
+        let (network, stats) = builder.build();
+        Ok((LabeledCitationNetwork { network, labels }, stats))
+    }
+    /// Returns the network without its labels
+    pub fn network(&self) -> &CitationNetwork {
+        &self.network
+    }
+    /// Returns the label of a paper, or `None` if it has none
+    pub fn label(&self, vertex: usize) -> Option<&str> {
+        self.labels.get(&vertex).map(String::as_str)
+    }
+    /// Returns the paper with a label, or `None` if no paper has it
+    ///
+    /// If several papers share the label, the one with the smallest id is returned.
+    pub fn vertex_with_label(&self, label: &str) -> Option<usize> {
+        self.labels
+            .iter()
+            .filter(|(_, other)| *other == label)
+            .map(|(&vertex, _)| vertex)
+            .min()
+    }
+}
+
+/// Parses a 1-based Pajek vertex number
+fn parse_vertex(text: &str, size: usize, line: usize) -> Result<usize, LoadError> {
+    match text.parse::<usize>() {
+        Ok(vertex) if (1..=size).contains(&vertex) => Ok(vertex),
+        _ => Err(LoadError::parse(
+            line,
+            format!(
+                "invalid vertex {}: expected an integer in [1, {}]",
+                text, size
+            ),
+        )),
+    }
+}
+
+/// Parses the label at the start of the rest of a vertex line
+///
+/// A label is either quoted, and may then contain spaces, or a single word. The
+/// coordinates and shape parameters that may follow it are ignored.
+fn parse_label(text: &str, line: usize) -> Result<Option<String>, LoadError> {
+    if let Some(quoted) = text.strip_prefix('"') {
+        let (label, _) = quoted
+            .split_once('"')
+            .ok_or_else(|| LoadError::parse(line, "unterminated label"))?;
+        return Ok(Some(label.to_string()));
+    }
+    Ok(text.split_whitespace().next().map(str::to_string))
+}
+
+impl CitationNetwork {
+    /// Loads a network from the Pajek `.net` format
+    ///
+    /// The file starts with `*Vertices n`, optionally followed by lines giving the
+    /// labels of the vertices `1..=n`, which become the paper ids. Every line of an
+    /// `*Arcs` section is a citation from its first vertex to its second, and every
+    /// line of an `*Edges` section is loaded as a citation in each direction. Weights,
+    /// coordinates and lines starting with `%` are ignored. Use
+    /// [`LabeledCitationNetwork::load_from_pajek`] to keep the labels.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to load from
+    pub fn load_from_pajek<R: Read>(reader: R) -> Result<CitationNetwork, LoadError> {
+        Ok(LabeledCitationNetwork::load_from_pajek(reader)?.network)
+    }
+    /// Loads a network from the Pajek `.net` format through a builder
+    ///
+    /// See [`CitationNetwork::load_from_pajek`]; the options of the builder decide
+    /// which edges are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to load from
+    /// * `builder` - The builder to add the nodes and edges to
+    pub fn load_from_pajek_with<R: Read>(
+        reader: R,
+        builder: CitationNetworkBuilder,
+    ) -> Result<(CitationNetwork, BuildStats), LoadError> {
+        let (labeled, stats) = LabeledCitationNetwork::load_from_pajek_with(reader, builder)?;
+        Ok((labeled.network, stats))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAJEK: &str = "% Exported from Pajek\n\
+                         *Vertices 4\n\
+                         1 \"9207016\" 0.1 0.2 0.5\n\
+                         2 \"Noncompact Symmetries\"\n\
+                         3 hepth\n\
+                         *Arcs\n\
+                         1 2 1.0\n\
+                         2 3\n\
+                         \n\
+                         *Edges\n\
+                         3 4 2\n\
+                         4 4\n";
+
+    #[test]
+    fn test_load_from_pajek() {
+        let network = CitationNetwork::load_from_pajek(PAJEK.as_bytes()).unwrap();
+        assert_eq!(network.nodes_sorted(), vec![1, 2, 3, 4]);
+        assert_eq!(
+            network.edges_iter().collect::<Vec<_>>(),
+            vec![(1, 2), (2, 3), (3, 4), (4, 3), (4, 4)]
+        );
+    }
+
+    #[test]
+    fn test_load_pajek_through_builder() {
+        let builder = CitationNetworkBuilder::new()
+            .dedup(true)
+            .drop_self_loops(true);
+        // The extra line is in the *Edges section, so it repeats the arc 1 -> 2
+        let input = format!("{}1 2\n", PAJEK);
+        let (network, stats) =
+            CitationNetwork::load_from_pajek_with(input.as_bytes(), builder).unwrap();
+        assert_eq!(network.nodes_sorted(), vec![1, 2, 3, 4]);
+        assert_eq!(
+            network.edges_iter().collect::<Vec<_>>(),
+            vec![(1, 2), (2, 1), (2, 3), (3, 4), (4, 3)]
+        );
+        assert_eq!(
+            stats,
+            BuildStats {
+                edges_added: 5,
+                duplicates_skipped: 1,
+                loops_dropped: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_labels() {
+        let labeled = LabeledCitationNetwork::load_from_pajek(PAJEK.as_bytes()).unwrap();
+        assert_eq!(labeled.network().num_edges(), 5);
+        assert_eq!(labeled.label(1), Some("9207016"));
+        assert_eq!(labeled.label(2), Some("Noncompact Symmetries"));
+        assert_eq!(labeled.label(3), Some("hepth"));
+        assert_eq!(labeled.label(4), None);
+        assert_eq!(labeled.vertex_with_label("hepth"), Some(3));
+        assert_eq!(labeled.vertex_with_label("missing"), None);
+    }
+
+    #[test]
+    fn test_malformed_pajek() {
+        let error = |input: &str| {
+            CitationNetwork::load_from_pajek(input.as_bytes())
+                .err()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(error(""), "line 1: expected *Vertices");
+        assert_eq!(error("1 2\n"), "line 1: expected *Vertices");
+        assert_eq!(error("*Vertices n\n"), "line 1: invalid vertex count n");
+        assert_eq!(
+            error("*Vertices 2\n*Arcs\n1 3\n"),
+            "line 3: invalid vertex 3: expected an integer in [1, 2]"
+        );
+        assert_eq!(
+            error("*Vertices 2\n*Arcs\n1\n"),
+            "line 3: expected two vertices"
+        );
+        assert_eq!(
+            error("*Vertices 2\n1 \"open\n"),
+            "line 2: unterminated label"
+        );
+        assert_eq!(
+            error("*Vertices 2\n*Matrix\n"),
+            "line 2: unsupported section *matrix"
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use std::fs;
+use std::path::Path;
+use std::thread;
+
+use crate::network::builder::CitationNetworkBuilder;
+use crate::network::stream::EdgeStream;
+use crate::network::{CitationNetwork, LoadError};
+
+/// Splits text into at most `parts` chunks of roughly equal size, each ending at a
+/// newline or at the end of the text
+///
+/// # Returns
+///
+/// The chunks with the number of lines before each of them.
+fn split_lines(text: &[u8], parts: usize) -> Vec<(&[u8], usize)> {
+    let target = text.len().div_ceil(parts).max(1);
+    let mut chunks = Vec::with_capacity(parts);
+    let mut start = 0;
+    let mut lines_before = 0;
+    while start < text.len() {
+        let end = match text[(start + target).min(text.len())..]
+            .iter()
+            .position(|&byte| byte == b'\n')
+        {
+            Some(offset) => (start + target + offset + 1).min(text.len()),
+            None => text.len(),
+        };
+        let chunk = &text[start..end];
+        chunks.push((chunk, lines_before));
+        lines_before += chunk.iter().filter(|&&byte| byte == b'\n').count();
+        start = end;
+    }
+    chunks
+}
+
+impl CitationNetwork {
+    /// Loads a network from an edge list file, parsing it on several threads
+    ///
+    /// The file is read into memory and split at line boundaries into one chunk per
+    /// thread. Each thread parses its chunk as an [`EdgeStream`], and the edges are
+    /// then added to the network in the order of the file, so the result is the
+    /// same network as [`CitationNetwork::load_from_reader`] gives for a SNAP file.
+    /// Comment lines starting with `#` and empty lines are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to load
+    /// * `num_threads` - The number of threads parsing the file
+    ///
+    /// # Returns
+    ///
+    /// The network, or the error of the first malformed line in the file, numbered
+    /// as in the whole file.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_threads` is 0.
+    pub fn load_from_file_parallel(
+        path: &Path,
+        num_threads: usize,
+    ) -> Result<CitationNetwork, LoadError> {
+        assert!(num_threads > 0, "at least one thread is needed");
+        let text = fs::read(path)?;
+        let chunks = split_lines(&text, num_threads);
+        let parsed: Vec<Result<Vec<(usize, usize)>, LoadError>> = thread::scope(|scope| {
+            let workers: Vec<_> = chunks
+                .iter()
+                .map(|&(chunk, lines_before)| {
+                    scope.spawn(move || {
+                        EdgeStream::new(chunk)
+                            .with_line_offset(lines_before)
+                            .collect()
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap())
+                .collect()
+        });
+        let mut builder = CitationNetworkBuilder::new();
+        for edges in parsed {
+            builder.extend(edges?);
+        }
+        Ok(builder.build().0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::{edge_list, random_edges};
+
+    /// Writes text to a file in the temporary directory that is unique to a test
+    fn write_temporary(name: &str, text: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("{}-{}", name, std::process::id()));
+        fs::write(&path, text).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_split_lines() {
+        let text = b"1 2\n3 4\n5 6\n7 8";
+        assert_eq!(
+            split_lines(text, 3),
+            vec![(&b"1 2\n3 4\n"[..], 0), (&b"5 6\n7 8"[..], 2)]
+        );
+        assert_eq!(split_lines(b"", 4), vec![]);
+        assert_eq!(split_lines(b"1 2\n", 8), vec![(&b"1 2\n"[..], 0)]);
+    }
+
+    #[test]
+    fn test_parallel_loading_matches_sequential() {
+        let text = edge_list(&random_edges(10_000, 50_000, 9));
+        let path = write_temporary("parallel-loading", &text);
+        let sequential = CitationNetwork::load_from_reader(text.as_bytes());
+        for num_threads in [1, 3, 8] {
+            let parallel = CitationNetwork::load_from_file_parallel(&path, num_threads).unwrap();
+            assert_eq!(parallel.nodes_sorted(), sequential.nodes_sorted());
+            assert!(parallel.edges_iter().eq(sequential.edges_iter()));
+        }
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_parallel_loading_reports_file_line() {
+        let mut text = edge_list(&random_edges(100, 1_000, 9));
+        text.push_str("17 abc\n");
+        let path = write_temporary("parallel-loading-error", &text);
+        let error = CitationNetwork::load_from_file_parallel(&path, 4)
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "line 1005: invalid paper id: abc");
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            CitationNetwork::load_from_file_parallel(&path, 4),
+            Err(LoadError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::network::CitationNetwork;
+
+impl CitationNetwork {
+    /// Returns the distinct citations between different papers
+    fn distinct_edges(&self) -> HashSet<(usize, usize)> {
+        self.edges_iter().filter(|&(from, to)| from != to).collect()
+    }
+    /// Returns the fraction of citations that are returned
+    ///
+    /// A citation from `u` to `v` is reciprocated if `v` also cites `u`. Repeated
+    /// citations are counted once and self-citations are ignored. A network without
+    /// citations has a reciprocity of 0.
+    pub fn reciprocity(&self) -> f64 {
+        let edges = self.distinct_edges();
+        reciprocated_fraction(&edges, edges.iter())
+    }
+    /// Returns the reciprocity of the citations within each community
+    ///
+    /// Only citations between two papers of the same community are considered.
+    /// Every community of a paper in the network is reported, with a reciprocity of
+    /// 0 if it has no internal citations.
+    ///
+    /// # Arguments
+    ///
+    /// * `communities` - The community of each paper
+    pub fn reciprocity_by_community(
+        &self,
+        communities: &HashMap<usize, usize>,
+    ) -> HashMap<usize, f64> {
+        let edges = self.distinct_edges();
+        let mut internal: HashMap<usize, Vec<&(usize, usize)>> = self
+            .nodes()
+            .filter_map(|vertex| communities.get(vertex))
+            .map(|&community| (community, Vec::new()))
+            .collect();
+        for edge in &edges {
+            let (from, to) = edge;
+            if let (Some(a), Some(b)) = (communities.get(from), communities.get(to)) {
+                if a == b {
+                    internal.get_mut(a).unwrap().push(edge);
+                }
+            }
+        }
+        internal
+            .into_iter()
+            .map(|(community, community_edges)| {
+                (
+                    community,
+                    reciprocated_fraction(&edges, community_edges.into_iter()),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Returns the fraction of some edges whose reverse is in a set, or 0 if there are
+/// no edges
+fn reciprocated_fraction<'a>(
+    edges: &HashSet<(usize, usize)>,
+    candidates: impl Iterator<Item = &'a (usize, usize)>,
+) -> f64 {
+    let (mut total, mut reciprocated) = (0, 0);
+    for &(from, to) in candidates {
+        total += 1;
+        if edges.contains(&(to, from)) {
+            reciprocated += 1;
+        }
+    }
+    if total == 0 {
+        return 0.0;
+    }
+    reciprocated as f64 / total as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_network() -> CitationNetwork {
+        let mut network = CitationNetwork::new();
+        // Community 0: 0 and 1 cite each other, 1 cites 2
+        network.add_edge(0, 1);
+        network.add_edge(1, 0);
+        network.add_edge(1, 2);
+        // Community 1: 3 and 4 cite each other
+        network.add_edge(3, 4);
+        network.add_edge(4, 3);
+        // Between the communities
+        network.add_edge(2, 3);
+        network
+    }
+
+    #[test]
+    fn test_reciprocity() {
+        let mut network = build_network();
+        // 4 of the 6 citations are returned
+        assert!((network.reciprocity() - 4.0 / 6.0).abs() < 1e-12);
+        network.add_edge(0, 1);
+        network.add_edge(5, 5);
+        assert!((network.reciprocity() - 4.0 / 6.0).abs() < 1e-12);
+        assert_eq!(CitationNetwork::new().reciprocity(), 0.0);
+    }
+
+    #[test]
+    fn test_reciprocity_by_community() {
+        let mut network = build_network();
+        network.add_node(6);
+        let communities: HashMap<usize, usize> =
+            [(0, 0), (1, 0), (2, 0), (3, 1), (4, 1), (6, 2)].into();
+        let reciprocity = network.reciprocity_by_community(&communities);
+        assert_eq!(reciprocity.len(), 3);
+        assert!((reciprocity[&0] - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(reciprocity[&1], 1.0);
+        assert_eq!(reciprocity[&2], 0.0);
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use crate::network::CitationNetwork;
+use crate::random::Random;
+
+impl CitationNetwork {
+    /// Returns a copy of the network with its citations randomly rewired
+    ///
+    /// Each swap picks two citations `a -> b` and `c -> d` at random and replaces them
+    /// with `a -> d` and `c -> b`. A swap is skipped if it would create a
+    /// self-citation or a citation that is already in the network, so every node
+    /// keeps its in-degree and out-degree. The result is a sample from the null model
+    /// of networks with the same degrees. The same seed always gives the same network.
+    ///
+    /// # Arguments
+    ///
+    /// * `swaps` - The number of swaps to attempt; ten times the number of edges is
+    ///   usually enough to forget the original structure
+    /// * `seed` - The seed of the random number generator
+    pub fn rewire_preserving_degrees(&self, swaps: usize, seed: u64) -> CitationNetwork {
+        let mut edges: Vec<(usize, usize)> = self.edges_iter().collect();
+        let mut counts: HashMap<(usize, usize), usize> = HashMap::new();
+        for &edge in &edges {
+            *counts.entry(edge).or_insert(0) += 1;
+        }
+        let mut random = Random::new(seed);
+        if edges.len() >= 2 {
+            for _ in 0..swaps {
+                let i = random.below(edges.len());
+                let j = random.below(edges.len());
+                let (a, b) = edges[i];
+                let (c, d) = edges[j];
+                if a == d || c == b || counts.contains_key(&(a, d)) || counts.contains_key(&(c, b))
+                {
+                    continue;
+                }
+                for edge in [(a, b), (c, d)] {
+                    let count = counts.get_mut(&edge).unwrap();
+                    *count -= 1;
+                    if *count == 0 {
+                        counts.remove(&edge);
+                    }
+                }
+                edges[i] = (a, d);
+                edges[j] = (c, b);
+                counts.insert((a, d), 1);
+                counts.insert((c, b), 1);
+            }
+        }
+        let mut rewired = CitationNetwork::new();
+        for vertex in self.nodes_sorted() {
+            rewired.add_node(vertex);
+        }
+        for (from, to) in edges {
+            rewired.add_edge(from, to);
+        }
+        rewired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generators::erdos_renyi;
+
+    #[test]
+    fn test_rewire_preserves_degrees() {
+        let network = erdos_renyi(50, 0.1, 3);
+        let rewired = network.rewire_preserving_degrees(10 * network.num_edges(), 5);
+        assert_eq!(rewired.size(), network.size());
+        assert_eq!(rewired.num_edges(), network.num_edges());
+        for vertex in network.nodes_sorted() {
+            assert_eq!(rewired.in_degree(vertex), network.in_degree(vertex));
+            assert_eq!(rewired.out_degree(vertex), network.out_degree(vertex));
+            assert!(!rewired.contains_edge(vertex, vertex));
+        }
+        assert!(!rewired.is_isomorphic_as_labeled(&network));
+        let again = network.rewire_preserving_degrees(10 * network.num_edges(), 5);
+        assert!(again.is_isomorphic_as_labeled(&rewired));
+        let mut edges: Vec<_> = rewired.edges_iter().collect();
+        edges.dedup();
+        assert_eq!(edges.len(), rewired.num_edges());
+    }
+}
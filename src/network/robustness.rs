@@ -0,0 +1,135 @@
+use crate::network::CitationNetwork;
+
+/// How removing a set of papers changes the connectivity of a network
+///
+/// Components are connected components with citations treated as undirected, and
+/// the degree of a paper is the number of citations it makes plus the number it
+/// receives.
+#[derive(Debug, PartialEq)]
+pub struct RemovalImpact {
+    /// The number of papers in the largest component before the removal
+    pub original_giant_component_size: usize,
+    /// The number of papers in the largest component after the removal
+    pub new_giant_component_size: usize,
+    /// The number of components before the removal
+    pub original_num_components: usize,
+    /// The number of components after the removal
+    pub new_num_components: usize,
+    /// The average degree before the removal minus the average degree after it
+    pub average_degree_drop: f64,
+}
+
+impl CitationNetwork {
+    /// Returns the average number of citations made and received by a paper, or 0 if
+    /// the network is empty
+    fn average_degree(&self) -> f64 {
+        if self.size() == 0 {
+            return 0.0;
+        }
+        2.0 * self.num_edges() as f64 / self.size() as f64
+    }
+    /// Returns the size of the largest component and the number of components
+    fn component_summary(&self) -> (usize, usize) {
+        let components = self.connected_components();
+        let giant = components.iter().map(Vec::len).max().unwrap_or(0);
+        (giant, components.len())
+    }
+    /// Measures how removing some papers would fragment the network
+    ///
+    /// The network itself is not changed. Papers not in the network are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertices_to_remove` - The ids of the papers to remove
+    pub fn impact_of_removal(&self, vertices_to_remove: &[usize]) -> RemovalImpact {
+        let mut attacked = self.clone();
+        for &vertex in vertices_to_remove {
+            attacked.remove_node(vertex);
+        }
+        let (original_giant_component_size, original_num_components) = self.component_summary();
+        let (new_giant_component_size, new_num_components) = attacked.component_summary();
+        RemovalImpact {
+            original_giant_component_size,
+            new_giant_component_size,
+            original_num_components,
+            new_num_components,
+            average_degree_drop: self.average_degree() - attacked.average_degree(),
+        }
+    }
+    /// Returns the order in which a targeted attack removes papers
+    ///
+    /// At each step the paper with the highest degree in what remains of the network
+    /// is removed, with ties broken by the smallest id. The degree of a paper is the
+    /// number of citations it makes plus the number it receives.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The number of papers to remove
+    ///
+    /// # Returns
+    ///
+    /// * `removed` - The ids of the removed papers in removal order, at most `k` and at
+    ///   most the size of the network
+    pub fn targeted_attack_sequence(&self, k: usize) -> Vec<usize> {
+        let mut attacked = self.clone();
+        let mut removed = Vec::with_capacity(k.min(self.size()));
+        while removed.len() < k {
+            let target = attacked.nodes().copied().max_by(|&a, &b| {
+                let degree = |v| attacked.in_degree(v) + attacked.out_degree(v);
+                degree(a).cmp(&degree(b)).then(b.cmp(&a))
+            });
+            let Some(target) = target else {
+                break;
+            };
+            attacked.remove_node(target);
+            removed.push(target);
+        }
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two stars joined through their centers 0 and 10
+    fn build_network() -> CitationNetwork {
+        let mut network = CitationNetwork::new();
+        for leaf in 1..5 {
+            network.add_edge(leaf, 0);
+        }
+        for leaf in 11..14 {
+            network.add_edge(leaf, 10);
+        }
+        network.add_edge(10, 0);
+        network
+    }
+
+    #[test]
+    fn test_impact_of_removal() {
+        let network = build_network();
+        let impact = network.impact_of_removal(&[0]);
+        assert_eq!(impact.original_giant_component_size, 9);
+        assert_eq!(impact.original_num_components, 1);
+        assert_eq!(impact.new_giant_component_size, 4);
+        assert_eq!(impact.new_num_components, 5);
+        // 16 / 9 before, 6 / 8 after
+        assert!((impact.average_degree_drop - (16.0 / 9.0 - 0.75)).abs() < 1e-12);
+        assert_eq!(network.size(), 9);
+        let nothing = network.impact_of_removal(&[42]);
+        assert_eq!(nothing.new_giant_component_size, 9);
+        assert_eq!(nothing.average_degree_drop, 0.0);
+    }
+
+    #[test]
+    fn test_targeted_attack_sequence() {
+        let network = build_network();
+        assert_eq!(network.targeted_attack_sequence(2), vec![0, 10]);
+        // Afterwards every remaining paper is isolated, so ties go to the smallest id
+        assert_eq!(network.targeted_attack_sequence(4), vec![0, 10, 1, 2]);
+        assert_eq!(network.targeted_attack_sequence(20).len(), 9);
+        assert!(CitationNetwork::new()
+            .targeted_attack_sequence(3)
+            .is_empty());
+    }
+}
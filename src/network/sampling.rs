@@ -0,0 +1,139 @@
+use std::collections::HashSet;
+
+use crate::network::CitationNetwork;
+use crate::random::Random;
+
+impl CitationNetwork {
+    /// Returns a random sample of the edges of the network
+    ///
+    /// Each edge is kept independently with probability `fraction`, and every node
+    /// is kept, so the sample is a sparser version of the same network. The same
+    /// seed always gives the same sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `fraction` - The probability of keeping each edge
+    /// * `seed` - The seed of the random number generator
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fraction` is not in `(0, 1]`.
+    pub fn sample_edges(&self, fraction: f64, seed: u64) -> CitationNetwork {
+        assert!(
+            fraction > 0.0 && fraction <= 1.0,
+            "fraction must be in (0, 1], got {}",
+            fraction
+        );
+        let mut random = Random::new(seed);
+        let mut sample = CitationNetwork::new();
+        for vertex in self.nodes_sorted() {
+            sample.add_node(vertex);
+        }
+        for (from, to) in self.edges_iter() {
+            if random.next_f64() < fraction {
+                sample.add_edge(from, to);
+            }
+        }
+        sample
+    }
+    /// Returns the subgraph induced by a random sample of the nodes of the network
+    ///
+    /// The sample has `fraction` of the nodes, rounded to the nearest integer and
+    /// chosen uniformly, together with every edge between two sampled nodes. The same
+    /// seed always gives the same sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `fraction` - The fraction of nodes to keep
+    /// * `seed` - The seed of the random number generator
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fraction` is not in `(0, 1]`.
+    pub fn sample_nodes(&self, fraction: f64, seed: u64) -> CitationNetwork {
+        assert!(
+            fraction > 0.0 && fraction <= 1.0,
+            "fraction must be in (0, 1], got {}",
+            fraction
+        );
+        let nodes = self.nodes_sorted();
+        let k = (fraction * nodes.len() as f64).round() as usize;
+        let sampled: HashSet<usize> = Random::new(seed).sample(&nodes, k).into_iter().collect();
+        self.subgraph_where(|vertex| sampled.contains(&vertex))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::random_edges;
+
+    fn build_network() -> CitationNetwork {
+        let mut network = CitationNetwork::new();
+        for (from, to) in random_edges(10_000, 50_000, 42) {
+            network.add_edge(from, to);
+        }
+        network
+    }
+
+    #[test]
+    fn test_sample_edges() {
+        let network = build_network();
+        let sample = network.sample_edges(0.1, 7);
+        assert_eq!(sample.size(), network.size());
+        // The standard deviation of the number of edges is about 67
+        assert!(sample.num_edges().abs_diff(5_000) < 350);
+        assert!(network
+            .sample_edges(0.1, 7)
+            .is_isomorphic_as_labeled(&sample));
+        assert!(!network
+            .sample_edges(0.1, 8)
+            .is_isomorphic_as_labeled(&sample));
+        assert_eq!(
+            network.sample_edges(1.0, 7).num_edges(),
+            network.num_edges()
+        );
+    }
+
+    #[test]
+    fn test_sample_nodes() {
+        let network = build_network();
+        let sample = network.sample_nodes(0.1, 7);
+        assert_eq!(sample.size(), 1_000);
+        // Each edge survives with probability about 1%
+        assert!(sample.num_edges().abs_diff(500) < 150);
+        let sampled: HashSet<usize> = sample.nodes().copied().collect();
+        assert!(sampled.iter().all(|&vertex| network.contains_node(vertex)));
+        for (from, to) in sample.edges_iter() {
+            assert!(network
+                .out_edges_from(from)
+                .any(|&reference| reference == to));
+            assert!(sampled.contains(&from) && sampled.contains(&to));
+        }
+        // The sample is the induced subgraph, so it keeps every citation between
+        // sampled papers
+        let induced = network
+            .edges_iter()
+            .filter(|(from, to)| sampled.contains(from) && sampled.contains(to))
+            .count();
+        assert_eq!(sample.num_edges(), induced);
+        assert!(network
+            .sample_nodes(0.1, 7)
+            .is_isomorphic_as_labeled(&sample));
+        assert!(network
+            .sample_nodes(1.0, 7)
+            .is_isomorphic_as_labeled(&network));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample_edges_rejects_zero() {
+        build_network().sample_edges(0.0, 7);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample_nodes_rejects_more_than_one() {
+        build_network().sample_nodes(1.5, 7);
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+
+use crate::network::CitationNetwork;
+
+/// Returns the Jaccard similarity of two sorted, deduplicated lists
+fn sorted_jaccard(a: &[usize], b: &[usize]) -> f64 {
+    let (mut i, mut j, mut common) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                common += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    let union = a.len() + b.len() - common;
+    if union == 0 {
+        return 0.0;
+    }
+    common as f64 / union as f64
+}
+
+impl CitationNetwork {
+    /// Returns the Jaccard similarity between the neighborhoods of two nodes
+    ///
+    /// The neighborhood of a node is the set of papers it cites together with the
+    /// papers citing it. The similarity is 0 if either node is not in the network or
+    /// both neighborhoods are empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The id of the first paper
+    /// * `b` - The id of the second paper
+    pub fn jaccard_similarity(&self, a: usize, b: usize) -> f64 {
+        if !self.contains_node(a) || !self.contains_node(b) {
+            return 0.0;
+        }
+        sorted_jaccard(&self.neighbors(a), &self.neighbors(b))
+    }
+    /// Returns the nodes whose neighborhoods are most similar to that of a node
+    ///
+    /// Only nodes within two hops share a neighbor with `vertex`, so only those are
+    /// examined and fewer than `n` nodes may be returned. The results are sorted by
+    /// similarity in non-ascending order, with ties broken by vertex id.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex` - The id of the query paper
+    /// * `n` - The number of papers to return
+    pub fn top_similar_neighbors(&self, vertex: usize, n: usize) -> Vec<(usize, f64)> {
+        if !self.contains_node(vertex) {
+            return Vec::new();
+        }
+        let neighbors = self.neighbors(vertex);
+        let candidates: HashSet<usize> = neighbors
+            .iter()
+            .flat_map(|&neighbor| self.neighbors(neighbor))
+            .filter(|&candidate| candidate != vertex)
+            .collect();
+        let mut similarities: Vec<_> = candidates
+            .into_iter()
+            .map(|candidate| {
+                let similarity = sorted_jaccard(&neighbors, &self.neighbors(candidate));
+                (candidate, similarity)
+            })
+            .collect();
+        similarities.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap().then(a.0.cmp(&b.0)));
+        similarities.truncate(n);
+        similarities
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jaccard_similarity() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 2);
+        graph.add_edge(3, 0);
+        graph.add_edge(1, 2);
+        graph.add_edge(3, 1);
+        graph.add_edge(4, 5);
+        assert_eq!(graph.jaccard_similarity(0, 1), 1.0);
+        assert_eq!(graph.jaccard_similarity(0, 4), 0.0);
+        assert_eq!(graph.jaccard_similarity(2, 3), 1.0);
+        assert_eq!(graph.jaccard_similarity(0, 42), 0.0);
+    }
+
+    #[test]
+    fn test_top_similar_neighbors() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 10);
+        graph.add_edge(0, 11);
+        graph.add_edge(1, 10);
+        graph.add_edge(1, 11);
+        graph.add_edge(2, 10);
+        graph.add_edge(2, 12);
+        let similar = graph.top_similar_neighbors(0, 3);
+        assert_eq!(similar.len(), 2);
+        assert_eq!(similar[0], (1, 1.0));
+        assert_eq!(similar[1].0, 2);
+        assert!((similar[1].1 - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(graph.top_similar_neighbors(0, 1), vec![(1, 1.0)]);
+        assert!(graph.top_similar_neighbors(42, 1).is_empty());
+    }
+}
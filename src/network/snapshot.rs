@@ -0,0 +1,103 @@
+use crate::network::CitationNetwork;
+
+/// A saved state of a network that it can be rolled back to
+///
+/// A snapshot is a full copy of the network, so it is unaffected by any change made
+/// to the network after it was taken. Its contents can only be inspected through
+/// its counts, or by restoring it.
+#[derive(Clone)]
+pub struct GraphSnapshot {
+    network: CitationNetwork,
+    num_edges: usize,
+}
+
+impl GraphSnapshot {
+    /// Returns the number of nodes the network had when the snapshot was taken
+    pub fn node_count(&self) -> usize {
+        self.network.size()
+    }
+    /// Returns the number of edges the network had when the snapshot was taken,
+    /// counting repeated citations
+    pub fn edge_count(&self) -> usize {
+        self.num_edges
+    }
+}
+
+impl CitationNetwork {
+    /// Saves the current state of the network
+    ///
+    /// Taking a snapshot, changing the network, measuring the effect and restoring
+    /// the snapshot is the way to analyze the impact of a change without keeping a
+    /// copy of the network around by hand.
+    pub fn snapshot(&self) -> GraphSnapshot {
+        GraphSnapshot {
+            network: self.clone(),
+            num_edges: self.num_edges(),
+        }
+    }
+    /// Rolls the network back to a saved state
+    ///
+    /// Every node and citation is replaced by those of the snapshot, including the
+    /// order of the citations of each paper, so the network is exactly as it was
+    /// when the snapshot was taken. The snapshot may come from another network.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot` - The state to restore, which is consumed
+    pub fn restore(&mut self, snapshot: GraphSnapshot) {
+        *self = snapshot.network;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::centrality::centrality::Centrality;
+    use crate::centrality::pagerank_centrality::calculate_pagerank_centrality;
+    use crate::example_graphs::zachary_like;
+
+    fn pagerank(network: &CitationNetwork) -> Vec<(usize, f64)> {
+        calculate_pagerank_centrality(network)
+            .iter()
+            .map(|c| (c.vertex(), c.score()))
+            .collect()
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut network = zachary_like();
+        network.add_edge(5, 0);
+        let edges: Vec<(usize, usize)> = network.edges_iter().collect();
+        let scores = pagerank(&network);
+        let snapshot = network.snapshot();
+        assert_eq!(snapshot.node_count(), 34);
+        assert_eq!(snapshot.edge_count(), 79);
+        // Cutting the instructor off changes the scores
+        network.remove_node(0);
+        network.add_edge(40, 33);
+        network.remove_edge(33, 32);
+        assert_ne!(pagerank(&network), scores);
+        assert_eq!(snapshot.node_count(), 34);
+        assert_eq!(snapshot.edge_count(), 79);
+        network.restore(snapshot);
+        assert_eq!(network.edges_iter().collect::<Vec<_>>(), edges);
+        assert_eq!(network.size(), 34);
+        assert_eq!(pagerank(&network), scores);
+    }
+
+    #[test]
+    fn test_restore_replaces_network() {
+        let mut network: CitationNetwork = [(1, 2), (2, 3)].into_iter().collect();
+        let empty = CitationNetwork::new().snapshot();
+        assert_eq!(empty.node_count(), 0);
+        assert_eq!(empty.edge_count(), 0);
+        let snapshot = network.snapshot();
+        let mut other = zachary_like();
+        other.restore(snapshot.clone());
+        assert!(other.is_isomorphic_as_labeled(&network));
+        network.restore(empty);
+        assert_eq!(network.size(), 0);
+        network.restore(snapshot);
+        assert_eq!(network.num_edges(), 2);
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::HashSet;
+
+use crate::network::index::NodeIndex;
+use crate::network::CitationNetwork;
+
+/// The adjacency structure of a network over compact indices
+struct SparseAdjacency {
+    /// The compact indices of the papers cited by each node, one entry per citation
+    targets: Vec<Vec<usize>>,
+}
+
+impl SparseAdjacency {
+    fn new(network: &CitationNetwork, index: &NodeIndex) -> SparseAdjacency {
+        let targets = index
+            .vertices()
+            .iter()
+            .map(|&vertex| {
+                network
+                    .out_edges_from(vertex)
+                    .map(|&to| index.index_of(to).unwrap())
+                    .collect()
+            })
+            .collect();
+        SparseAdjacency { targets }
+    }
+    /// Returns `A * v`
+    fn multiply(&self, v: &[f64]) -> Vec<f64> {
+        self.targets
+            .iter()
+            .map(|targets| targets.iter().map(|&j| v[j]).sum())
+            .collect()
+    }
+    /// Returns `A^T * v`
+    fn multiply_transpose(&self, v: &[f64]) -> Vec<f64> {
+        let mut result = vec![0.0; v.len()];
+        for (i, targets) in self.targets.iter().enumerate() {
+            for &j in targets {
+                result[j] += v[i];
+            }
+        }
+        result
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn norm(v: &[f64]) -> f64 {
+    dot(v, v).sqrt()
+}
+
+/// Removes the component of `v` along the unit vector `direction`
+fn orthogonalize(v: &mut [f64], direction: &[f64]) {
+    let projection = dot(v, direction);
+    for (x, d) in v.iter_mut().zip(direction) {
+        *x -= projection * d;
+    }
+}
+
+/// The largest number of power iterations run to find the Fiedler vector
+const FIEDLER_MAX_ITERATIONS: usize = 10_000;
+
+/// The change in the Fiedler vector below which the power iteration stops
+const FIEDLER_TOLERANCE: f64 = 1e-10;
+
+/// Estimates the largest singular value of `A` and its right singular vector
+///
+/// Power iteration on `A^T * A`, keeping the iterate orthogonal to `deflate` if
+/// given, so that the second largest singular value can be found as well.
+fn power_iteration(
+    adjacency: &SparseAdjacency,
+    start: Vec<f64>,
+    num_iterations: usize,
+    deflate: Option<&[f64]>,
+) -> (f64, Vec<f64>) {
+    let mut v = start;
+    if let Some(direction) = deflate {
+        orthogonalize(&mut v, direction);
+    }
+    let length = norm(&v);
+    if length == 0.0 {
+        return (0.0, v);
+    }
+    v.iter_mut().for_each(|x| *x /= length);
+    for _ in 0..num_iterations {
+        let mut w = adjacency.multiply_transpose(&adjacency.multiply(&v));
+        if let Some(direction) = deflate {
+            orthogonalize(&mut w, direction);
+        }
+        let length = norm(&w);
+        if length == 0.0 {
+            return (0.0, v);
+        }
+        v = w.into_iter().map(|x| x / length).collect();
+    }
+    (norm(&adjacency.multiply(&v)), v)
+}
+
+impl CitationNetwork {
+    /// Estimates the spectral gap of the adjacency matrix
+    ///
+    /// The gap is the difference between the two largest singular values of the
+    /// adjacency matrix, estimated by power iteration on `A^T * A` with deflation.
+    /// Only sparse products over the adjacency lists are used, so each iteration
+    /// takes O(V + E) time. A gap close to 0 means the network consists of weakly
+    /// connected parts of similar strength.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_iterations` - The number of power iterations for each singular value
+    pub fn approx_spectral_gap(&self, num_iterations: usize) -> f64 {
+        let index = self.node_index();
+        let n = index.len();
+        if n == 0 {
+            return 0.0;
+        }
+        let adjacency = SparseAdjacency::new(self, &index);
+        // Fixed, uneven start vectors keep the estimate deterministic and make it
+        // unlikely that a start vector is orthogonal to the singular vectors
+        let first_start = (0..n).map(|i| 1.0 + (i % 7) as f64 / 7.0).collect();
+        let second_start = (0..n)
+            .map(|i| 1.0 + (i % 5) as f64 - (i % 3) as f64)
+            .collect();
+        let (first, v) = power_iteration(&adjacency, first_start, num_iterations, None);
+        let (second, _) = power_iteration(&adjacency, second_start, num_iterations, Some(&v));
+        first - second
+    }
+    /// Splits the network in two by the signs of the Fiedler vector
+    ///
+    /// The Fiedler vector is the eigenvector of the second smallest eigenvalue of the
+    /// Laplacian `D - W` of the undirected network, where `W = A + A^T` and `D` holds
+    /// the row sums of `W`, so citations are followed in both directions and a mutual
+    /// citation counts twice. It is found by power iteration on `c * I - L`, with
+    /// `c` bounding the eigenvalues of `L`, keeping the iterate orthogonal to the
+    /// constant vector. Only the sparse products
+    /// [`CitationNetwork::adj_matvec`] and [`CitationNetwork::adj_t_matvec`] are
+    /// used, so the Laplacian is never built.
+    ///
+    /// Nodes with a non-negative component go to the first part and the others to the
+    /// second. The sign of the vector is chosen so that the node with the smallest id
+    /// is in the first part.
+    ///
+    /// # Returns
+    ///
+    /// * `(first, second)` - The subgraphs induced by the two parts
+    pub fn spectral_bisect(&self) -> (CitationNetwork, CitationNetwork) {
+        let index = self.node_index();
+        let n = index.len();
+        if n < 2 {
+            return (self.clone(), CitationNetwork::new());
+        }
+        let laplacian = |v: &[f64], degrees: &[f64]| -> Vec<f64> {
+            let forward = self.adj_matvec(&index, v);
+            let backward = self.adj_t_matvec(&index, v);
+            (0..n)
+                .map(|i| degrees[i] * v[i] - forward[i] - backward[i])
+                .collect()
+        };
+        let ones = vec![1.0; n];
+        let degrees: Vec<f64> = self
+            .adj_matvec(&index, &ones)
+            .iter()
+            .zip(self.adj_t_matvec(&index, &ones))
+            .map(|(out, into)| out + into)
+            .collect();
+        // Every eigenvalue of the Laplacian is at most twice the largest degree
+        let shift = 2.0 * degrees.iter().copied().fold(0.0, f64::max);
+        let constant: Vec<f64> = vec![1.0 / (n as f64).sqrt(); n];
+        let mut v: Vec<f64> = (0..n)
+            .map(|i| 1.0 + (i % 5) as f64 - (i % 3) as f64 + i as f64 / n as f64)
+            .collect();
+        orthogonalize(&mut v, &constant);
+        let length = norm(&v);
+        v.iter_mut().for_each(|x| *x /= length);
+        for _ in 0..FIEDLER_MAX_ITERATIONS {
+            let product = laplacian(&v, &degrees);
+            let mut w: Vec<f64> = (0..n).map(|i| shift * v[i] - product[i]).collect();
+            orthogonalize(&mut w, &constant);
+            let length = norm(&w);
+            if length == 0.0 {
+                break;
+            }
+            w.iter_mut().for_each(|x| *x /= length);
+            let change: f64 = w.iter().zip(&v).map(|(a, b)| (a - b).abs()).sum();
+            v = w;
+            if change < FIEDLER_TOLERANCE {
+                break;
+            }
+        }
+        let sign = if v[0] < 0.0 { -1.0 } else { 1.0 };
+        let first: HashSet<usize> = (0..n)
+            .filter(|&i| sign * v[i] >= 0.0)
+            .map(|i| index.vertex_at(i))
+            .collect();
+        (
+            self.subgraph_where(|vertex| first.contains(&vertex)),
+            self.subgraph_where(|vertex| !first.contains(&vertex)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_graphs::{path, two_cliques_bridge};
+    use crate::random::Random;
+
+    #[test]
+    fn test_spectral_gap_of_two_cliques() {
+        let mut network = two_cliques_bridge(6);
+        network.remove_edge(5, 6);
+        assert!(network.approx_spectral_gap(200).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_spectral_gap_of_random_regular_network() {
+        // Every node cites and is cited by exactly 6 nodes, counting repeats
+        let mut random = Random::new(3);
+        let mut network = CitationNetwork::new();
+        for _ in 0..6 {
+            let mut permutation: Vec<usize> = (0..100).collect();
+            random.shuffle(&mut permutation);
+            for (from, &to) in permutation.iter().enumerate() {
+                network.add_edge(from, to);
+            }
+        }
+        let gap = network.approx_spectral_gap(200);
+        assert!(gap > 1.0, "gap {}", gap);
+        assert!(gap <= 6.0 + 1e-9);
+    }
+
+    #[test]
+    fn test_spectral_gap_of_empty_network() {
+        assert_eq!(CitationNetwork::new().approx_spectral_gap(10), 0.0);
+    }
+
+    #[test]
+    fn test_spectral_bisect_two_cliques() {
+        let network = two_cliques_bridge(6);
+        let (first, second) = network.spectral_bisect();
+        assert_eq!(first.nodes_sorted(), (0..6).collect::<Vec<_>>());
+        assert_eq!(second.nodes_sorted(), (6..12).collect::<Vec<_>>());
+        assert_eq!(first.num_edges(), 15);
+        assert_eq!(second.num_edges(), 15);
+    }
+
+    #[test]
+    fn test_spectral_bisect_path() {
+        let (first, second) = path(10).spectral_bisect();
+        assert_eq!(first.nodes_sorted(), (0..5).collect::<Vec<_>>());
+        assert_eq!(second.nodes_sorted(), (5..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_spectral_bisect_small_networks() {
+        let (first, second) = CitationNetwork::new().spectral_bisect();
+        assert_eq!((first.size(), second.size()), (0, 0));
+        let mut network = CitationNetwork::new();
+        network.add_node(4);
+        let (first, second) = network.spectral_bisect();
+        assert_eq!(first.nodes_sorted(), vec![4]);
+        assert_eq!(second.size(), 0);
+    }
+}
@@ -0,0 +1,279 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::{BufRead, BufReader, Lines, Read};
+
+use crate::network::{CitationNetwork, LoadError};
+
+/// The numbers of nodes and edges declared by the `# Nodes: N Edges: M` comment
+/// of a SNAP header
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeclaredCounts {
+    pub nodes: usize,
+    pub edges: usize,
+}
+
+impl DeclaredCounts {
+    /// Reads the counts from the text of a comment line, without its comment marker
+    ///
+    /// # Returns
+    ///
+    /// The counts, or `None` if the comment does not declare both of them.
+    fn parse(comment: &str) -> Option<DeclaredCounts> {
+        let mut nodes = None;
+        let mut edges = None;
+        let mut words = comment.split_whitespace();
+        while let Some(word) = words.next() {
+            match word {
+                "Nodes:" => nodes = words.next()?.parse().ok(),
+                "Edges:" => edges = words.next()?.parse().ok(),
+                _ => {}
+            }
+        }
+        Some(DeclaredCounts {
+            nodes: nodes?,
+            edges: edges?,
+        })
+    }
+    /// Checks the declared counts against a loaded network
+    ///
+    /// The counts only match a network loaded with every edge, since a builder that
+    /// drops duplicates or self-citations leaves fewer edges than the file declares.
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network loaded from the file
+    ///
+    /// # Returns
+    ///
+    /// Nothing if the network has the declared numbers of nodes and edges, or the
+    /// declared and actual counts otherwise.
+    pub fn verify_counts(&self, network: &CitationNetwork) -> Result<(), CountMismatch> {
+        let actual = DeclaredCounts {
+            nodes: network.size(),
+            edges: network.num_edges(),
+        };
+        if actual == *self {
+            Ok(())
+        } else {
+            Err(CountMismatch {
+                declared: *self,
+                actual,
+            })
+        }
+    }
+}
+
+/// The error returned when a network does not have the counts its header declares
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CountMismatch {
+    /// The counts in the header
+    pub declared: DeclaredCounts,
+    /// The counts of the loaded network
+    pub actual: DeclaredCounts,
+}
+
+impl Display for CountMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the header declares {} nodes and {} edges but the network has {} nodes and {} edges",
+            self.declared.nodes, self.declared.edges, self.actual.nodes, self.actual.edges
+        )
+    }
+}
+
+impl Error for CountMismatch {}
+
+/// The edges of an edge list, parsed one line at a time
+///
+/// Only the current line is held in memory, so an edge list can be processed
+/// without building the network, however large it is. Empty lines and comment
+/// lines starting with `#` or `%`, such as the SNAP header, are skipped, however
+/// many there are. A malformed line yields an error and the stream goes on with the
+/// next line, while a read error ends the stream.
+pub struct EdgeStream<R> {
+    lines: Lines<BufReader<R>>,
+    /// The 1-based number of the last line read, counting any offset
+    line: usize,
+    /// Whether a read error has ended the stream
+    failed: bool,
+    /// The counts declared by the comments read so far
+    declared: Option<DeclaredCounts>,
+}
+
+impl<R: Read> EdgeStream<R> {
+    /// Creates a stream of the edges of an edge list
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to parse the edges from
+    pub fn new(reader: R) -> EdgeStream<R> {
+        EdgeStream {
+            lines: BufReader::new(reader).lines(),
+            line: 0,
+            failed: false,
+            declared: None,
+        }
+    }
+    /// Returns the counts declared by a `# Nodes: N Edges: M` comment, if one has
+    /// been read
+    ///
+    /// The SNAP header comes before the edges, so the counts are known once the
+    /// first edge has been read. If several comments declare counts, the last one
+    /// read wins.
+    pub fn declared_counts(&self) -> Option<DeclaredCounts> {
+        self.declared
+    }
+    /// Numbers the lines of the stream as if some lines came before them
+    ///
+    /// This keeps the line numbers in errors right when the reader starts partway
+    /// through a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The number of lines before the first line of the reader
+    pub fn with_line_offset(mut self, lines: usize) -> EdgeStream<R> {
+        self.line = lines;
+        self
+    }
+}
+
+impl<R: Read> Iterator for EdgeStream<R> {
+    type Item = Result<(usize, usize), LoadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(error) => {
+                    self.failed = true;
+                    return Some(Err(error.into()));
+                }
+            };
+            self.line += 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(comment) = line.strip_prefix(['#', '%']) {
+                if let Some(counts) = DeclaredCounts::parse(comment) {
+                    self.declared = Some(counts);
+                }
+                continue;
+            }
+            let ids: Vec<&str> = line.split_whitespace().collect();
+            let [from, to] = ids[..] else {
+                return Some(Err(LoadError::parse(
+                    self.line,
+                    format!("expected two paper ids, found {}", ids.len()),
+                )));
+            };
+            let parse = |id: &str| {
+                id.parse::<usize>()
+                    .map_err(|_| LoadError::parse(self.line, format!("invalid paper id: {}", id)))
+            };
+            return Some(parse(from).and_then(|from| Ok((from, parse(to)?))));
+        }
+    }
+}
+
+impl CitationNetwork {
+    /// Returns a stream of the edges of an edge list, without building a network
+    ///
+    /// Each line holds the ids of a citing and a cited paper separated by whitespace,
+    /// as in the SNAP format. Collect the edges into a [`CitationNetwork`] when the
+    /// network fits in memory, or fold them into an online statistic such as
+    /// [`crate::analysis::streaming::StreamingDegrees`] when it does not.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader to parse the edges from
+    pub fn stream_from<R: Read>(reader: R) -> EdgeStream<R> {
+        EdgeStream::new(reader)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{self, Cursor};
+
+    const EDGE_LIST: &str = "# Directed graph (each unordered pair of nodes is saved once)\n\
+                             # FromNodeId\tToNodeId\n\
+                             1001\t9304045\n\
+                             \n\
+                             1001 9308122\n\
+                             1002\t9308122\n";
+
+    #[test]
+    fn test_stream_from() {
+        let edges: Vec<(usize, usize)> = CitationNetwork::stream_from(Cursor::new(EDGE_LIST))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            edges,
+            vec![(1001, 9304045), (1001, 9308122), (1002, 9308122)]
+        );
+        let network: CitationNetwork = edges.into_iter().collect();
+        assert_eq!(network.in_degree(9308122), 2);
+    }
+
+    #[test]
+    fn test_malformed_lines() {
+        let input = Cursor::new("1 2\n3\n4 x\n5 6\n");
+        let results: Vec<String> = CitationNetwork::stream_from(input)
+            .map(|result| match result {
+                Ok((from, to)) => format!("{} -> {}", from, to),
+                Err(error) => error.to_string(),
+            })
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                "1 -> 2",
+                "line 2: expected two paper ids, found 1",
+                "line 3: invalid paper id: x",
+                "5 -> 6",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_declared_counts() {
+        let mut stream = CitationNetwork::stream_from(Cursor::new(
+            "% Nodes: 1 Edges: 1\n# Directed graph\n# Nodes: 3 Edges: 2\n1 2\n2 3\n",
+        ));
+        assert_eq!(stream.declared_counts(), None);
+        stream.next();
+        assert_eq!(
+            stream.declared_counts(),
+            Some(DeclaredCounts { nodes: 3, edges: 2 })
+        );
+        assert_eq!(DeclaredCounts::parse(" Nodes: 3"), None);
+        assert_eq!(DeclaredCounts::parse(" Nodes: x Edges: 4"), None);
+        assert_eq!(DeclaredCounts::parse(" FromNodeId\tToNodeId"), None);
+    }
+
+    /// A reader that fails after returning some data
+    struct FailingReader(Cursor<&'static str>);
+
+    impl Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.0.read(buf)? {
+                0 => Err(io::Error::other("disk on fire")),
+                n => Ok(n),
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_error_ends_stream() {
+        let mut stream = CitationNetwork::stream_from(FailingReader(Cursor::new("1 2\n")));
+        assert_eq!(stream.next().unwrap().unwrap(), (1, 2));
+        assert!(matches!(stream.next(), Some(Err(LoadError::Io(_)))));
+        assert!(stream.next().is_none());
+    }
+}
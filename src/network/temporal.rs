@@ -0,0 +1,168 @@
+use crate::network::CitationNetwork;
+
+/// A citation network in which every edge carries the time it was made
+///
+/// Timestamps are plain `u32` values whose unit is up to the caller; a common
+/// choice is `year * 100 + month`, so that 199207 is July 1992.
+#[derive(Default)]
+pub struct TimestampedCitationNetwork {
+    /// The edges and their timestamps in insertion order
+    edges: Vec<(usize, usize, u32)>,
+}
+
+impl TimestampedCitationNetwork {
+    /// Creates a new empty network
+    pub fn new() -> TimestampedCitationNetwork {
+        TimestampedCitationNetwork::default()
+    }
+    /// Adds an edge made at the given time
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The id of the source paper
+    /// * `to` - The id of the cited paper
+    /// * `timestamp` - When the citation was made
+    ///
+    pub fn add_timestamped_edge(&mut self, from: usize, to: usize, timestamp: u32) {
+        self.edges.push((from, to, timestamp));
+    }
+    /// Returns the number of edges in the network
+    pub fn num_edges(&self) -> usize {
+        self.edges.len()
+    }
+    /// Returns an iterator over the edges as (citing, cited, timestamp) triples
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, u32)> + '_ {
+        self.edges.iter().copied()
+    }
+    /// Returns the network of all edges, without timestamps
+    pub fn to_network(&self) -> CitationNetwork {
+        self.subgraph_where(|_| true)
+    }
+    /// Returns the network of the edges whose timestamps satisfy a predicate
+    ///
+    /// Only the nodes incident to a kept edge are in the result.
+    fn subgraph_where<F: Fn(u32) -> bool>(&self, predicate: F) -> CitationNetwork {
+        let mut network = CitationNetwork::new();
+        for &(from, to, timestamp) in &self.edges {
+            if predicate(timestamp) {
+                network.add_edge(from, to);
+            }
+        }
+        network
+    }
+    /// Returns the network of the edges made strictly before a time
+    ///
+    /// Only the nodes incident to such an edge are in the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - The time before which edges are kept
+    pub fn subgraph_before(&self, t: u32) -> CitationNetwork {
+        self.subgraph_where(|timestamp| timestamp < t)
+    }
+    /// Returns the network of the edges made in the time range `[t_start, t_end)`
+    ///
+    /// Only the nodes incident to such an edge are in the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `t_start` - The first time whose edges are kept
+    /// * `t_end` - The time from which edges are no longer kept
+    pub fn subgraph_in_range(&self, t_start: u32, t_end: u32) -> CitationNetwork {
+        self.subgraph_where(|timestamp| t_start <= timestamp && timestamp < t_end)
+    }
+    /// Returns how the in-degree of a node grows over time
+    ///
+    /// The in-degree is sampled at the earliest timestamp in the network and then
+    /// every `step` until the latest timestamp, which is always the last sample.
+    /// Each sample counts the citations made at or before its time.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex` - The id of the paper
+    /// * `step` - The time between samples
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is 0.
+    pub fn temporal_degree_evolution(&self, vertex: usize, step: u32) -> Vec<(u32, usize)> {
+        assert!(step > 0, "the time step must be positive");
+        let (Some(first), Some(last)) = (
+            self.edges.iter().map(|edge| edge.2).min(),
+            self.edges.iter().map(|edge| edge.2).max(),
+        ) else {
+            return Vec::new();
+        };
+        let mut citations: Vec<u32> = self
+            .edges
+            .iter()
+            .filter(|edge| edge.1 == vertex)
+            .map(|edge| edge.2)
+            .collect();
+        citations.sort_unstable();
+        let mut evolution = Vec::new();
+        let mut t = first;
+        loop {
+            let in_degree = citations.partition_point(|&timestamp| timestamp <= t);
+            evolution.push((t, in_degree));
+            if t >= last {
+                break;
+            }
+            t = t.saturating_add(step).min(last);
+        }
+        evolution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_network() -> TimestampedCitationNetwork {
+        let mut network = TimestampedCitationNetwork::new();
+        network.add_timestamped_edge(1, 0, 199203);
+        network.add_timestamped_edge(2, 0, 199307);
+        network.add_timestamped_edge(3, 0, 199401);
+        network.add_timestamped_edge(3, 1, 199401);
+        network.add_timestamped_edge(4, 2, 199512);
+        network
+    }
+
+    #[test]
+    fn test_subgraphs() {
+        let network = build_network();
+        assert_eq!(network.num_edges(), 5);
+        assert_eq!(network.to_network().num_edges(), 5);
+        let before = network.subgraph_before(199401);
+        assert_eq!(before.num_edges(), 2);
+        assert_eq!(before.size(), 3);
+        assert!(!before.contains_node(3));
+        let range = network.subgraph_in_range(199307, 199512);
+        assert_eq!(range.num_edges(), 3);
+        assert_eq!(range.in_degree(0), 2);
+        assert!(!range.contains_node(4));
+        assert_eq!(network.subgraph_before(199001).size(), 0);
+    }
+
+    #[test]
+    fn test_temporal_degree_evolution() {
+        let network = build_network();
+        assert_eq!(
+            network.temporal_degree_evolution(0, 100),
+            vec![
+                (199203, 1),
+                (199303, 1),
+                (199403, 3),
+                (199503, 3),
+                (199512, 3)
+            ]
+        );
+        assert_eq!(
+            network.temporal_degree_evolution(4, 1000),
+            vec![(199203, 0), (199512, 0)]
+        );
+        assert!(TimestampedCitationNetwork::new()
+            .temporal_degree_evolution(0, 1)
+            .is_empty());
+    }
+}
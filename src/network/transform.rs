@@ -0,0 +1,643 @@
+use std::collections::HashSet;
+
+use crate::network::{CitationNetwork, ContractError, UnknownVertex};
+
+/// The number of edges above which building a complement graph prints a warning
+const COMPLEMENT_WARNING_EDGES: usize = 10_000_000;
+
+impl CitationNetwork {
+    /// Returns the distinct papers cited by a node, excluding the node itself
+    fn distinct_references(&self, vertex: usize) -> HashSet<usize> {
+        self.out_edges_from(vertex)
+            .copied()
+            .filter(|&to| to != vertex)
+            .collect()
+    }
+    /// Returns the complement of the network
+    ///
+    /// The complement has the same nodes, and an edge from i to j (with i != j)
+    /// exactly when i does not cite j in this network. A network with `n` nodes and
+    /// `m` distinct citations has a complement with `n * (n - 1) - m` edges, so a
+    /// warning is printed if that exceeds ten million.
+    pub fn complement(&self) -> CitationNetwork {
+        let num_nodes = self.size();
+        let num_edges: usize = self
+            .nodes()
+            .map(|&vertex| self.complement_degree(vertex))
+            .sum();
+        if num_edges > COMPLEMENT_WARNING_EDGES {
+            eprintln!(
+                "warning: the complement of a network with {} nodes has {} edges",
+                num_nodes, num_edges
+            );
+        }
+        let vertices = self.nodes_sorted();
+        let mut complement = CitationNetwork::new();
+        for &from in &vertices {
+            complement.add_node(from);
+            let references = self.distinct_references(from);
+            for &to in &vertices {
+                if to != from && !references.contains(&to) {
+                    complement.add_edge(from, to);
+                }
+            }
+        }
+        complement
+    }
+    /// Merges one node into another
+    ///
+    /// Every citation to or from `merge` is redirected to `keep`, and `merge` is
+    /// deleted. Citations between the two nodes would become self-loops and are
+    /// dropped, and citations that `keep` ends up having more than once are
+    /// deduplicated. Contracting a node into itself changes nothing. Use
+    /// [`crate::metadata::MetadataStore::merge`] to merge the metadata of the papers
+    /// as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - The id of the paper that remains
+    /// * `merge` - The id of the paper merged into it
+    ///
+    /// # Returns
+    ///
+    /// An error naming `keep` or `merge` if it is not in the network, in which case
+    /// the network is unchanged.
+    pub fn contract_nodes(&mut self, keep: usize, merge: usize) -> Result<(), UnknownVertex> {
+        for vertex in [keep, merge] {
+            if !self.contains_node(vertex) {
+                return Err(UnknownVertex(vertex));
+            }
+        }
+        if keep == merge {
+            return Ok(());
+        }
+        let citers: Vec<usize> = self.in_edges_to(merge).copied().collect();
+        let references: Vec<usize> = self.out_edges_from(merge).copied().collect();
+        self.remove_node(merge);
+        for citer in citers {
+            if citer != keep && citer != merge {
+                self.add_edge(citer, keep);
+            }
+        }
+        for reference in references {
+            if reference != keep && reference != merge {
+                self.add_edge(keep, reference);
+            }
+        }
+        self.dedup_edges_of(keep);
+        Ok(())
+    }
+    /// Merges each group of nodes into its first node
+    ///
+    /// Each group is contracted as by [`CitationNetwork::contract_nodes`], with the
+    /// first node kept and the others merged into it in order. Empty groups are
+    /// skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `groups` - The groups of ids of the same papers, which must not share nodes
+    ///
+    /// # Returns
+    ///
+    /// An error naming the first node that is not in the network or that appears
+    /// more than once in the groups. The groups are checked before any of them is
+    /// contracted, so the network is unchanged on error.
+    pub fn contract_groups(&mut self, groups: &[Vec<usize>]) -> Result<(), ContractError> {
+        let mut seen = HashSet::new();
+        for &vertex in groups.iter().flatten() {
+            if !self.contains_node(vertex) {
+                return Err(ContractError::UnknownVertex(vertex));
+            }
+            if !seen.insert(vertex) {
+                return Err(ContractError::RepeatedVertex(vertex));
+            }
+        }
+        for group in groups {
+            if let Some((&keep, merged)) = group.split_first() {
+                for &merge in merged {
+                    self.contract_nodes(keep, merge).unwrap();
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Removes repeated citations to and from a node
+    fn dedup_edges_of(&mut self, vertex: usize) {
+        fn dedup(edges: &mut Vec<usize>) {
+            let mut seen = HashSet::new();
+            edges.retain(|&v| seen.insert(v));
+        }
+        fn dedup_occurrences(edges: &mut Vec<usize>, vertex: usize) {
+            let mut seen = false;
+            edges.retain(|&v| v != vertex || !std::mem::replace(&mut seen, true));
+        }
+        let citers = self.in_edges.get_mut(&vertex).unwrap();
+        dedup(citers);
+        for citer in citers.clone() {
+            dedup_occurrences(self.out_edges.get_mut(&citer).unwrap(), vertex);
+        }
+        let references = self.out_edges.get_mut(&vertex).unwrap();
+        dedup(references);
+        for reference in references.clone() {
+            dedup_occurrences(self.in_edges.get_mut(&reference).unwrap(), vertex);
+        }
+    }
+    /// Returns the subgraph induced by the nodes satisfying a predicate
+    ///
+    /// The subgraph has every node for which `predicate` returns true and every edge
+    /// between two such nodes, including repeated citations, so a predicate that
+    /// accepts every node gives a copy of the network.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - Whether to keep a node, given its id
+    pub fn subgraph_where<F>(&self, predicate: F) -> CitationNetwork
+    where
+        F: Fn(usize) -> bool,
+    {
+        let kept: HashSet<usize> = self
+            .nodes()
+            .copied()
+            .filter(|&vertex| predicate(vertex))
+            .collect();
+        let mut subgraph = CitationNetwork::new();
+        for vertex in self.nodes_sorted() {
+            if kept.contains(&vertex) {
+                subgraph.add_node(vertex);
+                for &reference in self.out_edges_from(vertex) {
+                    if kept.contains(&reference) {
+                        subgraph.add_edge(vertex, reference);
+                    }
+                }
+            }
+        }
+        subgraph
+    }
+    /// Returns the symmetric closure of the network
+    ///
+    /// Papers joined by a citation in either direction are joined in both
+    /// directions, once each, so a mutual citation is not doubled and repeated
+    /// citations are kept once. A self-citation stays a single self-loop. Measures
+    /// computed on the result, such as in-degree or betweenness, are those of the
+    /// undirected network.
+    pub fn to_undirected(&self) -> CitationNetwork {
+        let mut undirected = CitationNetwork::new();
+        for vertex in self.nodes_sorted() {
+            undirected.add_node(vertex);
+            for neighbor in self.neighbors(vertex) {
+                undirected.add_edge(vertex, neighbor);
+            }
+            if self.contains_edge(vertex, vertex) {
+                undirected.add_edge(vertex, vertex);
+            }
+        }
+        undirected
+    }
+    /// Returns whether every citation is matched by a citation in the other
+    /// direction
+    ///
+    /// Repeated citations are not counted, so a network is symmetric exactly when it
+    /// has the same distinct citations as [`CitationNetwork::to_undirected`].
+    pub fn is_symmetric(&self) -> bool {
+        self.edges_iter()
+            .all(|(from, to)| self.contains_edge(to, from))
+    }
+    /// Repeatedly removes the nodes cited fewer than `min_in_degree` times
+    ///
+    /// Removing a paper also removes its citations, which can drop the papers it
+    /// cites below the threshold in turn, so removal continues until every remaining
+    /// paper is cited at least `min_in_degree` times. In-degrees count repeated
+    /// citations and self-citations, as [`CitationNetwork::in_degree`] does. The
+    /// pruning uses a worklist, so long chains of removals do not grow the stack.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_in_degree` - The smallest in-degree a paper keeps
+    ///
+    /// # Returns
+    ///
+    /// * `removed` - The number of nodes removed
+    pub fn prune_by_in_degree(&mut self, min_in_degree: usize) -> usize {
+        self.prune_in_rounds(min_in_degree).0
+    }
+    /// Removes the nodes cited fewer than `min_in_degree` times in rounds
+    ///
+    /// The first round removes the nodes below the threshold, and each later round
+    /// the nodes that the previous one dropped below it. Only the references of
+    /// removed nodes can drop, so each node and citation is looked at a bounded
+    /// number of times whatever the number of rounds.
+    ///
+    /// # Returns
+    ///
+    /// * `(removed, rounds)` - The number of nodes removed, and the number of rounds
+    ///   that removed at least one node
+    fn prune_in_rounds(&mut self, min_in_degree: usize) -> (usize, usize) {
+        let mut round: Vec<usize> = self
+            .nodes_sorted()
+            .into_iter()
+            .filter(|&vertex| self.in_degree(vertex) < min_in_degree)
+            .collect();
+        // In-degrees only drop, so a node is queued once and removed once
+        let mut queued: HashSet<usize> = round.iter().copied().collect();
+        let (mut removed, mut rounds) = (0, 0);
+        while !round.is_empty() {
+            rounds += 1;
+            let mut next = Vec::new();
+            for vertex in round {
+                let references = self.distinct_references(vertex);
+                self.remove_node(vertex);
+                removed += 1;
+                for reference in references {
+                    if self.in_degree(reference) < min_in_degree && queued.insert(reference) {
+                        next.push(reference);
+                    }
+                }
+            }
+            round = next;
+        }
+        (removed, rounds)
+    }
+    /// Returns the subgraph of the papers cited at least `min_in_degree` times in
+    /// this network
+    ///
+    /// This is a single pass: in-degrees are those of this network, so papers in the
+    /// result can be cited fewer times within it. See
+    /// [`CitationNetwork::filter_by_min_in_degree`] for the filter repeated until
+    /// that cannot happen.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_in_degree` - The smallest in-degree a paper keeps
+    pub fn filter_by_min_in_degree_once(&self, min_in_degree: usize) -> CitationNetwork {
+        self.subgraph_where(|vertex| self.in_degree(vertex) >= min_in_degree)
+    }
+    /// Returns the backbone of papers cited at least `min_in_degree` times by other
+    /// papers of the backbone
+    ///
+    /// This is [`CitationNetwork::filter_by_min_in_degree_once`] applied in rounds
+    /// until a round removes nothing, and leaves the same nodes as
+    /// [`CitationNetwork::prune_by_in_degree`], which removes them in place. A round
+    /// only looks at the papers cited by those removed in the previous one, so the
+    /// whole filter takes time proportional to the size of the network however many
+    /// rounds it needs.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_in_degree` - The smallest in-degree a paper keeps
+    ///
+    /// # Returns
+    ///
+    /// * `(backbone, rounds)` - The filtered network, and the number of rounds that
+    ///   removed at least one paper
+    pub fn filter_by_min_in_degree(&self, min_in_degree: usize) -> (CitationNetwork, usize) {
+        let mut backbone = self.clone();
+        let (_, rounds) = backbone.prune_in_rounds(min_in_degree);
+        (backbone, rounds)
+    }
+    /// Returns the out-degree of a node in the complement of the network
+    ///
+    /// The complement is not built, so this takes time proportional to the number of
+    /// papers the node cites. Nodes not in the network have complement degree 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex` - The id of the paper
+    pub fn complement_degree(&self, vertex: usize) -> usize {
+        if !self.contains_node(vertex) {
+            return 0;
+        }
+        self.size() - 1 - self.distinct_references(vertex).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_graphs::{complete_dag, path, zachary_like};
+
+    #[test]
+    fn test_complement() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(1, 2);
+        graph.add_edge(1, 2);
+        graph.add_node(3);
+        let complement = graph.complement();
+        assert_eq!(complement.size(), 4);
+        assert_eq!(complement.num_edges(), 4 * 3 - 3);
+        let mut references: Vec<_> = complement.out_edges_from(0).copied().collect();
+        references.sort_unstable();
+        assert_eq!(references, vec![3]);
+        let mut references: Vec<_> = complement.out_edges_from(3).copied().collect();
+        references.sort_unstable();
+        assert_eq!(references, vec![0, 1, 2]);
+        assert_eq!(complement.complement().num_edges(), 3);
+    }
+
+    #[test]
+    fn test_complement_degree() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(0, 0);
+        graph.add_edge(1, 2);
+        graph.add_node(3);
+        assert_eq!(graph.complement_degree(0), 1);
+        assert_eq!(graph.complement_degree(1), 2);
+        assert_eq!(graph.complement_degree(3), 3);
+        assert_eq!(graph.complement_degree(42), 0);
+        let complement = graph.complement();
+        for &vertex in graph.nodes() {
+            assert_eq!(
+                complement.out_edges_from(vertex).count(),
+                graph.complement_degree(vertex)
+            );
+        }
+    }
+
+    #[test]
+    fn test_contract_nodes() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(1, 3);
+        graph.add_edge(2, 3);
+        graph.add_edge(2, 1);
+        graph.add_edge(4, 2);
+        graph.contract_nodes(1, 2).unwrap();
+        assert_eq!(graph.size(), 4);
+        // 0 -> 1, 1 -> 3 and 4 -> 1 remain; the duplicates and 2 -> 1 are gone
+        assert_eq!(graph.num_edges(), 3);
+        assert!(!graph.contains_node(2));
+        assert_eq!(graph.in_degree(1), 2);
+        assert_eq!(graph.out_degree(1), 1);
+        assert_eq!(
+            graph.out_edges_from(1).copied().collect::<Vec<_>>(),
+            vec![3]
+        );
+        assert_eq!(
+            graph.out_edges_from(0).copied().collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert_eq!(graph.in_edges_to(3).copied().collect::<Vec<_>>(), vec![1]);
+        graph.contract_nodes(1, 1).unwrap();
+        assert_eq!(graph.size(), 4);
+        assert_eq!(graph.num_edges(), 3);
+    }
+
+    #[test]
+    fn test_contract_merged_node_citing_kept_node() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(2, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.contract_nodes(1, 2).unwrap();
+        assert_eq!(graph.nodes_sorted(), vec![1, 3]);
+        assert_eq!(graph.edges_iter().collect::<Vec<_>>(), vec![(1, 3)]);
+        assert_eq!(graph.in_degree(1), 0);
+    }
+
+    #[test]
+    fn test_contract_unknown_vertex() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        assert_eq!(graph.contract_nodes(1, 42), Err(UnknownVertex(42)));
+        assert_eq!(graph.contract_nodes(42, 1), Err(UnknownVertex(42)));
+        assert_eq!(
+            graph.contract_groups(&[vec![0, 1], vec![7]]),
+            Err(ContractError::UnknownVertex(7))
+        );
+        assert_eq!(graph.size(), 2);
+        assert_eq!(graph.num_edges(), 1);
+    }
+
+    #[test]
+    fn test_contract_repeated_vertex() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 4);
+        let before = graph.clone();
+        // The first group could be contracted, but the groups share paper 3
+        for groups in [
+            vec![vec![0, 1], vec![2, 3], vec![4, 3]],
+            vec![vec![0, 1], vec![2, 3, 3]],
+            vec![vec![0, 1], vec![3, 3]],
+        ] {
+            let error = graph.contract_groups(&groups).unwrap_err();
+            assert_eq!(error, ContractError::RepeatedVertex(3));
+            assert!(graph.is_isomorphic_as_labeled(&before));
+            assert_eq!(
+                graph.edges_iter().collect::<Vec<_>>(),
+                before.edges_iter().collect::<Vec<_>>()
+            );
+        }
+        assert_eq!(
+            graph
+                .contract_groups(&[vec![2, 2]])
+                .unwrap_err()
+                .to_string(),
+            "vertex 2 appears in the groups more than once"
+        );
+        assert_eq!(
+            graph.contract_groups(&[vec![9]]).unwrap_err().to_string(),
+            "vertex 9 is not in the network"
+        );
+    }
+
+    #[test]
+    fn test_contract_groups() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(5, 3);
+        graph.add_edge(4, 6);
+        graph.add_edge(3, 4);
+        graph
+            .contract_groups(&[vec![1, 2, 3], vec![], vec![4, 5]])
+            .unwrap();
+        assert_eq!(graph.nodes_sorted(), vec![0, 1, 4, 6]);
+        assert_eq!(graph.in_degree(1), 2);
+        assert_eq!(graph.out_degree(1), 1);
+        assert_eq!(graph.out_degree(4), 2);
+        assert_eq!(graph.num_edges(), 4);
+    }
+
+    #[test]
+    fn test_subgraph_where() {
+        let mut graph = CitationNetwork::new();
+        for from in 0..5 {
+            for to in 10..15 {
+                graph.add_edge(from, to);
+            }
+        }
+        graph.add_edge(0, 10);
+        graph.add_edge(10, 10);
+        graph.add_node(20);
+        let copy = graph.subgraph_where(|v| graph.nodes().any(|&n| n == v));
+        assert!(copy.is_isomorphic_as_labeled(&graph));
+        assert_eq!(copy.num_edges(), graph.num_edges());
+        let cited = graph.subgraph_where(|v| graph.in_degree(v) > 5);
+        assert_eq!(cited.nodes_sorted(), vec![10]);
+        assert_eq!(cited.num_edges(), 1);
+        let even = graph.subgraph_where(|v| v % 2 == 0);
+        assert_eq!(even.nodes_sorted(), vec![0, 2, 4, 10, 12, 14, 20]);
+        assert_eq!(even.num_edges(), 3 * 3 + 1 + 1);
+        assert_eq!(graph.subgraph_where(|_| false).size(), 0);
+    }
+
+    #[test]
+    fn test_to_undirected() {
+        use crate::centrality::centrality::Centrality;
+        use crate::centrality::degree_centrality::calculate_degree_centrality;
+
+        // 0 and 1 cite each other; every other citation is one-way
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 0);
+        graph.add_edge(0, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 1);
+        graph.add_node(4);
+        assert!(!graph.is_symmetric());
+        let undirected = graph.to_undirected();
+        assert!(undirected.is_symmetric());
+        assert_eq!(undirected.nodes_sorted(), graph.nodes_sorted());
+        assert_eq!(undirected.num_edges(), 2 * 4);
+        for centrality in calculate_degree_centrality(&undirected).iter() {
+            let vertex = centrality.vertex();
+            let mutual = usize::from(vertex <= 1);
+            let expected = graph.in_degree(vertex) + graph.out_degree(vertex) - mutual;
+            assert_eq!(centrality.score() as usize, expected);
+        }
+        assert_eq!(
+            undirected.to_undirected().num_edges(),
+            undirected.num_edges()
+        );
+    }
+
+    #[test]
+    fn test_to_undirected_with_repeats_and_loops() {
+        let mut graph = CitationNetwork::new();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 1);
+        graph.add_edge(2, 2);
+        graph.add_edge(2, 2);
+        let undirected = graph.to_undirected();
+        assert_eq!(
+            undirected.edges_iter().collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0), (2, 2)]
+        );
+        graph.add_edge(1, 0);
+        assert!(graph.is_symmetric());
+        assert!(CitationNetwork::new().is_symmetric());
+    }
+
+    #[test]
+    fn test_undirected_measures_of_projection() {
+        // Measures that already treat citations as undirected are unchanged by the
+        // projection, which has one citation in each direction per linked pair
+        for graph in [complete_dag(4), path(5), zachary_like()] {
+            let undirected = graph.to_undirected();
+            assert_eq!(undirected.num_edges(), 2 * graph.num_edges());
+            assert_eq!(undirected.triangle_count(), graph.triangle_count());
+            assert_eq!(
+                undirected.average_clustering_coefficient(),
+                graph.average_clustering_coefficient()
+            );
+            assert_eq!(undirected.is_bipartite(), graph.is_bipartite());
+        }
+    }
+
+    #[test]
+    fn test_prune_by_in_degree() {
+        // 0 -> 1 -> 2 -> ... -> 99 is a chain that unravels one paper at a time
+        let mut graph = path(100);
+        assert_eq!(graph.prune_by_in_degree(1), 100);
+        assert_eq!(graph.size(), 0);
+
+        // 10, 11 and 12 cite each other; 13 is cited only by 14, which nobody cites
+        let mut graph = CitationNetwork::new();
+        for (from, to) in [(10, 11), (11, 12), (12, 10), (11, 10), (12, 11), (10, 12)] {
+            graph.add_edge(from, to);
+        }
+        graph.add_edge(14, 13);
+        graph.add_edge(13, 10);
+        graph.add_edge(14, 14);
+        let mut pruned = graph.clone();
+        assert_eq!(pruned.prune_by_in_degree(2), 2);
+        assert_eq!(pruned.nodes_sorted(), vec![10, 11, 12]);
+        assert!(pruned
+            .nodes_sorted()
+            .into_iter()
+            .all(|vertex| pruned.in_degree(vertex) >= 2));
+        assert_eq!(graph.clone().prune_by_in_degree(0), 0);
+        assert_eq!(graph.prune_by_in_degree(3), 5);
+    }
+
+    #[test]
+    fn test_prune_random_network() {
+        let mut graph = crate::generators::erdos_renyi(300, 0.02, 4);
+        let size = graph.size();
+        let removed = graph.prune_by_in_degree(3);
+        assert_eq!(graph.size(), size - removed);
+        assert!(removed > 0 && graph.size() > 0);
+        assert!(graph
+            .nodes_sorted()
+            .into_iter()
+            .all(|vertex| graph.in_degree(vertex) >= 3));
+    }
+
+    #[test]
+    fn test_filter_by_min_in_degree_cascades() {
+        // Each paper in the chain cites the next, so only the first is uncited
+        let graph = path(6);
+        let once = graph.filter_by_min_in_degree_once(1);
+        assert_eq!(once.nodes_sorted(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(once.num_edges(), 4);
+        assert_eq!(once.in_degree(1), 0);
+        let (backbone, rounds) = graph.filter_by_min_in_degree(1);
+        assert_eq!(backbone.size(), 0);
+        assert_eq!(rounds, 6);
+        let (unchanged, rounds) = graph.filter_by_min_in_degree(0);
+        assert_eq!(unchanged.size(), 6);
+        assert_eq!(rounds, 0);
+    }
+
+    #[test]
+    fn test_filter_by_min_in_degree_matches_pruning() {
+        let graph = crate::generators::erdos_renyi(300, 0.05, 11);
+        let (backbone, rounds) = graph.filter_by_min_in_degree(8);
+        let mut pruned = graph.clone();
+        pruned.prune_by_in_degree(8);
+        assert!(rounds > 0);
+        assert!(backbone.size() > 0);
+        assert_eq!(backbone.nodes_sorted(), pruned.nodes_sorted());
+        assert!(backbone.edges_iter().eq(pruned.edges_iter()));
+        assert!(backbone
+            .nodes()
+            .all(|&vertex| backbone.in_degree(vertex) >= 8));
+        // The rounds are those of the single-pass filter applied until it settles
+        let mut filtered = graph.clone();
+        let mut expected = 0;
+        loop {
+            let next = filtered.filter_by_min_in_degree_once(8);
+            if next.size() == filtered.size() {
+                break;
+            }
+            filtered = next;
+            expected += 1;
+        }
+        assert_eq!(rounds, expected);
+        assert_eq!(filtered.nodes_sorted(), backbone.nodes_sorted());
+    }
+
+    #[test]
+    fn test_filter_long_chain() {
+        // Every round removes only the head of the chain
+        let (backbone, rounds) = path(50_000).filter_by_min_in_degree(1);
+        assert_eq!(backbone.size(), 0);
+        assert_eq!(rounds, 50_000);
+    }
+}
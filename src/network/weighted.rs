@@ -0,0 +1,438 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::network::CitationNetwork;
+
+/// A citation network with a weight on every citation
+///
+/// Each ordered pair of papers has at most one weighted citation; adding a citation
+/// that is already in the network replaces its weight.
+#[derive(Clone, Debug, Default)]
+pub struct WeightedCitationNetwork {
+    network: CitationNetwork,
+    weights: HashMap<(usize, usize), f64>,
+}
+
+impl WeightedCitationNetwork {
+    /// Creates an empty network
+    pub fn new() -> WeightedCitationNetwork {
+        WeightedCitationNetwork::default()
+    }
+    /// Adds a weighted edge to the network
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The id of the citing paper
+    /// * `to` - The id of the cited paper
+    /// * `weight` - The weight of the citation
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: f64) {
+        if self.weights.insert((from, to), weight).is_none() {
+            self.network.add_edge(from, to);
+        }
+    }
+    /// Adds a node without any edges to the network
+    pub fn add_node(&mut self, vertex: usize) {
+        self.network.add_node(vertex);
+    }
+    /// Returns the weight of a citation, or `None` if it is not in the network
+    pub fn weight(&self, from: usize, to: usize) -> Option<f64> {
+        self.weights.get(&(from, to)).copied()
+    }
+    /// Returns the network without its weights
+    pub fn network(&self) -> &CitationNetwork {
+        &self.network
+    }
+    /// Returns the minimum spanning arborescence rooted at a node
+    ///
+    /// An arborescence rooted at `root` has exactly one path from the root to every
+    /// other node, so it has `n - 1` edges, all pointing away from the root. This is
+    /// Edmonds' algorithm: every node except the root picks its cheapest incoming
+    /// citation, and each cycle this creates is contracted into a single node, with
+    /// the weights of the citations entering it reduced by the cheapest citation they
+    /// would replace, until no cycle is left. Self-citations are ignored, negative
+    /// weights are allowed, and ties are broken in favour of the citation with the
+    /// smallest ids. It runs in `O(V E)` time.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The id of the paper the arborescence starts from
+    ///
+    /// # Returns
+    ///
+    /// * `Some(arborescence)` - Every node of the network with the edges of the arborescence
+    /// * `None` - If `root` is not in the network or some node cannot be reached from it
+    pub fn minimum_spanning_arborescence(&self, root: usize) -> Option<CitationNetwork> {
+        if !self.network.contains_node(root) {
+            return None;
+        }
+        let nodes = self.network.nodes_sorted();
+        let index: HashMap<usize, usize> = nodes.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+        let edges: Vec<(usize, usize, f64)> = self
+            .network
+            .edges_iter()
+            .filter(|&(from, to)| from != to)
+            .map(|(from, to)| (index[&from], index[&to], self.weights[&(from, to)]))
+            .collect();
+        let chosen = arborescence(nodes.len(), index[&root], &edges)?;
+        let mut tree = CitationNetwork::new();
+        for &vertex in &nodes {
+            tree.add_node(vertex);
+        }
+        for edge in chosen {
+            let (from, to, _) = edges[edge];
+            tree.add_edge(nodes[from], nodes[to]);
+        }
+        Some(tree)
+    }
+    /// Returns the shortest distance from a node to every node it reaches
+    ///
+    /// This is Dijkstra's algorithm with a binary heap, running in `O(E log V)` time.
+    /// The source is at distance 0, and a source that is not in the network reaches
+    /// nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The id of the paper the paths start from
+    ///
+    /// # Panics
+    ///
+    /// Panics if some citation has a negative or NaN weight, for which Dijkstra's
+    /// algorithm gives wrong answers; use Bellman-Ford for such networks instead.
+    pub fn dijkstra(&self, source: usize) -> HashMap<usize, f64> {
+        self.shortest_paths(source).0
+    }
+    /// Returns a shortest path between two nodes together with its total weight
+    ///
+    /// See [`WeightedCitationNetwork::dijkstra`]. When several paths are equally
+    /// short, the one found first is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The id of the paper the path starts from
+    /// * `target` - The id of the paper the path ends at
+    ///
+    /// # Returns
+    ///
+    /// * `Some((path, weight))` - The papers on the path from `source` to `target`, both included
+    /// * `None` - If `target` cannot be reached from `source`
+    ///
+    /// # Panics
+    ///
+    /// Panics if some citation has a negative or NaN weight.
+    pub fn dijkstra_path(&self, source: usize, target: usize) -> Option<(Vec<usize>, f64)> {
+        let (distances, predecessors) = self.shortest_paths(source);
+        let distance = *distances.get(&target)?;
+        let mut path = vec![target];
+        let mut vertex = target;
+        while let Some(&previous) = predecessors.get(&vertex) {
+            path.push(previous);
+            vertex = previous;
+        }
+        path.reverse();
+        Some((path, distance))
+    }
+    /// Runs Dijkstra's algorithm, returning the distances and the predecessor of
+    /// every reached node except the source
+    fn shortest_paths(&self, source: usize) -> (HashMap<usize, f64>, HashMap<usize, usize>) {
+        assert!(
+            self.weights.values().all(|&weight| weight >= 0.0),
+            "Dijkstra's algorithm requires non-negative weights"
+        );
+        let mut distances = HashMap::new();
+        let mut predecessors = HashMap::new();
+        if !self.network.contains_node(source) {
+            return (distances, predecessors);
+        }
+        let mut settled = HashMap::new();
+        let mut heap = BinaryHeap::from([Candidate {
+            distance: 0.0,
+            vertex: source,
+        }]);
+        distances.insert(source, 0.0);
+        while let Some(Candidate { distance, vertex }) = heap.pop() {
+            if settled.contains_key(&vertex) {
+                continue;
+            }
+            settled.insert(vertex, distance);
+            for &reference in self.network.out_edges_from(vertex) {
+                let candidate = distance + self.weights[&(vertex, reference)];
+                if distances
+                    .get(&reference)
+                    .is_none_or(|&known| candidate < known)
+                {
+                    distances.insert(reference, candidate);
+                    predecessors.insert(reference, vertex);
+                    heap.push(Candidate {
+                        distance: candidate,
+                        vertex: reference,
+                    });
+                }
+            }
+        }
+        (settled, predecessors)
+    }
+}
+
+/// A node waiting in the heap of Dijkstra's algorithm, ordered so the closest node,
+/// and among equally close nodes the smallest id, comes out first
+#[derive(PartialEq)]
+struct Candidate {
+    distance: f64,
+    vertex: usize,
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Candidate) -> Ordering {
+        other
+            .distance
+            .total_cmp(&self.distance)
+            .then(other.vertex.cmp(&self.vertex))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Candidate) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<&CitationNetwork> for WeightedCitationNetwork {
+    /// Gives every distinct citation of a network weight 1
+    fn from(network: &CitationNetwork) -> WeightedCitationNetwork {
+        let mut weighted = WeightedCitationNetwork::new();
+        for vertex in network.nodes_sorted() {
+            weighted.add_node(vertex);
+        }
+        for (from, to) in network.edges_iter() {
+            weighted.add_edge(from, to, 1.0);
+        }
+        weighted
+    }
+}
+
+impl CitationNetwork {
+    /// Returns a spanning arborescence rooted at a node, with every citation weighing 1
+    ///
+    /// See [`WeightedCitationNetwork::minimum_spanning_arborescence`]; with equal
+    /// weights every spanning arborescence is minimal.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The id of the paper the arborescence starts from
+    pub fn minimum_spanning_arborescence(&self, root: usize) -> Option<CitationNetwork> {
+        WeightedCitationNetwork::from(self).minimum_spanning_arborescence(root)
+    }
+}
+
+/// Runs Edmonds' algorithm on the nodes `0..n`
+///
+/// # Arguments
+///
+/// * `n` - The number of nodes
+/// * `root` - The root of the arborescence
+/// * `edges` - The edges with their weights, without self-loops
+///
+/// # Returns
+///
+/// * `Some(chosen)` - The indices in `edges` of the edges of the arborescence
+/// * `None` - If some node cannot be reached from the root
+fn arborescence(n: usize, root: usize, edges: &[(usize, usize, f64)]) -> Option<Vec<usize>> {
+    let mut cheapest: Vec<Option<usize>> = vec![None; n];
+    for (i, &(_, to, weight)) in edges.iter().enumerate() {
+        if to != root && cheapest[to].is_none_or(|j| weight < edges[j].2) {
+            cheapest[to] = Some(i);
+        }
+    }
+    let mut parent = vec![root; n];
+    for vertex in (0..n).filter(|&vertex| vertex != root) {
+        parent[vertex] = edges[cheapest[vertex]?].0;
+    }
+    let Some(cycle) = find_cycle(root, &parent) else {
+        return Some(cheapest.into_iter().flatten().collect());
+    };
+    // Contract the cycle into the node `k - 1` and renumber the others `0..k - 1`
+    let mut contracted = vec![usize::MAX; n];
+    for &vertex in &cycle {
+        contracted[vertex] = n - cycle.len();
+    }
+    for (next, vertex) in contracted
+        .iter_mut()
+        .filter(|c| **c == usize::MAX)
+        .enumerate()
+    {
+        *vertex = next;
+    }
+    let mut reduced = Vec::new();
+    let mut original = Vec::new();
+    for (i, &(from, to, weight)) in edges.iter().enumerate() {
+        if contracted[from] != contracted[to] {
+            let weight = if contracted[to] == n - cycle.len() {
+                weight - edges[cheapest[to].unwrap()].2
+            } else {
+                weight
+            };
+            reduced.push((contracted[from], contracted[to], weight));
+            original.push(i);
+        }
+    }
+    let chosen: Vec<usize> = arborescence(n - cycle.len() + 1, contracted[root], &reduced)?
+        .into_iter()
+        .map(|i| original[i])
+        .collect();
+    // The cycle keeps all its edges except the one into the node the tree enters at
+    let entry = chosen
+        .iter()
+        .map(|&i| edges[i].1)
+        .find(|to| cycle.contains(to))
+        .unwrap();
+    let kept = cycle
+        .iter()
+        .filter(|&&vertex| vertex != entry)
+        .map(|&vertex| cheapest[vertex].unwrap());
+    Some(chosen.iter().copied().chain(kept).collect())
+}
+
+/// Returns the nodes of a cycle in the graph where each node points to its parent,
+/// if there is one
+fn find_cycle(root: usize, parent: &[usize]) -> Option<Vec<usize>> {
+    // 0 is unvisited, and `start + 1` marks the nodes visited from `start`
+    let mut visited = vec![0; parent.len()];
+    for start in 0..parent.len() {
+        let mut vertex = start;
+        while vertex != root && visited[vertex] == 0 {
+            visited[vertex] = start + 1;
+            vertex = parent[vertex];
+        }
+        if vertex != root && visited[vertex] == start + 1 {
+            let mut cycle = vec![vertex];
+            let mut other = parent[vertex];
+            while other != vertex {
+                cycle.push(other);
+                other = parent[other];
+            }
+            return Some(cycle);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_network() -> WeightedCitationNetwork {
+        let mut network = WeightedCitationNetwork::new();
+        network.add_edge(0, 1, 5.0);
+        network.add_edge(0, 2, 6.0);
+        network.add_edge(1, 2, 1.0);
+        network.add_edge(2, 1, 2.0);
+        network.add_edge(2, 3, 3.0);
+        network.add_edge(1, 3, 4.0);
+        network.add_edge(3, 2, 0.5);
+        network
+    }
+
+    #[test]
+    fn test_minimum_spanning_arborescence() {
+        let network = build_network();
+        // The cheapest citations into 2 and 3 form a cycle, and so do those into
+        // 1 and the contracted pair
+        let tree = network.minimum_spanning_arborescence(0).unwrap();
+        assert_eq!(
+            tree.edges_iter().collect::<Vec<_>>(),
+            vec![(0, 1), (1, 2), (2, 3)]
+        );
+        let total: f64 = tree
+            .edges_iter()
+            .map(|(from, to)| network.weight(from, to).unwrap())
+            .sum();
+        assert_eq!(total, 9.0);
+    }
+
+    #[test]
+    fn test_minimum_spanning_arborescence_without_cycles() {
+        let mut network = build_network();
+        network.add_edge(0, 3, 1.0);
+        network.add_edge(0, 3, 2.5);
+        assert_eq!(network.weight(0, 3), Some(2.5));
+        // The cheapest citations into 1, 2 and 3 now form a path from the root
+        let tree = network.minimum_spanning_arborescence(0).unwrap();
+        assert_eq!(
+            tree.edges_iter().collect::<Vec<_>>(),
+            vec![(0, 3), (2, 1), (3, 2)]
+        );
+    }
+
+    #[test]
+    fn test_unreachable_nodes() {
+        let mut network = build_network();
+        // Nothing cites 0
+        assert!(network.minimum_spanning_arborescence(3).is_none());
+        assert!(network.minimum_spanning_arborescence(42).is_none());
+        network.add_node(4);
+        assert!(network.minimum_spanning_arborescence(0).is_none());
+    }
+
+    #[test]
+    fn test_unweighted_arborescence() {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        network.add_edge(0, 2);
+        network.add_edge(1, 2);
+        network.add_edge(2, 0);
+        network.add_edge(2, 2);
+        let tree = network.minimum_spanning_arborescence(1).unwrap();
+        assert_eq!(tree.size(), 3);
+        assert_eq!(tree.num_edges(), 2);
+        assert_eq!(tree.edges_iter().collect::<Vec<_>>(), vec![(1, 2), (2, 0)]);
+    }
+
+    fn build_road_network() -> WeightedCitationNetwork {
+        let mut network = WeightedCitationNetwork::new();
+        network.add_edge(0, 1, 4.0);
+        network.add_edge(0, 2, 1.0);
+        network.add_edge(2, 1, 2.0);
+        network.add_edge(1, 3, 1.0);
+        network.add_edge(2, 3, 5.0);
+        network.add_edge(3, 4, 3.0);
+        network.add_edge(5, 0, 1.0);
+        network
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let network = build_road_network();
+        let distances = network.dijkstra(0);
+        assert_eq!(distances.len(), 5);
+        assert_eq!(distances[&0], 0.0);
+        assert_eq!(distances[&2], 1.0);
+        assert_eq!(distances[&1], 3.0);
+        assert_eq!(distances[&3], 4.0);
+        assert_eq!(distances[&4], 7.0);
+        assert!(!distances.contains_key(&5));
+        assert!(network.dijkstra(42).is_empty());
+    }
+
+    #[test]
+    fn test_dijkstra_path() {
+        let network = build_road_network();
+        assert_eq!(
+            network.dijkstra_path(0, 4),
+            Some((vec![0, 2, 1, 3, 4], 7.0))
+        );
+        assert_eq!(network.dijkstra_path(5, 1), Some((vec![5, 0, 2, 1], 4.0)));
+        assert_eq!(network.dijkstra_path(3, 3), Some((vec![3], 0.0)));
+        assert_eq!(network.dijkstra_path(4, 0), None);
+        assert_eq!(network.dijkstra_path(0, 42), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "non-negative weights")]
+    fn test_dijkstra_negative_weight() {
+        let mut network = build_road_network();
+        network.add_edge(4, 0, -1.0);
+        network.dijkstra(0);
+    }
+}
@@ -0,0 +1,90 @@
+/// A small seeded pseudo-random number generator
+///
+/// The generator is SplitMix64, which is fast, has a 64-bit state and passes
+/// BigCrush. It is used wherever an algorithm samples at random, so that results
+/// are reproducible from a seed on every platform. It is not suitable for
+/// cryptographic use.
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    /// Creates a generator from a seed
+    pub fn new(seed: u64) -> Random {
+        Random { state: seed }
+    }
+    /// Returns the next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    /// Returns a uniformly distributed float in `[0, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+    /// Returns a uniformly distributed integer in `[0, n)`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "cannot sample from an empty range");
+        // Lemire's multiply-shift reduction; the bias is negligible for graph sizes
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+    /// Shuffles a slice in place with the Fisher-Yates algorithm
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+    /// Returns `k` distinct items chosen uniformly from a slice, or all of them if
+    /// there are fewer than `k`
+    pub fn sample<T: Clone>(&mut self, items: &[T], k: usize) -> Vec<T> {
+        let mut items = items.to_vec();
+        let k = k.min(items.len());
+        for i in 0..k {
+            let j = i + self.below(items.len() - i);
+            items.swap(i, j);
+        }
+        items.truncate(k);
+        items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_is_deterministic() {
+        let mut a = Random::new(42);
+        let mut b = Random::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        let value = a.next_f64();
+        assert!((0.0..1.0).contains(&value));
+        assert!(a.below(10) < 10);
+    }
+
+    #[test]
+    fn test_sample_and_shuffle() {
+        let mut random = Random::new(7);
+        let items: Vec<usize> = (0..20).collect();
+        let mut sample = random.sample(&items, 5);
+        assert_eq!(sample.len(), 5);
+        sample.sort_unstable();
+        sample.dedup();
+        assert_eq!(sample.len(), 5);
+        assert_eq!(random.sample(&items, 50).len(), 20);
+        let mut shuffled = items.clone();
+        random.shuffle(&mut shuffled);
+        shuffled.sort_unstable();
+        assert_eq!(shuffled, items);
+    }
+}
@@ -0,0 +1,372 @@
+//! An interactive session for exploring a network from a prompt
+//!
+//! A [`Session`] reads one command per line, such as `top pagerank 10` or
+//! `path 9501001 9905111`, and writes its answer. Rankings are cached for the whole
+//! session, so each measure is only computed once however often it is used.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use crate::analysis::profile::node_profile;
+use crate::centrality::centrality::CentralityRank;
+use crate::centrality::measure::{find_measure, registry};
+use crate::centrality::pagerank_centrality::PageRankCentrality;
+use crate::network::{CitationNetwork, Direction, UnknownVertex};
+
+/// The number of papers listed by `top` when no number is given
+const DEFAULT_TOP: usize = 10;
+
+/// The help text listing the commands
+const HELP: &str = "commands:
+  top <measure> [n]                 the n highest ranked papers of a measure
+  profile <vertex>                  everything known about a paper
+  neighbors <vertex> [in|out|both]  the citers or references of a paper, or both
+  path <from> <to>                  a shortest chain of citations between two papers
+  help                              this list
+  quit                              end the session
+";
+
+/// A command of an interactive session
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// List the highest ranked papers of a measure
+    Top { measure: String, n: usize },
+    /// Show the profile of a paper
+    Profile(usize),
+    /// List the papers adjacent to a paper in a direction
+    Neighbors(usize, Direction),
+    /// Show a shortest chain of citations between two papers
+    Path(usize, usize),
+    /// List the commands
+    Help,
+    /// End the session
+    Quit,
+}
+
+impl Command {
+    /// Parses a line of input into a command
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The words of the command, separated by whitespace
+    ///
+    /// # Returns
+    ///
+    /// The command, `None` for a blank line, or a message describing what is wrong
+    /// with the line.
+    pub fn parse(line: &str) -> Result<Option<Command>, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((&name, args)) = words.split_first() else {
+            return Ok(None);
+        };
+        let command = match (name, args) {
+            ("top", [measure]) => Command::Top {
+                measure: measure.to_string(),
+                n: DEFAULT_TOP,
+            },
+            ("top", [measure, n]) => Command::Top {
+                measure: measure.to_string(),
+                n: n.parse().map_err(|_| format!("invalid count: {}", n))?,
+            },
+            ("top", _) => return Err("usage: top <measure> [n]".to_string()),
+            ("profile", [vertex]) => Command::Profile(parse_vertex(vertex)?),
+            ("profile", _) => return Err("usage: profile <vertex>".to_string()),
+            ("neighbors", [vertex]) => {
+                Command::Neighbors(parse_vertex(vertex)?, Direction::Undirected)
+            }
+            ("neighbors", [vertex, direction]) => {
+                let direction = match *direction {
+                    "in" => Direction::Incoming,
+                    "out" => Direction::Outgoing,
+                    "both" => Direction::Undirected,
+                    _ => {
+                        return Err(format!(
+                            "invalid direction: {} (use in, out or both)",
+                            direction
+                        ))
+                    }
+                };
+                Command::Neighbors(parse_vertex(vertex)?, direction)
+            }
+            ("neighbors", _) => return Err("usage: neighbors <vertex> [in|out|both]".to_string()),
+            ("path", [from, to]) => Command::Path(parse_vertex(from)?, parse_vertex(to)?),
+            ("path", _) => return Err("usage: path <from> <to>".to_string()),
+            ("help", []) => Command::Help,
+            ("quit" | "exit", []) => Command::Quit,
+            _ => {
+                return Err(format!(
+                    "unknown command: {} (type help for the commands)",
+                    name
+                ))
+            }
+        };
+        Ok(Some(command))
+    }
+}
+
+/// Parses the id of a paper
+fn parse_vertex(word: &str) -> Result<usize, String> {
+    word.parse()
+        .map_err(|_| format!("invalid vertex id: {}", word))
+}
+
+/// Joins a list of paper ids with commas, or returns "none" if it is empty
+fn join(vertices: &[usize]) -> String {
+    if vertices.is_empty() {
+        return "none".to_string();
+    }
+    let vertices: Vec<String> = vertices.iter().map(|v| v.to_string()).collect();
+    vertices.join(", ")
+}
+
+/// Returns an error message if a paper is not in the network
+fn check_vertex(network: &CitationNetwork, vertex: usize) -> Result<(), String> {
+    if network.contains_node(vertex) {
+        Ok(())
+    } else {
+        Err(UnknownVertex(vertex).to_string())
+    }
+}
+
+/// An interactive session over a single network
+pub struct Session {
+    network: CitationNetwork,
+    /// The scores of every measure computed so far, by name, in rank order
+    rankings: HashMap<String, Vec<(usize, f64)>>,
+}
+
+impl Session {
+    /// Creates a session over a network, with no rankings computed yet
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network to explore
+    pub fn new(network: CitationNetwork) -> Session {
+        Session {
+            network,
+            rankings: HashMap::new(),
+        }
+    }
+    /// Runs commands read from the input until it ends or a `quit` command
+    ///
+    /// A prompt is written before each command, and a command that fails writes an
+    /// error message and leaves the session running.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Where the commands are read from, one per line
+    /// * `output` - Where the prompts and answers are written
+    pub fn run<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> io::Result<()> {
+        let mut lines = input.lines();
+        loop {
+            write!(output, "> ")?;
+            output.flush()?;
+            let Some(line) = lines.next().transpose()? else {
+                writeln!(output)?;
+                return Ok(());
+            };
+            if !self.execute(&line, &mut output)? {
+                return Ok(());
+            }
+        }
+    }
+    /// Runs a single command
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The command
+    /// * `output` - Where the answer or error message is written
+    ///
+    /// # Returns
+    ///
+    /// Whether the session continues, which is `false` only after `quit`.
+    pub fn execute<W: Write>(&mut self, line: &str, output: &mut W) -> io::Result<bool> {
+        let command = match Command::parse(line) {
+            Ok(Some(command)) => command,
+            Ok(None) => return Ok(true),
+            Err(message) => {
+                writeln!(output, "error: {}", message)?;
+                return Ok(true);
+            }
+        };
+        if command == Command::Quit {
+            return Ok(false);
+        }
+        match self.answer(command) {
+            Ok(answer) => write!(output, "{}", answer)?,
+            Err(message) => writeln!(output, "error: {}", message)?,
+        }
+        Ok(true)
+    }
+    /// Returns the answer to a command, or a message describing why it failed
+    fn answer(&mut self, command: Command) -> Result<String, String> {
+        match command {
+            Command::Top { measure, n } => {
+                let ranking = self.ranking(&measure)?;
+                Ok(ranking
+                    .iter()
+                    .take(n)
+                    .map(|(vertex, score)| format!("vertex {}: {} {}\n", vertex, measure, score))
+                    .collect())
+            }
+            Command::Profile(vertex) => {
+                let profile = node_profile(&self.network, vertex).map_err(|e| e.to_string())?;
+                let pagerank: CentralityRank<f64, PageRankCentrality> = CentralityRank::new(
+                    self.ranking("pagerank")?
+                        .iter()
+                        .map(|&(vertex, score)| PageRankCentrality::new(vertex, score))
+                        .collect(),
+                );
+                Ok(profile.with_pagerank(&pagerank).to_string())
+            }
+            Command::Neighbors(vertex, direction) => {
+                check_vertex(&self.network, vertex)?;
+                let mut neighbors = self.network.neighbors_in(vertex, direction);
+                neighbors.sort_unstable();
+                let relation = match direction {
+                    Direction::Incoming => "is cited by",
+                    Direction::Outgoing => "cites",
+                    Direction::Undirected => "is linked to",
+                };
+                Ok(format!(
+                    "{} {} {} papers: {}\n",
+                    vertex,
+                    relation,
+                    neighbors.len(),
+                    join(&neighbors)
+                ))
+            }
+            Command::Path(from, to) => {
+                check_vertex(&self.network, from)?;
+                check_vertex(&self.network, to)?;
+                match self.network.shortest_path(from, to) {
+                    Some(path) => {
+                        let path: Vec<String> = path.iter().map(|v| v.to_string()).collect();
+                        Ok(format!("{}\n", path.join(" -> ")))
+                    }
+                    None => Ok(format!("no path from {} to {}\n", from, to)),
+                }
+            }
+            Command::Help => Ok(HELP.to_string()),
+            Command::Quit => Ok(String::new()),
+        }
+    }
+    /// Returns the scores of a measure in rank order, computing them on first use
+    fn ranking(&mut self, name: &str) -> Result<&[(usize, f64)], String> {
+        if !self.rankings.contains_key(name) {
+            let measure = find_measure(name).ok_or_else(|| {
+                let names: Vec<_> = registry().iter().map(|m| m.name().to_string()).collect();
+                format!("unknown measure {} (available: {})", name, names.join(", "))
+            })?;
+            let scores = measure.compute(&self.network);
+            self.rankings.insert(name.to_string(), scores);
+        }
+        Ok(&self.rankings[name])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Papers 1 and 2 cite 3, which cites 4
+    fn build_session() -> Session {
+        let network: CitationNetwork = [(1, 3), (2, 3), (3, 4)].into_iter().collect();
+        Session::new(network)
+    }
+
+    fn run_script(session: &mut Session, script: &str) -> String {
+        let mut output = Vec::new();
+        session.run(script.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            Command::parse("top pagerank 3"),
+            Ok(Some(Command::Top {
+                measure: "pagerank".to_string(),
+                n: 3
+            }))
+        );
+        assert_eq!(
+            Command::parse("  neighbors 9905111 in "),
+            Ok(Some(Command::Neighbors(9905111, Direction::Incoming)))
+        );
+        assert_eq!(Command::parse("path 1 2"), Ok(Some(Command::Path(1, 2))));
+        assert_eq!(Command::parse("exit"), Ok(Some(Command::Quit)));
+        assert_eq!(Command::parse(""), Ok(None));
+        assert_eq!(
+            Command::parse("profile x"),
+            Err("invalid vertex id: x".to_string())
+        );
+        assert_eq!(
+            Command::parse("path 1"),
+            Err("usage: path <from> <to>".to_string())
+        );
+        assert_eq!(
+            Command::parse("neighbors 1 up"),
+            Err("invalid direction: up (use in, out or both)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_scripted_session() {
+        let mut session = build_session();
+        let output = run_script(
+            &mut session,
+            "top degree 2\n\
+             neighbors 3 in\n\
+             neighbors 3\n\
+             path 1 4\n\
+             path 4 1\n\
+             \n\
+             quit\n\
+             top degree 1\n",
+        );
+        assert_eq!(
+            output,
+            "> vertex 3: degree 2\n\
+             vertex 4: degree 1\n\
+             > 3 is cited by 2 papers: 1, 2\n\
+             > 3 is linked to 3 papers: 1, 2, 4\n\
+             > 1 -> 3 -> 4\n\
+             > no path from 4 to 1\n\
+             > > "
+        );
+    }
+
+    #[test]
+    fn test_session_errors() {
+        let mut session = build_session();
+        let output = run_script(
+            &mut session,
+            "frobnicate\nprofile 9\npath 1 9\ntop nothing\nneighbors 9 out\n",
+        );
+        assert_eq!(
+            output,
+            "> error: unknown command: frobnicate (type help for the commands)\n\
+             > error: vertex 9 is not in the network\n\
+             > error: vertex 9 is not in the network\n\
+             > error: unknown measure nothing (available: degree, pagerank, impact, katz, distinct-citers, core-rank)\n\
+             > error: vertex 9 is not in the network\n\
+             > \n"
+        );
+    }
+
+    #[test]
+    fn test_rankings_are_cached() {
+        let mut session = build_session();
+        let output = run_script(&mut session, "profile 3\ntop pagerank 1\n");
+        assert!(output.starts_with("> vertex 3\n  in-degree: 2\n"));
+        assert!(output.contains("  PageRank: "));
+        assert_eq!(session.rankings.len(), 1);
+        // A cached ranking is used even after the network changes
+        let cached = session.rankings["pagerank"].clone();
+        session.network.add_edge(4, 1);
+        run_script(&mut session, "top pagerank\n");
+        assert_eq!(session.rankings["pagerank"], cached);
+        assert!(run_script(&mut session, "help\n").contains("  quit "));
+    }
+}
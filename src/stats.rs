@@ -0,0 +1,265 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use crate::network::CitationNetwork;
+use crate::random::Random;
+
+/// Returns how many connected components of each size the network has
+///
+/// Citations are treated as undirected.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+///
+/// # Returns
+///
+/// * `distribution` - A map from component size to the number of components of that size
+pub fn component_size_distribution(network: &CitationNetwork) -> BTreeMap<usize, usize> {
+    let mut distribution = BTreeMap::new();
+    for component in network.connected_components() {
+        *distribution.entry(component.len()).or_insert(0) += 1;
+    }
+    distribution
+}
+
+/// Describes a component size distribution in one line
+///
+/// For example, "one giant component of 27400 nodes plus 300 smaller components".
+///
+/// # Arguments
+///
+/// * `distribution` - The distribution returned by [`component_size_distribution`]
+pub fn describe_components(distribution: &BTreeMap<usize, usize>) -> String {
+    let Some((&largest, &count)) = distribution.iter().next_back() else {
+        return "no components".to_string();
+    };
+    let others: usize = distribution.values().sum::<usize>() - count;
+    let mut description = if count == 1 {
+        format!("one giant component of {} nodes", largest)
+    } else {
+        format!("{} largest components of {} nodes", count, largest)
+    };
+    if others > 0 {
+        description += &format!(" plus {} smaller components", others);
+    }
+    description
+}
+
+/// A statistic of a whole network that can be compared with a null model
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatisticKind {
+    /// The average local clustering coefficient
+    Clustering,
+    /// The degree assortativity
+    Assortativity,
+    /// The fraction of citations that are reciprocated
+    Reciprocity,
+}
+
+impl StatisticKind {
+    /// Computes the statistic on a network
+    ///
+    /// # Arguments
+    ///
+    /// * `network` - The network to analyze
+    pub fn compute(self, network: &CitationNetwork) -> f64 {
+        match self {
+            StatisticKind::Clustering => network.average_clustering_coefficient(),
+            StatisticKind::Assortativity => network.degree_assortativity(),
+            StatisticKind::Reciprocity => network.reciprocity(),
+        }
+    }
+}
+
+/// How a statistic of a network compares with the same statistic on random networks
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SignificanceResult {
+    /// The value of the statistic on the network
+    pub observed: f64,
+    /// The mean of the statistic over the null samples
+    pub null_mean: f64,
+    /// The population standard deviation of the statistic over the null samples
+    pub null_std: f64,
+    /// The number of null standard deviations the observed value is above the mean
+    pub z_score: f64,
+}
+
+impl Display for SignificanceResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "observed {} against null {} \u{b1} {} (z = {})",
+            self.observed, self.null_mean, self.null_std, self.z_score
+        )
+    }
+}
+
+/// Tests whether a statistic of a network differs from what its degrees alone explain
+///
+/// The null samples are copies of the network rewired with
+/// [`CitationNetwork::rewire_preserving_degrees`], with ten swaps per edge each. If
+/// the null samples all have the same value, the z-score is 0 when the observed
+/// value equals it and infinite otherwise. The same seed always gives the same
+/// result.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `statistic` - The statistic to compare
+/// * `num_null_samples` - The number of rewired networks to compare with
+/// * `seed` - The seed of the random number generator
+///
+/// # Panics
+///
+/// Panics if `num_null_samples` is 0.
+pub fn significance_test(
+    network: &CitationNetwork,
+    statistic: StatisticKind,
+    num_null_samples: usize,
+    seed: u64,
+) -> SignificanceResult {
+    significance_test_with_progress(network, statistic, num_null_samples, seed, &mut |_| {})
+}
+
+/// Tests whether a statistic of a network differs from what its degrees alone
+/// explain, reporting progress
+///
+/// See [`significance_test`]. Rewiring a large network takes a while, so
+/// `on_sample` is called with the number of null samples completed so far after
+/// each one.
+///
+/// # Arguments
+///
+/// * `network` - The network to analyze
+/// * `statistic` - The statistic to compare
+/// * `num_null_samples` - The number of rewired networks to compare with
+/// * `seed` - The seed of the random number generator
+/// * `on_sample` - Called after each null sample
+///
+/// # Panics
+///
+/// Panics if `num_null_samples` is 0.
+pub fn significance_test_with_progress(
+    network: &CitationNetwork,
+    statistic: StatisticKind,
+    num_null_samples: usize,
+    seed: u64,
+    on_sample: &mut dyn FnMut(usize),
+) -> SignificanceResult {
+    assert!(num_null_samples > 0, "at least one null sample is needed");
+    let observed = statistic.compute(network);
+    let mut random = Random::new(seed);
+    let swaps = 10 * network.num_edges();
+    let null_values: Vec<f64> = (1..=num_null_samples)
+        .map(|sample| {
+            let rewired = network.rewire_preserving_degrees(swaps, random.next_u64());
+            let value = statistic.compute(&rewired);
+            on_sample(sample);
+            value
+        })
+        .collect();
+    let n = num_null_samples as f64;
+    let null_mean = null_values.iter().sum::<f64>() / n;
+    let null_std = (null_values
+        .iter()
+        .map(|value| (value - null_mean).powi(2))
+        .sum::<f64>()
+        / n)
+        .sqrt();
+    let z_score = if null_std > 0.0 {
+        (observed - null_mean) / null_std
+    } else if observed == null_mean {
+        0.0
+    } else {
+        (observed - null_mean).signum() * f64::INFINITY
+    };
+    SignificanceResult {
+        observed,
+        null_mean,
+        null_std,
+        z_score,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_network() -> CitationNetwork {
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        network.add_edge(1, 2);
+        network.add_edge(2, 0);
+        network.add_edge(3, 4);
+        network.add_node(5);
+        network.add_node(6);
+        network
+    }
+
+    #[test]
+    fn test_component_size_distribution() {
+        let distribution = component_size_distribution(&build_network());
+        assert_eq!(distribution, BTreeMap::from([(1, 2), (2, 1), (3, 1)]));
+        assert_eq!(
+            describe_components(&distribution),
+            "one giant component of 3 nodes plus 3 smaller components"
+        );
+        assert_eq!(describe_components(&BTreeMap::new()), "no components");
+    }
+
+    #[test]
+    fn test_remove_isolates() {
+        let mut network = build_network();
+        assert_eq!(network.remove_isolates(), 2);
+        assert_eq!(network.size(), 5);
+        assert_eq!(network.num_edges(), 4);
+        assert_eq!(
+            component_size_distribution(&network),
+            BTreeMap::from([(2, 1), (3, 1)])
+        );
+        assert_eq!(network.remove_isolates(), 0);
+    }
+
+    #[test]
+    fn test_significance_of_clustering() {
+        // Twenty cliques of four papers, joined in a ring by one citation each
+        let mut network = CitationNetwork::new();
+        for clique in 0..20 {
+            let base = 4 * clique;
+            for i in 0..4 {
+                for j in i + 1..4 {
+                    network.add_edge(base + i, base + j);
+                }
+            }
+            network.add_edge(base + 3, (base + 4) % 80);
+        }
+        let mut progress = Vec::new();
+        let result = significance_test_with_progress(
+            &network,
+            StatisticKind::Clustering,
+            10,
+            42,
+            &mut |sample| progress.push(sample),
+        );
+        assert_eq!(progress, (1..=10).collect::<Vec<_>>());
+        assert!(result.observed > 0.7);
+        assert!(result.null_mean < result.observed);
+        assert!(result.z_score > 5.0);
+        assert_eq!(
+            significance_test(&network, StatisticKind::Clustering, 10, 42),
+            result
+        );
+    }
+
+    #[test]
+    fn test_significance_without_variation() {
+        // A single citation cannot be rewired
+        let mut network = CitationNetwork::new();
+        network.add_edge(0, 1);
+        let result = significance_test(&network, StatisticKind::Reciprocity, 3, 42);
+        assert_eq!(result.observed, 0.0);
+        assert_eq!(result.null_std, 0.0);
+        assert_eq!(result.z_score, 0.0);
+    }
+}